@@ -37,6 +37,7 @@ struct CloneDelegate<W> {
     refs_directory: Option<PathBuf>,
     ref_filter: Option<&'static [&'static str]>,
     wanted_refs: Vec<BString>,
+    filter: Option<BString>,
 }
 static FILTER: &[&str] = &["HEAD", "refs/tags", "refs/heads"];
 
@@ -108,6 +109,11 @@ impl<W> protocol::fetch::DelegateBlocking for CloneDelegate<W> {
                 arguments.want_ref(r.as_ref())
             }
         }
+        if let Some(filter) = &self.filter {
+            arguments
+                .filter(filter.as_ref())
+                .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+        }
         Ok(Action::Cancel)
     }
 }
@@ -151,6 +157,7 @@ mod blocking_io {
         directory: Option<PathBuf>,
         refs_directory: Option<PathBuf>,
         wanted_refs: Vec<BString>,
+        filter: Option<BString>,
         progress: P,
         ctx: Context<W>,
     ) -> anyhow::Result<()>
@@ -172,6 +179,7 @@ mod blocking_io {
             refs_directory,
             ref_filter: None,
             wanted_refs,
+            filter,
         };
         protocol::fetch(
             transport,
@@ -232,6 +240,7 @@ mod async_io {
         directory: Option<PathBuf>,
         refs_directory: Option<PathBuf>,
         wanted_refs: Vec<BString>,
+        filter: Option<BString>,
         progress: P,
         ctx: Context<W>,
     ) -> anyhow::Result<()>
@@ -254,6 +263,7 @@ mod async_io {
             refs_directory,
             ref_filter: None,
             wanted_refs,
+            filter,
         };
         blocking::unblock(move || {
             futures_lite::future::block_on(protocol::fetch(
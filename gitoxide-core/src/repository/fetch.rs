@@ -5,20 +5,57 @@ use crate::OutputFormat;
 pub struct Options {
     pub format: OutputFormat,
     pub dry_run: bool,
+    /// If set, add to the entries of `FETCH_HEAD` instead of replacing them.
+    pub append: bool,
     pub remote: Option<String>,
     /// If non-empty, override all ref-specs otherwise configured in the remote
     pub ref_specs: Vec<BString>,
+    /// If set, like `refs/mirror/`, rewrite the destination of all fetch ref-specs to land underneath this prefix,
+    /// so `refs/remotes/origin/*` becomes `refs/mirror/remotes/origin/*`.
+    ///
+    /// Tags are only fetched if one of the ref-specs asks for them.
+    pub ref_prefix_override: Option<BString>,
     pub shallow: gix::remote::fetch::Shallow,
     pub handshake_info: bool,
     pub negotiation_info: bool,
     pub open_negotiation_graph: Option<std::path::PathBuf>,
+    /// If set, override `gitoxide.fetch.maxRetries` to retry this many times after transient network errors.
+    pub max_retries: Option<u32>,
+    /// If set, the amount of threads to use for loading local pack indices and for resolving the received pack,
+    /// overriding `pack.threads`.
+    pub jobs: Option<usize>,
 }
 
 pub const PROGRESS_RANGE: std::ops::RangeInclusive<u8> = 1..=3;
 
+/// Read ref-specs from `input`, one per line, ignoring empty lines and lines starting with `#`, and fail on the first
+/// line that isn't a valid fetch ref-spec along with its line number.
+pub fn ref_specs_from_lines(input: impl std::io::BufRead) -> anyhow::Result<Vec<BString>> {
+    use anyhow::Context;
+    use gix::bstr::ByteSlice;
+
+    let mut out = Vec::new();
+    for (line_number, line) in input.split(b'\n').enumerate().map(|(idx, line)| (idx + 1, line)) {
+        let line = line?;
+        let spec = line.trim();
+        if spec.is_empty() || spec.starts_with(b"#") {
+            continue;
+        }
+        gix::refspec::parse(spec.as_bstr(), gix::refspec::parse::Operation::Fetch)
+            .with_context(|| format!("Invalid ref-spec '{}' on line {line_number}", spec.as_bstr()))?;
+        out.push(spec.into());
+    }
+    Ok(out)
+}
+
 pub(crate) mod function {
     use anyhow::bail;
-    use gix::{prelude::ObjectIdExt, refspec::match_group::validate::Fix, remote::fetch::Status};
+    use gix::{
+        bstr::{BStr, BString, ByteSlice},
+        prelude::ObjectIdExt,
+        refspec::{instruction, match_group::validate::Fix, Instruction},
+        remote::fetch::Status,
+    };
     use layout::{
         backends::svg::SVGWriter,
         core::{base::Orientation, geometry::Point, style::StyleAttr},
@@ -32,24 +69,28 @@ pub(crate) mod function {
         repo: gix::Repository,
         mut progress: P,
         mut out: impl std::io::Write,
-        err: impl std::io::Write,
+        mut err: impl std::io::Write,
         Options {
             format,
             dry_run,
+            append,
             remote,
             handshake_info,
             negotiation_info,
             open_negotiation_graph,
+            max_retries,
+            jobs,
             shallow,
             ref_specs,
+            ref_prefix_override,
         }: Options,
     ) -> anyhow::Result<()>
     where
         P: gix::Progress,
         P::SubProgress: 'static,
     {
-        if format != OutputFormat::Human {
-            bail!("JSON output isn't yet supported for fetching.");
+        if format != OutputFormat::Human && (handshake_info || negotiation_info || open_negotiation_graph.is_some()) {
+            bail!("Handshake and negotiation information can't be shown with JSON output.");
         }
 
         let mut remote = crate::repository::remote::by_name_or_url(&repo, remote.as_deref())?;
@@ -57,13 +98,43 @@ pub(crate) mod function {
             remote.replace_refspecs(ref_specs.iter(), gix::remote::Direction::Fetch)?;
             remote = remote.with_fetch_tags(gix::remote::fetch::Tags::None);
         }
+        if let Some(prefix) = ref_prefix_override.as_ref() {
+            let specs = ref_specs_with_prefix(&repo, remote.refspecs(gix::remote::Direction::Fetch), prefix.as_bstr())?;
+            remote.replace_refspecs(specs.iter(), gix::remote::Direction::Fetch)?;
+            remote = remote.with_fetch_tags(gix::remote::fetch::Tags::None);
+            let num_existing = repo
+                .references()?
+                .prefixed(gix::path::from_bstr(prefix.as_bstr()))?
+                .count();
+            if num_existing != 0 {
+                writeln!(
+                    err,
+                    "{num_existing} ref(s) already exist under '{prefix}' and may be updated by this fetch"
+                )?;
+            }
+        }
         let res: gix::remote::fetch::Outcome = remote
             .connect(gix::remote::Direction::Fetch)?
-            .prepare_fetch(&mut progress, Default::default())?
+            .prepare_fetch(
+                &mut progress,
+                gix::remote::ref_map::Options {
+                    max_retries,
+                    ..Default::default()
+                },
+            )?
             .with_dry_run(dry_run)
+            .with_thread_limit(jobs)
+            .with_append_fetch_head(append)
             .with_shallow(shallow)
             .receive(&mut progress, &gix::interrupt::IS_INTERRUPTED)?;
 
+        #[cfg(feature = "serde")]
+        if format == OutputFormat::Json {
+            let ref_specs = remote.refspecs(gix::remote::Direction::Fetch);
+            serde_json::to_writer_pretty(&mut out, &json::Outcome::new(res, ref_specs, dry_run))?;
+            return Ok(());
+        }
+
         if handshake_info {
             writeln!(out, "Handshake Information")?;
             writeln!(out, "\t{:?}", res.ref_map.handshake)?;
@@ -75,6 +146,7 @@ pub(crate) mod function {
                 update_refs,
                 negotiate,
                 dry_run: _,
+                estimate,
             } => {
                 let negotiate_default = Default::default();
                 print_updates(
@@ -86,6 +158,9 @@ pub(crate) mod function {
                     &mut out,
                     err,
                 )?;
+                if let Some(estimate) = estimate {
+                    print_estimate(&mut out, estimate)?;
+                }
                 if negotiation_info {
                     print_negotiate_info(&mut out, negotiate.as_ref())?;
                 }
@@ -123,6 +198,47 @@ pub(crate) mod function {
         Ok(())
     }
 
+    /// Rewrite the destinations of all `specs` which update local refs so they land underneath `prefix`,
+    /// failing if `prefix` isn't suitable or can't be created as existing refs are in the way.
+    fn ref_specs_with_prefix(
+        repo: &gix::Repository,
+        specs: &[gix::refspec::RefSpec],
+        prefix: &BStr,
+    ) -> anyhow::Result<Vec<BString>> {
+        if !prefix.starts_with(b"refs/") || !prefix.ends_with(b"/") || prefix.len() == "refs/".len() {
+            bail!("The ref prefix '{prefix}' must start with 'refs/' and end with '/', like 'refs/mirror/'");
+        }
+        let mut parent = prefix[..prefix.len() - 1].as_bstr();
+        while parent.len() > "refs".len() {
+            if repo.try_find_reference(parent)?.is_some() {
+                bail!("Cannot fetch into '{prefix}' as the reference '{parent}' is in the way");
+            }
+            parent = parent[..parent.rfind_byte(b'/').expect("at least 'refs/'")].as_bstr();
+        }
+
+        Ok(specs
+            .iter()
+            .map(|spec| match spec.to_ref().instruction() {
+                Instruction::Fetch(instruction::Fetch::AndUpdate {
+                    src,
+                    dst,
+                    allow_non_fast_forward,
+                }) => {
+                    let mut out = BString::default();
+                    if allow_non_fast_forward {
+                        out.push(b'+');
+                    }
+                    out.extend_from_slice(src);
+                    out.push(b':');
+                    out.extend_from_slice(prefix);
+                    out.extend_from_slice(dst.strip_prefix(b"refs/").unwrap_or(dst.as_bytes()));
+                    out
+                }
+                _ => spec.to_ref().to_bstring(),
+            })
+            .collect())
+    }
+
     fn render_graph(
         repo: &gix::Repository,
         graph: &gix::negotiate::IdMap,
@@ -197,6 +313,155 @@ pub(crate) mod function {
         }
     }
 
+    fn print_estimate(
+        mut out: impl std::io::Write,
+        estimate: gix::remote::fetch::outcome::Estimate,
+    ) -> std::io::Result<()> {
+        use gix::remote::fetch::outcome::Estimate;
+        match estimate {
+            Estimate::Objects(num_objects) => {
+                writeln!(out, "DRY-RUN: the server would have sent {num_objects} object(s)")
+            }
+            Estimate::RefDelta(num_objects) => writeln!(
+                out,
+                "DRY-RUN: at least {num_objects} object(s) would have been received as remote refs point to them"
+            ),
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    mod json {
+        use gix::{
+            refspec::match_group::validate::Fix,
+            remote::fetch::{outcome, Source, Status},
+        };
+
+        use crate::repository::remote::JsonRef;
+
+        /// The outcome of a fetch, with the same information as is printed in human-readable form.
+        #[derive(serde::Serialize)]
+        pub struct Outcome {
+            pub dry_run: bool,
+            /// The ref updates, one per mapping of a remote ref to a local one, ordered by ref-spec.
+            pub updates: Vec<Update>,
+            /// Destinations that were removed from mappings as they didn't start with `refs/`.
+            pub fixes: Vec<RemovedDestination>,
+            /// The amount of refs the remote sent.
+            pub remote_refs: usize,
+            /// The amount of remote refs that weren't matched by any ref-spec.
+            pub filtered_refs: usize,
+            pub negotiation_rounds: usize,
+            pub estimate: Option<Estimate>,
+            pub pack_path: Option<std::path::PathBuf>,
+            pub index_path: Option<std::path::PathBuf>,
+        }
+
+        #[derive(serde::Serialize)]
+        pub struct Update {
+            pub spec: String,
+            /// If `true`, the ref-spec wasn't configured but implied, like when fetching tags automatically.
+            pub implicit: bool,
+            pub remote: JsonSource,
+            /// The name of the local ref to update, if there is one.
+            pub local: Option<String>,
+            pub mode: String,
+        }
+
+        #[derive(serde::Serialize)]
+        pub enum JsonSource {
+            ObjectId(String),
+            Ref(JsonRef),
+        }
+
+        #[derive(serde::Serialize)]
+        pub struct RemovedDestination {
+            pub spec: String,
+            pub name: String,
+        }
+
+        #[derive(serde::Serialize)]
+        pub enum Estimate {
+            Objects(u32),
+            RefDelta(usize),
+        }
+
+        impl Outcome {
+            pub fn new(
+                outcome: gix::remote::fetch::Outcome,
+                refspecs: &[gix::refspec::RefSpec],
+                dry_run: bool,
+            ) -> Self {
+                let (update_refs, negotiation_rounds, estimate, pack_path, index_path) = match outcome.status {
+                    Status::NoPackReceived {
+                        update_refs,
+                        negotiate,
+                        dry_run: _,
+                        estimate,
+                    } => (
+                        update_refs,
+                        negotiate.map_or(0, |n| n.rounds.len()),
+                        estimate.map(|estimate| match estimate {
+                            outcome::Estimate::Objects(num) => Estimate::Objects(num),
+                            outcome::Estimate::RefDelta(num) => Estimate::RefDelta(num),
+                        }),
+                        None,
+                        None,
+                    ),
+                    Status::Change {
+                        update_refs,
+                        write_pack_bundle,
+                        negotiate,
+                    } => (
+                        update_refs,
+                        negotiate.rounds.len(),
+                        None,
+                        write_pack_bundle.data_path,
+                        write_pack_bundle.index_path,
+                    ),
+                };
+                let map = outcome.ref_map;
+                let mut updates = update_refs
+                    .iter_mapping_updates(&map.mappings, refspecs, &map.extra_refspecs)
+                    .filter_map(|(update, mapping, spec, edit)| spec.map(|spec| (update, mapping, spec, edit)))
+                    .collect::<Vec<_>>();
+                updates.sort_by_key(|t| t.2);
+                let updates = updates
+                    .into_iter()
+                    .map(|(update, mapping, spec, edit)| Update {
+                        spec: spec.to_ref().to_bstring().to_string(),
+                        implicit: mapping.spec_index.implicit_index().is_some(),
+                        remote: match &mapping.remote {
+                            Source::ObjectId(id) => JsonSource::ObjectId(id.to_string()),
+                            Source::Ref(r) => JsonSource::Ref(r.clone().into()),
+                        },
+                        local: edit.map(|edit| edit.name.to_string()),
+                        mode: update.mode.to_string(),
+                    })
+                    .collect();
+                Outcome {
+                    dry_run,
+                    updates,
+                    fixes: map
+                        .fixes
+                        .iter()
+                        .map(|fix| match fix {
+                            Fix::MappingWithPartialDestinationRemoved { name, spec } => RemovedDestination {
+                                spec: spec.to_ref().to_bstring().to_string(),
+                                name: name.to_string(),
+                            },
+                        })
+                        .collect(),
+                    remote_refs: map.remote_refs.len(),
+                    filtered_refs: num_filtered_refs(&map),
+                    negotiation_rounds,
+                    estimate,
+                    pack_path,
+                    index_path,
+                }
+            }
+        }
+    }
+
     pub(crate) fn print_updates(
         repo: &gix::Repository,
         negotiate: &gix::remote::fetch::outcome::Negotiate,
@@ -263,13 +528,17 @@ pub(crate) mod function {
             };
             match edit {
                 Some(edit) => {
-                    writeln!(out, " -> {} [{}]", edit.name, update.mode)
+                    write!(out, " -> {} [{}]", edit.name, update.mode)
                 }
-                None => writeln!(out, " [{}]", update.mode),
+                None => write!(out, " [{}]", update.mode),
+            }?;
+            match update.mode.reason() {
+                Some(reason) => writeln!(out, ": {reason}"),
+                None => writeln!(out),
             }?;
         }
         consume_skipped_tags(&mut skipped_due_to_implicit_tag, &mut out)?;
-        if !map.fixes.is_empty() {
+        if !map.fixes.is_empty() && repo.advice(&gix::config::tree::Advice::FETCH_REMOVED_DESTINATIONS)? {
             writeln!(
                 err,
                 "The following destination refs were removed as they didn't start with 'ref/'"
@@ -294,12 +563,13 @@ pub(crate) mod function {
                 }
             }
         }
-        if map.remote_refs.len() - map.mappings.len() != 0 {
+        let num_filtered_refs = num_filtered_refs(&map);
+        if num_filtered_refs != 0 && repo.advice(&gix::config::tree::Advice::FETCH_FILTERED_TIPS)? {
             writeln!(
                 err,
                 "server sent {} tips, {} were filtered due to {} refspec(s).",
                 map.remote_refs.len(),
-                map.remote_refs.len() - map.mappings.len(),
+                num_filtered_refs,
                 refspecs.len()
             )?;
         }
@@ -310,4 +580,13 @@ pub(crate) mod function {
         }
         Ok(())
     }
+
+    /// Count the remote refs that no mapping refers to, as several ref-specs may map the same remote ref.
+    fn num_filtered_refs(map: &gix::remote::fetch::RefMap) -> usize {
+        let mapped: std::collections::HashSet<_> = map.mappings.iter().filter_map(|m| m.remote.as_name()).collect();
+        map.remote_refs
+            .iter()
+            .filter(|r| !mapped.contains(r.unpack().0))
+            .count()
+    }
 }
@@ -8,6 +8,8 @@ mod access;
 pub mod commit;
 mod init;
 pub mod verify;
+///
+pub mod write;
 
 const COMMIT_DATA_ENTRY_SIZE_SANS_HASH: usize = 16;
 pub(crate) const FAN_LEN: usize = 256;
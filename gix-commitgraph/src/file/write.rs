@@ -0,0 +1,285 @@
+//! Write commit-graph files, either self-contained or on top of a chain of base graphs.
+use std::{collections::HashMap, convert::TryInto, io::Write};
+
+use crate::{
+    file::{
+        BASE_GRAPHS_LIST_CHUNK_ID, COMMIT_DATA_CHUNK_ID, COMMIT_DATA_ENTRY_SIZE_SANS_HASH,
+        EXTENDED_EDGES_LIST_CHUNK_ID, EXTENDED_EDGES_MASK, FAN_LEN, HEADER_LEN, LAST_EXTENDED_EDGE_MASK, NO_PARENT,
+        OID_FAN_CHUNK_ID, OID_LOOKUP_CHUNK_ID, SIGNATURE,
+    },
+    File, Graph, Position, GENERATION_NUMBER_MAX, MAX_COMMITS,
+};
+
+/// The error returned by [`File::write_to()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("Commit {id} refers to parent {parent} which isn't part of the commits to write or of the base graph")]
+    MissingParent {
+        id: gix_hash::ObjectId,
+        parent: gix_hash::ObjectId,
+    },
+    #[error("Commit {id} uses a different hash kind than {expected:?}")]
+    ObjectHashMismatch {
+        id: gix_hash::ObjectId,
+        expected: gix_hash::Kind,
+    },
+    #[error("Cannot write {0} commits, the maximum is {}", MAX_COMMITS)]
+    TooManyCommits(usize),
+    #[error("Cannot write a commit-graph on top of {0} base graphs, the maximum is 255")]
+    TooManyBaseGraphs(usize),
+}
+
+/// A commit with all information needed to place it into a commit-graph file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Commit {
+    /// The id of the commit itself.
+    pub id: gix_hash::ObjectId,
+    /// The id of the commit's root tree.
+    pub tree: gix_hash::ObjectId,
+    /// The ids of all parents of the commit, in order.
+    ///
+    /// Each parent must also be part of the set of commits to write, or of the base graph.
+    pub parents: Vec<gix_hash::ObjectId>,
+    /// The committer time in seconds since the unix epoch.
+    pub committer_timestamp: u64,
+}
+
+/// The result of [`File::write_to()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Outcome {
+    /// The amount of commits that were written.
+    pub num_commits: u32,
+    /// The checksum of the written file, as stored in its trailer.
+    pub checksum: gix_hash::ObjectId,
+}
+
+/// Writing
+impl File {
+    /// Write all `commits` as a single commit-graph file without base graphs into `out`, using `object_hash` for all ids.
+    ///
+    /// Commits may be passed in any order and duplicates are ignored, but the set must be closed, i.e. each parent of
+    /// a commit must be present as well.
+    pub fn write_to(
+        commits: impl IntoIterator<Item = Commit>,
+        out: impl std::io::Write,
+        object_hash: gix_hash::Kind,
+    ) -> Result<Outcome, Error> {
+        write_layer(commits, None, out, object_hash)
+    }
+
+    /// Write all `commits` as a commit-graph file to be placed on top of the chain of files in `base` into `out`,
+    /// using `object_hash` for all ids.
+    ///
+    /// Commits that are already part of `base` are skipped, and parents of commits may be part of `base` or of `commits`.
+    /// Note that the written file is only valid as part of a chain that lists all files of `base` before it.
+    pub fn write_to_with_base(
+        commits: impl IntoIterator<Item = Commit>,
+        base: &Graph,
+        out: impl std::io::Write,
+        object_hash: gix_hash::Kind,
+    ) -> Result<Outcome, Error> {
+        write_layer(commits, Some(base), out, object_hash)
+    }
+}
+
+fn write_layer(
+    commits: impl IntoIterator<Item = Commit>,
+    base: Option<&Graph>,
+    out: impl std::io::Write,
+    object_hash: gix_hash::Kind,
+) -> Result<Outcome, Error> {
+    let mut commits: Vec<_> = commits.into_iter().collect();
+    commits.sort_by(|a, b| a.id.cmp(&b.id));
+    commits.dedup_by(|a, b| a.id == b.id);
+    if let Some(base) = base {
+        commits.retain(|commit| base.lookup(commit.id).is_none());
+    }
+    let num_base_commits = base.map_or(0, Graph::num_commits);
+    if commits.len() + num_base_commits as usize > MAX_COMMITS as usize {
+        return Err(Error::TooManyCommits(commits.len() + num_base_commits as usize));
+    }
+    let base_files = base.map_or(&[][..], |base| base.files.as_slice());
+    let num_base_graphs: u8 = base_files
+        .len()
+        .try_into()
+        .map_err(|_| Error::TooManyBaseGraphs(base_files.len()))?;
+
+    let position_by_id: HashMap<_, _> = commits
+        .iter()
+        .enumerate()
+        .map(|(pos, c)| (c.id, num_base_commits + pos as u32))
+        .collect();
+    let mut parent_positions = Vec::with_capacity(commits.len());
+    for commit in &commits {
+        for id in std::iter::once(&commit.id).chain(Some(&commit.tree)) {
+            if id.kind() != object_hash {
+                return Err(Error::ObjectHashMismatch {
+                    id: *id,
+                    expected: object_hash,
+                });
+            }
+        }
+        parent_positions.push(
+            commit
+                .parents
+                .iter()
+                .map(|parent| {
+                    position_by_id
+                        .get(parent)
+                        .copied()
+                        .or_else(|| base.and_then(|base| base.lookup(parent)).map(|pos| pos.0))
+                        .ok_or(Error::MissingParent {
+                            id: commit.id,
+                            parent: *parent,
+                        })
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+        );
+    }
+    let generations = compute_generations(&parent_positions, num_base_commits, |pos| {
+        base.expect("only commits of the base have positions below its size")
+            .commit_at(Position(pos))
+            .generation()
+    });
+
+    let num_extra_edges: usize = parent_positions
+        .iter()
+        .filter(|parents| parents.len() > 2)
+        .map(|parents| parents.len() - 1)
+        .sum();
+
+    let hash_len = object_hash.len_in_bytes();
+    let mut cf = gix_chunk::file::Index::for_writing();
+    cf.plan_chunk(OID_FAN_CHUNK_ID, (FAN_LEN * 4) as u64);
+    cf.plan_chunk(OID_LOOKUP_CHUNK_ID, (commits.len() * hash_len) as u64);
+    cf.plan_chunk(
+        COMMIT_DATA_CHUNK_ID,
+        (commits.len() * (hash_len + COMMIT_DATA_ENTRY_SIZE_SANS_HASH)) as u64,
+    );
+    if num_extra_edges != 0 {
+        cf.plan_chunk(EXTENDED_EDGES_LIST_CHUNK_ID, (num_extra_edges * 4) as u64);
+    }
+    if num_base_graphs != 0 {
+        cf.plan_chunk(BASE_GRAPHS_LIST_CHUNK_ID, (base_files.len() * hash_len) as u64);
+    }
+
+    let mut out = gix_features::hash::Write::new(out, object_hash);
+    out.write_all(SIGNATURE)?;
+    out.write_all(&[1 /* version */, object_hash as u8])?;
+    out.write_all(&[
+        cf.num_chunks()
+            .try_into()
+            .expect("BUG: we never write more than a handful of chunks"),
+        num_base_graphs,
+    ])?;
+
+    let mut chunk_write = cf.into_write(&mut out, HEADER_LEN)?;
+    while let Some(chunk_to_write) = chunk_write.next_chunk() {
+        match chunk_to_write {
+            OID_FAN_CHUNK_ID => {
+                let mut fan = [0u32; FAN_LEN];
+                for commit in &commits {
+                    fan[commit.id.first_byte() as usize] += 1;
+                }
+                let mut total = 0;
+                for count in fan {
+                    total += count;
+                    chunk_write.write_all(&total.to_be_bytes())?;
+                }
+            }
+            OID_LOOKUP_CHUNK_ID => {
+                for commit in &commits {
+                    chunk_write.write_all(commit.id.as_slice())?;
+                }
+            }
+            COMMIT_DATA_CHUNK_ID => {
+                let mut extra_edge_index = 0;
+                for ((commit, parents), generation) in commits.iter().zip(&parent_positions).zip(&generations) {
+                    chunk_write.write_all(commit.tree.as_slice())?;
+                    let (parent1, parent2) = match parents.as_slice() {
+                        [] => (NO_PARENT, NO_PARENT),
+                        [p1] => (*p1, NO_PARENT),
+                        [p1, p2] => (*p1, *p2),
+                        [p1, rest @ ..] => {
+                            let edge = EXTENDED_EDGES_MASK | extra_edge_index;
+                            extra_edge_index += rest.len() as u32;
+                            (*p1, edge)
+                        }
+                    };
+                    chunk_write.write_all(&parent1.to_be_bytes())?;
+                    chunk_write.write_all(&parent2.to_be_bytes())?;
+                    let time = commit.committer_timestamp & 0x0003_ffff_ffff;
+                    let generation_and_time_high = (generation << 2) | (time >> 32) as u32;
+                    chunk_write.write_all(&generation_and_time_high.to_be_bytes())?;
+                    chunk_write.write_all(&(time as u32).to_be_bytes())?;
+                }
+            }
+            EXTENDED_EDGES_LIST_CHUNK_ID => {
+                for parents in parent_positions.iter().filter(|parents| parents.len() > 2) {
+                    let (last, edges) = parents[1..].split_last().expect("at least two more parents");
+                    for edge in edges {
+                        chunk_write.write_all(&edge.to_be_bytes())?;
+                    }
+                    chunk_write.write_all(&(last | LAST_EXTENDED_EDGE_MASK).to_be_bytes())?;
+                }
+            }
+            BASE_GRAPHS_LIST_CHUNK_ID => {
+                for file in base_files {
+                    chunk_write.write_all(file.checksum().as_slice())?;
+                }
+            }
+            unknown => unreachable!("BUG: forgot to implement chunk {:?}", std::str::from_utf8(&unknown)),
+        }
+    }
+
+    let checksum: gix_hash::ObjectId = out.hash.digest().into();
+    out.inner.write_all(checksum.as_slice())?;
+    Ok(Outcome {
+        num_commits: commits.len() as u32,
+        checksum,
+    })
+}
+
+/// Compute the topological level of each commit, with `parent_positions` listing the parents of each commit by position.
+/// Positions below `num_base_commits` refer to commits of the base graph, whose generation is obtained with `base_generation`.
+fn compute_generations(
+    parent_positions: &[Vec<u32>],
+    num_base_commits: u32,
+    base_generation: impl Fn(u32) -> u32,
+) -> Vec<u32> {
+    const UNKNOWN: u32 = 0;
+    let mut generations = vec![UNKNOWN; parent_positions.len()];
+    let mut stack = Vec::new();
+    for start in 0..parent_positions.len() {
+        if generations[start] != UNKNOWN {
+            continue;
+        }
+        stack.push(start);
+        while let Some(&pos) = stack.last() {
+            let mut max_parent_generation = 0;
+            let mut all_parents_known = true;
+            for parent in &parent_positions[pos] {
+                if *parent < num_base_commits {
+                    max_parent_generation = max_parent_generation.max(base_generation(*parent));
+                    continue;
+                }
+                let parent = (*parent - num_base_commits) as usize;
+                match generations[parent] {
+                    UNKNOWN => {
+                        all_parents_known = false;
+                        stack.push(parent);
+                    }
+                    generation => max_parent_generation = max_parent_generation.max(generation),
+                }
+            }
+            if all_parents_known {
+                generations[pos] = (max_parent_generation + 1).min(GENERATION_NUMBER_MAX);
+                stack.pop();
+            }
+        }
+    }
+    generations
+}
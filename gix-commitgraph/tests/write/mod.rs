@@ -0,0 +1,138 @@
+use gix_commitgraph::{file, Graph};
+
+use crate::{check_common, graph_and_expected};
+
+fn to_write_commit(cg: &Graph, c: file::Commit<'_>) -> file::write::Commit {
+    file::write::Commit {
+        id: c.id().to_owned(),
+        tree: c.root_tree_id().to_owned(),
+        parents: c
+            .iter_parents()
+            .map(|p| cg.id_at(p.expect("valid parent")).to_owned())
+            .collect(),
+        committer_timestamp: c.committer_timestamp(),
+    }
+}
+
+fn rewrite(cg: &Graph) -> (Graph, gix_testtools::tempfile::TempDir) {
+    let commits = cg.iter_commits().map(|c| to_write_commit(cg, c));
+    let tmp = gix_testtools::tempfile::TempDir::new().expect("can create temp dir");
+    let path = tmp.path().join("commit-graph");
+    let outcome = gix_commitgraph::File::write_to(
+        commits,
+        std::fs::File::create(&path).expect("can create file"),
+        gix_hash::Kind::Sha1,
+    )
+    .expect("write succeeds");
+    assert_eq!(outcome.num_commits, cg.num_commits());
+    (Graph::from_file(&path).expect("written graph is valid"), tmp)
+}
+
+#[test]
+fn octopus_merges_round_trip() {
+    let (cg, refs) = graph_and_expected(
+        "octopus_merges.sh",
+        &[
+            "root",
+            "parent1",
+            "parent2",
+            "parent3",
+            "parent4",
+            "three_parents",
+            "four_parents",
+        ],
+    );
+    let (written, _tmp) = rewrite(&cg);
+    check_common(&written, &refs);
+
+    assert_eq!(written.commit_at(refs["root"].pos()).generation(), 1);
+    assert_eq!(written.commit_at(refs["parent4"].pos()).generation(), 2);
+    assert_eq!(written.commit_at(refs["three_parents"].pos()).generation(), 3);
+    assert_eq!(written.commit_at(refs["four_parents"].pos()).generation(), 3);
+}
+
+#[test]
+fn single_parent_round_trip() {
+    let (cg, refs) = graph_and_expected("single_parent.sh", &["parent", "child"]);
+    let (written, _tmp) = rewrite(&cg);
+    check_common(&written, &refs);
+    assert_eq!(written.commit_at(refs["child"].pos()).generation(), 2);
+}
+
+#[test]
+fn layers_on_top_of_a_base_graph_round_trip() {
+    let (cg, refs) = graph_and_expected(
+        "octopus_merges.sh",
+        &[
+            "root",
+            "parent1",
+            "parent2",
+            "parent3",
+            "parent4",
+            "three_parents",
+            "four_parents",
+        ],
+    );
+    let tmp = gix_testtools::tempfile::TempDir::new().expect("can create temp dir");
+    let base_path = tmp.path().join("base.graph");
+    let (base_commits, layer_commits): (Vec<_>, Vec<_>) = cg.iter_commits().partition(|c| c.generation() == 1);
+    gix_commitgraph::File::write_to(
+        base_commits.into_iter().map(|c| to_write_commit(&cg, c)),
+        std::fs::File::create(&base_path).expect("can create file"),
+        gix_hash::Kind::Sha1,
+    )
+    .expect("write succeeds");
+    let base = Graph::from_file(&base_path).expect("written graph is valid");
+
+    let layer_path = tmp.path().join("layer.graph");
+    let outcome = gix_commitgraph::File::write_to_with_base(
+        cg.iter_commits().map(|c| to_write_commit(&cg, c)),
+        &base,
+        std::fs::File::create(&layer_path).expect("can create file"),
+        gix_hash::Kind::Sha1,
+    )
+    .expect("write succeeds");
+    assert_eq!(
+        outcome.num_commits as usize,
+        layer_commits.len(),
+        "commits of the base are skipped"
+    );
+
+    let open = |path| gix_commitgraph::File::at(path).expect("written file is valid");
+    let layer = open(&layer_path);
+    assert_eq!(layer.base_graph_count(), 1);
+    let chain = Graph::new(vec![open(&base_path), layer]).expect("valid chain");
+    chain
+        .verify_integrity(|_| Ok::<_, std::convert::Infallible>(()))
+        .expect("chain is valid");
+    assert_eq!(chain.num_commits(), cg.num_commits());
+    for info in refs.values() {
+        let commit = chain.commit_by_id(info.id()).expect("every commit is present");
+        let expected = cg.commit_by_id(info.id()).expect("present in original");
+        assert_eq!(commit.generation(), expected.generation(), "{}", info.id());
+        assert_eq!(commit.root_tree_id(), info.root_tree_id());
+        let parents: Vec<_> = commit
+            .iter_parents()
+            .map(|p| chain.id_at(p.expect("valid parent")).to_owned())
+            .collect();
+        assert_eq!(parents, info.parent_ids().map(ToOwned::to_owned).collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn missing_parents_are_rejected() {
+    let id = gix_hash::ObjectId::from_hex(b"1111111111111111111111111111111111111111").expect("valid hex");
+    let parent = gix_hash::ObjectId::from_hex(b"2222222222222222222222222222222222222222").expect("valid hex");
+    let err = gix_commitgraph::File::write_to(
+        Some(file::write::Commit {
+            id,
+            tree: gix_hash::Kind::Sha1.null(),
+            parents: vec![parent],
+            committer_timestamp: 0,
+        }),
+        Vec::new(),
+        gix_hash::Kind::Sha1,
+    )
+    .unwrap_err();
+    assert!(matches!(err, file::write::Error::MissingParent { .. }));
+}
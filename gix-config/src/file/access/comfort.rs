@@ -45,6 +45,23 @@ impl<'event> File<'event> {
             .ok()
     }
 
+    /// Like [`string()`][File::string()], but returning `default` if the string wasn't found.
+    pub fn string_or<'a>(
+        &'a self,
+        section_name: impl AsRef<str>,
+        subsection_name: Option<&BStr>,
+        key: impl AsRef<str>,
+        default: &'a BStr,
+    ) -> Cow<'a, BStr> {
+        self.string(section_name, subsection_name, key)
+            .unwrap_or(Cow::Borrowed(default))
+    }
+
+    /// Like [`string_or()`][File::string_or()], but suitable for statically known `key`s like `remote.origin.url`.
+    pub fn string_or_by_key<'a, 'b>(&'a self, key: impl Into<&'b BStr>, default: &'a BStr) -> Cow<'a, BStr> {
+        self.string_by_key(key).unwrap_or(Cow::Borrowed(default))
+    }
+
     /// Like [`value()`][File::value()], but returning `None` if the path wasn't found.
     ///
     /// Note that this path is not vetted and should only point to resources which can't be used
@@ -145,6 +162,40 @@ impl<'event> File<'event> {
         self.boolean_filter(key.section_name, key.subsection_name, key.value_name, filter)
     }
 
+    /// Like [`boolean_by_key()`][File::boolean_by_key()], but for `key`s that are expected to have a single value, like `core.bare`.
+    ///
+    /// If `warn_if_multivar` is `true` and `key` has more than one value, a warning is logged as this typically indicates
+    /// a misconfiguration. The last value wins either way.
+    pub fn boolean_single_by_key<'a>(
+        &self,
+        key: impl Into<&'a BStr>,
+        warn_if_multivar: bool,
+    ) -> Option<Result<bool, value::Error>> {
+        let key = key.into();
+        if warn_if_multivar && self.is_multivar_by_key(key) {
+            log::warn!("Configuration key \"{key}\" is expected to have a single value, but has multiple - the last one is used");
+        }
+        self.boolean_by_key(key)
+    }
+
+    /// Like [`boolean()`][File::boolean()], but returning `default` if the boolean value wasn't found.
+    ///
+    /// Values which are present but can't be parsed as boolean are still an error.
+    pub fn boolean_or(
+        &self,
+        section_name: impl AsRef<str>,
+        subsection_name: Option<&BStr>,
+        key: impl AsRef<str>,
+        default: bool,
+    ) -> Result<bool, value::Error> {
+        self.boolean(section_name, subsection_name, key).unwrap_or(Ok(default))
+    }
+
+    /// Like [`boolean_or()`][File::boolean_or()], but suitable for statically known `key`s like `core.bare`.
+    pub fn boolean_or_by_key<'a>(&self, key: impl Into<&'a BStr>, default: bool) -> Result<bool, value::Error> {
+        self.boolean_by_key(key).unwrap_or(Ok(default))
+    }
+
     /// Like [`value()`][File::value()], but returning an `Option` if the integer wasn't found.
     pub fn integer(
         &self,
@@ -185,6 +236,24 @@ impl<'event> File<'event> {
         self.integer_filter(key.section_name, key.subsection_name, key.value_name, filter)
     }
 
+    /// Like [`integer()`][File::integer()], but returning `default` if the integer wasn't found.
+    ///
+    /// Values which are present but can't be parsed as integer are still an error.
+    pub fn integer_or(
+        &self,
+        section_name: impl AsRef<str>,
+        subsection_name: Option<&BStr>,
+        key: impl AsRef<str>,
+        default: i64,
+    ) -> Result<i64, value::Error> {
+        self.integer(section_name, subsection_name, key).unwrap_or(Ok(default))
+    }
+
+    /// Like [`integer_or()`][File::integer_or()], but suitable for statically known `key`s like `core.deltaBaseCacheLimit`.
+    pub fn integer_or_by_key<'a>(&self, key: impl Into<&'a BStr>, default: i64) -> Result<i64, value::Error> {
+        self.integer_by_key(key).unwrap_or(Ok(default))
+    }
+
     /// Similar to [`values(…)`][File::values()] but returning strings if at least one of them was found.
     pub fn strings(
         &self,
@@ -271,4 +340,58 @@ impl<'event> File<'event> {
         let key = crate::parse::key(key)?;
         self.integers_filter(key.section_name, key.subsection_name, key.value_name, filter)
     }
+
+    /// Like [`value()`][File::value()], but returning `None` if the value wasn't found, and converting it into
+    /// any type `T` that can be created from a byte string.
+    ///
+    /// This allows to read values into custom types through the same entry point as built-in types.
+    /// The conversion error `E` is named separately as it must not depend on the lifetime of the value, which may be
+    /// owned after normalization and thus is only borrowed during the conversion.
+    pub fn value_as<T, E>(
+        &self,
+        section_name: impl AsRef<str>,
+        subsection_name: Option<&BStr>,
+        key: impl AsRef<str>,
+    ) -> Option<Result<T, E>>
+    where
+        T: for<'a> TryFrom<&'a BStr, Error = E>,
+    {
+        self.value_as_filter(section_name, subsection_name, key, &mut |_| true)
+    }
+
+    /// Like [`value_as()`][File::value_as()], but suitable for statically known `key`s like `core.autocrlf`.
+    pub fn value_as_by_key<'a, T, E>(&self, key: impl Into<&'a BStr>) -> Option<Result<T, E>>
+    where
+        T: for<'b> TryFrom<&'b BStr, Error = E>,
+    {
+        self.value_as_filter_by_key(key, &mut |_| true)
+    }
+
+    /// Like [`value_as()`][File::value_as()], but the section containing the returned value must pass `filter` as well.
+    pub fn value_as_filter<T, E>(
+        &self,
+        section_name: impl AsRef<str>,
+        subsection_name: Option<&BStr>,
+        key: impl AsRef<str>,
+        filter: &mut MetadataFilter,
+    ) -> Option<Result<T, E>>
+    where
+        T: for<'a> TryFrom<&'a BStr, Error = E>,
+    {
+        let value = self.raw_value_filter(section_name, subsection_name, key, filter).ok()?;
+        Some(T::try_from(value.as_ref()))
+    }
+
+    /// Like [`value_as_filter()`][File::value_as_filter()], but suitable for statically known `key`s like `core.autocrlf`.
+    pub fn value_as_filter_by_key<'a, T, E>(
+        &self,
+        key: impl Into<&'a BStr>,
+        filter: &mut MetadataFilter,
+    ) -> Option<Result<T, E>>
+    where
+        T: for<'b> TryFrom<&'b BStr, Error = E>,
+    {
+        let key = crate::parse::key(key)?;
+        self.value_as_filter(key.section_name, key.subsection_name, key.value_name, filter)
+    }
 }
@@ -1,6 +1,10 @@
-use std::{borrow::Cow, convert::TryFrom};
+use std::{
+    borrow::Cow,
+    collections::{hash_map::Entry, HashMap},
+    convert::TryFrom,
+};
 
-use bstr::{BStr, ByteSlice};
+use bstr::{BStr, BString, ByteSlice, ByteVec};
 use gix_features::threading::OwnShared;
 use smallvec::SmallVec;
 
@@ -11,7 +15,8 @@ use crate::{
         Metadata, MetadataFilter, SectionId,
     },
     lookup,
-    parse::Event,
+    parse::{section::Key, Event},
+    value::{normalize_bstr, normalize_bstring},
     File,
 };
 
@@ -262,6 +267,90 @@ impl<'event> File<'event> {
         })
     }
 
+    /// Return `true` if `key` in `section_name` and `subsection_name` occurs more than once, no matter in which section
+    /// or file, making it a multivar.
+    ///
+    /// This is useful to detect misconfiguration of keys that are expected to have a single value, as for these
+    /// the last value wins silently.
+    pub fn is_multivar(
+        &self,
+        section_name: impl AsRef<str>,
+        subsection_name: Option<&BStr>,
+        key: impl AsRef<str>,
+    ) -> bool {
+        let key = Key::from_str_unchecked(key.as_ref());
+        self.section_ids_by_name_and_subname(section_name.as_ref(), subsection_name)
+            .map_or(false, |ids| {
+                ids.map(|id| {
+                    self.sections
+                        .get(&id)
+                        .expect("known section id")
+                        .keys()
+                        .filter(|k| **k == key)
+                        .count()
+                })
+                .sum::<usize>()
+                    > 1
+            })
+    }
+
+    /// Like [`is_multivar()`][File::is_multivar()], but suitable for statically known `key`s like `core.bare`.
+    pub fn is_multivar_by_key<'a>(&self, key: impl Into<&'a BStr>) -> bool {
+        crate::parse::key(key).map_or(false, |key| {
+            self.is_multivar(key.section_name, key.subsection_name, key.value_name)
+        })
+    }
+
+    /// Return all values whose full key, like `remote.origin.url`, matches `glob`, along with that key and the metadata of
+    /// the section containing them, in order of occurrence in the file. This is similar to `git config --get-regexp`.
+    ///
+    /// Unlike with `git`, `glob` is a [wildmatch][gix_glob::wildmatch()] pattern instead of a regular expression, and as `*`
+    /// also matches `.`, `remote.*.url` yields the urls of all remotes.
+    /// Keys are matched in their canonical form, that is with section and value names in lower-case and subsection names
+    /// as they are, and keys without a value, like implicit booleans, are skipped.
+    pub fn iter_matching<'a>(
+        &'a self,
+        glob: impl Into<&'a BStr>,
+    ) -> impl Iterator<Item = (BString, Cow<'a, BStr>, &'a Metadata)> + 'a {
+        let glob = glob.into();
+        let mut out = Vec::new();
+        for section in self.sections() {
+            let header = section.header();
+            let mut prefix = BString::from(header.name().to_ascii_lowercase());
+            if let Some(subsection_name) = header.subsection_name() {
+                prefix.push(b'.');
+                prefix.push_str(subsection_name);
+            }
+            prefix.push(b'.');
+
+            let mut key = None;
+            let mut concatenated_value = BString::default();
+            for event in section.body().as_ref() {
+                let value = match event {
+                    Event::SectionKey(section_key) => {
+                        let mut full_key = prefix.clone();
+                        full_key.push_str(section_key.as_ref().to_ascii_lowercase());
+                        key = gix_glob::wildmatch(glob, full_key.as_bstr(), gix_glob::wildmatch::Mode::empty())
+                            .then_some(full_key);
+                        continue;
+                    }
+                    Event::Value(v) if key.is_some() => normalize_bstr(v.as_ref()),
+                    Event::ValueNotDone(v) if key.is_some() => {
+                        concatenated_value.push_str(v.as_ref());
+                        continue;
+                    }
+                    Event::ValueDone(v) if key.is_some() => {
+                        concatenated_value.push_str(v.as_ref());
+                        normalize_bstring(std::mem::take(&mut concatenated_value))
+                    }
+                    _ => continue,
+                };
+                out.push((key.take().expect("checked"), value, section.meta()));
+            }
+        }
+        out.into_iter()
+    }
+
     /// Returns the number of values in the config, no matter in which section.
     ///
     /// For example, a config with multiple empty sections will return 0.
@@ -327,6 +416,51 @@ impl<'event> File<'event> {
         })
     }
 
+    /// Return all sections which occur more than once as tuple of `(name, subsection_name, metadata)`, with `metadata`
+    /// belonging to each occurrence of the section, in order of their first occurrence.
+    ///
+    /// Duplicate sections are valid and their values are merged, but they often are a mistake that leads to surprising
+    /// values, which makes this useful for linting.
+    /// Note that section names are compared case-insensitively, whereas subsection names are case-sensitive.
+    pub fn duplicate_sections(&self) -> Vec<(&BStr, Option<&BStr>, Vec<&Metadata>)> {
+        let mut out: Vec<(&BStr, Option<&BStr>, Vec<&Metadata>)> = Vec::new();
+        let mut index_by_name = HashMap::new();
+        for section in self.sections() {
+            let header = section.header();
+            match index_by_name.entry((header.name().to_ascii_lowercase(), header.subsection_name())) {
+                Entry::Occupied(entry) => out[*entry.get()].2.push(section.meta()),
+                Entry::Vacant(entry) => {
+                    entry.insert(out.len());
+                    out.push((header.name(), header.subsection_name(), vec![section.meta()]));
+                }
+            }
+        }
+        out.retain(|(_, _, metas)| metas.len() > 1);
+        out
+    }
+
+    /// Return all sections grouped by their origin, that is the [source][Metadata::source] and [path][Metadata::path] of their
+    /// [metadata][file::Section::meta()], as tuples of `(metadata, sections)` in order of first occurrence.
+    ///
+    /// `metadata` belongs to the first section of each group. As sections pulled in via `include.path` have the path of the
+    /// included file, they form their own group while sharing the source of the including file.
+    /// This makes it possible to tell which file a value came from, for example to list only values of the local configuration.
+    pub fn sections_by_origin(&self) -> Vec<(&Metadata, Vec<&file::Section<'event>>)> {
+        let mut out: Vec<(&Metadata, Vec<&file::Section<'event>>)> = Vec::new();
+        let mut index_by_origin = HashMap::new();
+        for section in self.sections() {
+            let meta = section.meta();
+            match index_by_origin.entry((meta.source, meta.path.as_deref())) {
+                Entry::Occupied(entry) => out[*entry.get()].1.push(section),
+                Entry::Vacant(entry) => {
+                    entry.insert(out.len());
+                    out.push((meta, vec![section]));
+                }
+            }
+        }
+        out
+    }
+
     /// Return all events which are in front of the first of our sections, or `None` if there are none.
     pub fn frontmatter(&self) -> Option<impl Iterator<Item = &Event<'event>>> {
         (!self.frontmatter_events.is_empty()).then(|| self.frontmatter_events.iter())
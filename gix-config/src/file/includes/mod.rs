@@ -94,36 +94,64 @@ fn append_followed_includes_recursively(
             Some(p) => p,
             None => continue,
         };
-        if !config_path.is_file() {
+        let config_paths = if config_path.is_file() {
+            vec![config_path]
+        } else if options.includes.follow_directories && config_path.is_dir() {
+            files_in_directory_sorted(&config_path)?
+        } else {
             continue;
-        }
-
-        buf.clear();
-        std::io::copy(&mut std::fs::File::open(&config_path)?, buf)?;
-        let config_meta = Metadata {
-            path: Some(config_path),
-            trust: meta.trust,
-            level: meta.level + 1,
-            source: meta.source,
-        };
-        let no_follow_options = init::Options {
-            includes: includes::Options::no_follow(),
-            ..options
         };
 
-        let mut include_config =
-            File::from_bytes_owned(buf, config_meta, no_follow_options).map_err(|err| match err {
-                init::Error::Parse(err) => Error::Parse(err),
-                init::Error::Interpolate(err) => Error::Interpolate(err),
-                init::Error::Includes(_) => unreachable!("BUG: {:?} not possible due to no-follow options", err),
-            })?;
-        resolve_includes_recursive(&mut include_config, depth + 1, buf, options)?;
+        // Combine all files first so they retain their order when inserted past the including section.
+        let mut combined_config: Option<File<'static>> = None;
+        for config_path in config_paths {
+            buf.clear();
+            std::io::copy(&mut std::fs::File::open(&config_path)?, buf)?;
+            let config_meta = Metadata {
+                path: Some(config_path),
+                trust: meta.trust,
+                level: meta.level + 1,
+                source: meta.source,
+            };
+            let no_follow_options = init::Options {
+                includes: includes::Options::no_follow(),
+                ..options
+            };
 
-        target_config.append_or_insert(include_config, Some(section_id));
+            let mut include_config =
+                File::from_bytes_owned(buf, config_meta, no_follow_options).map_err(|err| match err {
+                    init::Error::Parse(err) => Error::Parse(err),
+                    init::Error::Interpolate(err) => Error::Interpolate(err),
+                    init::Error::Includes(_) => unreachable!("BUG: {:?} not possible due to no-follow options", err),
+                })?;
+            resolve_includes_recursive(&mut include_config, depth + 1, buf, options)?;
+
+            match combined_config.as_mut() {
+                Some(combined) => {
+                    combined.append(include_config);
+                }
+                None => combined_config = Some(include_config),
+            }
+        }
+        if let Some(include_config) = combined_config {
+            target_config.append_or_insert(include_config, Some(section_id));
+        }
     }
     Ok(())
 }
 
+fn files_in_directory_sorted(dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_file() {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
 fn detach_include_paths(
     include_paths: &mut Vec<(SectionId, crate::Path<'static>)>,
     section: &file::Section<'_>,
@@ -258,7 +286,10 @@ fn gitdir_matches(
         return Ok(true);
     }
 
-    let expanded_git_dir = gix_path::into_bstr(gix_path::realpath(gix_path::from_byte_slice(&git_dir))?);
+    // The resolved path uses the separators of the platform, so make it comparable to the pattern like the `git_dir` above.
+    let expanded_git_dir = gix_path::to_unix_separators_on_windows(gix_path::into_bstr(gix_path::realpath(
+        gix_path::from_byte_slice(&git_dir),
+    )?));
     Ok(gix_glob::wildmatch(
         pattern_path.as_bstr(),
         expanded_git_dir.as_bstr(),
@@ -38,6 +38,11 @@ pub struct Options<'a> {
     /// If true, default true, configuration not originating from a path will cause errors when trying to resolve
     /// relative include paths (which would require the including configuration's path).
     pub err_on_missing_config_path: bool,
+    /// If true, default false, an include path pointing to a directory will include all files within it in the order
+    /// of their sorted file names, instead of being ignored.
+    ///
+    /// This isn't supported by `git`.
+    pub follow_directories: bool,
     /// Used during path interpolation, both for include paths before trying to read the file, and for
     /// paths used in conditional `gitdir` includes.
     pub interpolate: interpolate::Context<'a>,
@@ -54,6 +59,7 @@ impl<'a> Options<'a> {
             err_on_max_depth_exceeded: false,
             err_on_interpolation_failure: false,
             err_on_missing_config_path: false,
+            follow_directories: false,
             interpolate: Default::default(),
             conditional: Default::default(),
         }
@@ -68,6 +74,7 @@ impl<'a> Options<'a> {
             err_on_max_depth_exceeded: true,
             err_on_interpolation_failure: false,
             err_on_missing_config_path: true,
+            follow_directories: false,
             interpolate,
             conditional,
         }
@@ -91,6 +98,7 @@ impl<'a> Options<'a> {
             err_on_max_depth_exceeded: true,
             err_on_interpolation_failure: false,
             err_on_missing_config_path: true,
+            follow_directories: false,
             interpolate: interpolate::Context {
                 git_install_dir: None,
                 home_dir,
@@ -100,6 +108,14 @@ impl<'a> Options<'a> {
         }
     }
 
+    /// If `enabled`, include paths that point to a directory cause all files in it to be included, sorted by file name.
+    ///
+    /// Note that this isn't supported by `git` and thus off by default.
+    pub fn follow_directories(mut self, enabled: bool) -> Self {
+        self.follow_directories = enabled;
+        self
+    }
+
     /// Set the context used for interpolation when interpolating paths to include as well as the paths
     /// in `gitdir` conditional includes.
     pub fn interpolate_with(mut self, context: interpolate::Context<'a>) -> Self {
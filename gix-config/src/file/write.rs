@@ -68,6 +68,18 @@ impl File<'_> {
     pub fn write_to(&self, out: impl std::io::Write) -> std::io::Result<()> {
         self.write_to_filter(out, |_| true)
     }
+
+    /// Like [`write_to()`][Self::write_to()], but skip all sections that were loaded from included files, leaving only
+    /// sections of files that were loaded directly along with their `include` and `includeIf` sections.
+    ///
+    /// This allows to write back changes to a configuration file without inlining the files it includes.
+    /// Note that changes should then be made with the `*_filter()` methods, like
+    /// [`set_raw_value_filter()`][Self::set_raw_value_filter()], to only affect sections with a
+    /// [level][crate::file::Metadata::level] of 0. Otherwise they may be made to sections of included files,
+    /// which aren't written.
+    pub fn write_to_without_includes(&self, out: impl std::io::Write) -> std::io::Result<()> {
+        self.write_to_filter(out, |section| section.meta().level == 0)
+    }
 }
 
 pub(crate) fn ends_with_newline(e: &[crate::parse::Event<'_>], nl: impl AsRef<[u8]>, default: bool) -> bool {
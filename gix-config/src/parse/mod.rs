@@ -28,6 +28,8 @@ pub mod section;
 ///
 mod key;
 pub use key::{parse_unvalidated as key, Key};
+///
+pub mod validated_key;
 
 #[cfg(test)]
 pub(crate) mod tests;
@@ -0,0 +1,51 @@
+use bstr::{BStr, BString, ByteSlice};
+
+use crate::parse::Key;
+
+/// The error returned by [`parse()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Key {input:?} must consist of a section name and a key name separated by a dot, like 'core.bare'")]
+    MissingDot { input: BString },
+    #[error("Section name {name:?} must consist of alphanumeric characters or dashes")]
+    InvalidSectionName { name: BString },
+    #[error("Subsection name {name:?} must not contain newlines or null bytes")]
+    InvalidSubsectionName { name: BString },
+    #[error("Subsection name {name:?} has a quote only on one side")]
+    UnbalancedQuotes { name: BString },
+    #[error("Key name {name:?} must start with a letter followed by alphanumeric characters or dashes")]
+    InvalidKeyName { name: BString },
+}
+
+/// Parse `input` like `core.bare`, `remote.origin.url` or `remote.my.sub.url` into a validated `Key`, as suitable for
+/// keys passed on the command-line like `git config remote.origin.url`.
+///
+/// The section name ends at the first dot and the key name starts after the last one, so everything in between is the
+/// subsection name which may thus contain dots itself. The subsection may be quoted, like in `remote."my.sub".url`,
+/// in which case the quotes are removed.
+pub fn parse(input: &BStr) -> Result<Key<'_>, Error> {
+    let key = crate::parse::key(input).ok_or_else(|| Error::MissingDot { input: input.into() })?;
+
+    crate::parse::section::Name::try_from(key.section_name).map_err(|_| Error::InvalidSectionName {
+        name: key.section_name.into(),
+    })?;
+    crate::parse::section::Key::try_from(key.value_name).map_err(|_| Error::InvalidKeyName {
+        name: key.value_name.into(),
+    })?;
+    let subsection_name = match key.subsection_name {
+        Some(name) => {
+            let name = match (name.first(), name.last()) {
+                (Some(b'"'), Some(b'"')) if name.len() > 1 => name[1..name.len() - 1].as_bstr(),
+                (Some(b'"'), _) | (_, Some(b'"')) => return Err(Error::UnbalancedQuotes { name: name.into() }),
+                _ => name,
+            };
+            if !crate::parse::section::header::is_valid_subsection(name) {
+                return Err(Error::InvalidSubsectionName { name: name.into() });
+            }
+            Some(name)
+        }
+        None => None,
+    };
+    Ok(Key { subsection_name, ..key })
+}
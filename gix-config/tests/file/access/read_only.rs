@@ -1,6 +1,6 @@
 use std::{borrow::Cow, convert::TryFrom};
 
-use bstr::BStr;
+use bstr::{BStr, ByteSlice};
 use gix_config::{
     color,
     file::{init, Metadata},
@@ -193,6 +193,76 @@ fn get_value_looks_up_all_sections_before_failing() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn value_as_converts_into_custom_types() -> crate::Result {
+    #[derive(Debug, PartialEq, Eq)]
+    enum AutoCrlf {
+        Enabled,
+        Disabled,
+        Input,
+    }
+
+    impl TryFrom<&BStr> for AutoCrlf {
+        type Error = String;
+
+        fn try_from(value: &BStr) -> Result<Self, Self::Error> {
+            Ok(match value.to_str_lossy().as_ref() {
+                "true" => AutoCrlf::Enabled,
+                "false" => AutoCrlf::Disabled,
+                "input" => AutoCrlf::Input,
+                other => return Err(format!("invalid value: {other:?}")),
+            })
+        }
+    }
+
+    let mut file = File::from_bytes_no_includes(
+        b"[core]\n\tautocrlf = input\n\tinvalid = yes",
+        Metadata::api(),
+        Default::default(),
+    )?;
+    assert_eq!(
+        file.value_as::<AutoCrlf, _>("core", None, "autocrlf").transpose()?,
+        Some(AutoCrlf::Input)
+    );
+    assert_eq!(
+        file.value_as_by_key::<AutoCrlf, _>("core.autocrlf").transpose()?,
+        Some(AutoCrlf::Input)
+    );
+    assert_eq!(
+        file.value_as_by_key::<AutoCrlf, _>("core.invalid"),
+        Some(Err("invalid value: \"yes\"".into())),
+        "conversion errors are passed through as is"
+    );
+    assert_eq!(file.value_as_by_key::<AutoCrlf, _>("core.missing"), None);
+
+    file.append(File::from_bytes_no_includes(
+        b"[core]\n\tautocrlf = true",
+        Metadata {
+            trust: gix_sec::Trust::Reduced,
+            ..Metadata::api()
+        },
+        Default::default(),
+    )?);
+    assert_eq!(
+        file.value_as_by_key::<AutoCrlf, _>("core.autocrlf").transpose()?,
+        Some(AutoCrlf::Enabled),
+        "the last value wins"
+    );
+    let mut trusted_only = |meta: &Metadata| meta.trust == gix_sec::Trust::Full;
+    assert_eq!(
+        file.value_as_filter_by_key::<AutoCrlf, _>("core.autocrlf", &mut trusted_only)
+            .transpose()?,
+        Some(AutoCrlf::Input),
+        "filters can exclude untrusted sections"
+    );
+    assert_eq!(
+        file.value_as_filter::<AutoCrlf, _>("core", None, "autocrlf", &mut trusted_only)
+            .transpose()?,
+        Some(AutoCrlf::Input)
+    );
+    Ok(())
+}
+
 #[test]
 fn section_names_are_case_insensitive() -> crate::Result {
     let config = "[core] a=true";
@@ -256,6 +326,144 @@ fn sections_by_name() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn duplicate_sections() -> crate::Result {
+    let mut file = File::from_bytes_no_includes(
+        b"[remote \"origin\"]\n\turl = first\n[core]\n[remote \"upstream\"]\n[REMOTE \"Origin\"]",
+        Metadata::api(),
+        Default::default(),
+    )?;
+    assert_eq!(
+        file.duplicate_sections().len(),
+        0,
+        "subsection names are case-sensitive, so there are no duplicates yet"
+    );
+
+    file.append(File::from_bytes_no_includes(
+        b"[Remote \"origin\"]\n\turl = second",
+        Metadata {
+            level: 1,
+            ..Metadata::api()
+        },
+        Default::default(),
+    )?);
+    let duplicates = file.duplicate_sections();
+    assert_eq!(duplicates.len(), 1, "only the origin remote is doubled");
+    let (name, subsection_name, metas) = &duplicates[0];
+    assert_eq!(*name, "remote", "the name of the first occurrence is used");
+    assert_eq!(*subsection_name, Some("origin".into()));
+    assert_eq!(
+        metas.iter().map(|meta| meta.level).collect::<Vec<_>>(),
+        [0, 1],
+        "the metadata of each occurrence is provided"
+    );
+    assert_eq!(
+        file.strings_by_key("remote.origin.url").expect("present"),
+        [cow_str("first"), cow_str("second")],
+        "values are still merged"
+    );
+    Ok(())
+}
+
+#[test]
+fn sections_by_origin() -> crate::Result {
+    let dir = tempfile::tempdir()?;
+    let global_path = dir.path().join("global");
+    let local_path = dir.path().join("local");
+    let included_path = dir.path().join("included");
+    std::fs::write(&global_path, "[user]\n\tname = global\n[core]\n\tbare = false")?;
+    std::fs::write(
+        &local_path,
+        "[core]\n\tbare = true\n[include]\n\tpath = included\n[remote \"origin\"]\n\turl = local",
+    )?;
+    std::fs::write(&included_path, "[user]\n\temail = included@example.com")?;
+
+    let config = File::from_paths_metadata(
+        [
+            Metadata::try_from_path(&global_path, gix_config::Source::User)?,
+            Metadata::try_from_path(&local_path, gix_config::Source::Local)?,
+        ],
+        init::Options {
+            includes: gix_config::file::includes::Options::follow(Default::default(), Default::default()),
+            ..Default::default()
+        },
+    )?
+    .expect("non-empty");
+
+    let origins = config.sections_by_origin();
+    assert_eq!(
+        origins
+            .iter()
+            .map(|(meta, sections)| (meta.source, meta.path.clone(), meta.level, sections.len()))
+            .collect::<Vec<_>>(),
+        [
+            (gix_config::Source::User, Some(global_path), 0, 2),
+            (gix_config::Source::Local, Some(local_path), 0, 3),
+            (gix_config::Source::Local, Some(included_path), 1, 1),
+        ],
+        "sections are grouped by file, with included files being their own group while inheriting the source"
+    );
+
+    let values_by_origin = |idx: usize| {
+        origins[idx]
+            .1
+            .iter()
+            .filter(|section| section.header().name() != "include")
+            .flat_map(|section| {
+                section
+                    .body()
+                    .keys()
+                    .map(move |key| format!("{}.{key}", section.header().name()))
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(values_by_origin(0), ["user.name", "core.bare"]);
+    assert_eq!(
+        values_by_origin(1),
+        ["core.bare", "remote.url"],
+        "only values of the local file are listed"
+    );
+    assert_eq!(
+        values_by_origin(2),
+        ["user.email"],
+        "included values are attributed to the included file"
+    );
+    Ok(())
+}
+
+#[test]
+fn values_with_default() -> crate::Result {
+    let config = File::try_from("[core]\n\tbare = true\n\tinvalid-bool = maybe\n\tnum = 42\n\tinvalid-num = many")?;
+
+    assert!(
+        config.boolean_or("core", None, "missing", true)?,
+        "missing values use the default"
+    );
+    assert!(!config.boolean_or("core", None, "missing", false)?);
+    assert!(
+        !config.boolean_or("missing", None, "bare", false)?,
+        "so do missing sections"
+    );
+    assert!(config.boolean_or("core", None, "bare", false)?, "present values win");
+    assert!(config.boolean_or_by_key("core.bare", false)?);
+    assert!(
+        config.boolean_or("core", None, "invalid-bool", true).is_err(),
+        "present but invalid values are an error despite the default"
+    );
+    assert!(config.boolean_or_by_key("core.invalid-bool", true).is_err());
+
+    assert_eq!(config.integer_or("core", None, "missing", 7)?, 7);
+    assert_eq!(config.integer_or_by_key("core.num", 7)?, 42);
+    assert!(config.integer_or("core", None, "invalid-num", 7).is_err());
+
+    assert_eq!(
+        config.string_or("core", None, "missing", "default".into()),
+        cow_str("default")
+    );
+    assert_eq!(config.string_or_by_key("core.num", "default".into()), cow_str("42"));
+    Ok(())
+}
+
 #[test]
 fn unknown_section() -> crate::Result {
     let config = File::default();
@@ -398,3 +606,117 @@ fn overrides_with_implicit_booleans_work_across_sections() {
         "empty implicit booleans "
     );
 }
+
+#[test]
+fn iter_matching_yields_all_matching_keys_in_order() -> crate::Result {
+    let config = File::try_from(
+        r#"
+        [remote "origin"]
+            url = https://example.com/origin
+            fetch = +refs/heads/*:refs/remotes/origin/*
+        [core]
+            bare
+        [Remote "Fork"]
+            URL = "https://example.com/\
+fork"
+        [remote "origin"]
+            pushurl = https://example.com/push
+            url = https://example.com/other
+        "#,
+    )?;
+
+    let actual: Vec<_> = config
+        .iter_matching("remote.*.url")
+        .map(|(key, value, meta)| {
+            assert_eq!(meta.source, gix_config::Source::Api);
+            format!("{key}={value}")
+        })
+        .collect();
+    assert_eq!(
+        actual,
+        [
+            "remote.origin.url=https://example.com/origin",
+            "remote.Fork.url=https://example.com/fork",
+            "remote.origin.url=https://example.com/other",
+        ],
+        "section and value names are lower-cased, subsections keep their case, and values are normalized"
+    );
+    assert_eq!(
+        config.iter_matching("core.*").count(),
+        0,
+        "implicit values can't be represented and are skipped"
+    );
+    assert_eq!(config.iter_matching("*").count(), 5);
+    Ok(())
+}
+
+mod multivar {
+    use std::{convert::TryFrom, sync::Mutex};
+
+    use gix_config::File;
+
+    static WARNINGS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    struct CollectWarnings;
+
+    impl log::Log for CollectWarnings {
+        fn enabled(&self, metadata: &log::Metadata<'_>) -> bool {
+            metadata.level() == log::Level::Warn
+        }
+
+        fn log(&self, record: &log::Record<'_>) {
+            if self.enabled(record.metadata()) {
+                WARNINGS.lock().unwrap().push(record.args().to_string());
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    fn warnings_about(key: &str) -> usize {
+        WARNINGS
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|warning| warning.contains(key))
+            .count()
+    }
+
+    #[test]
+    fn is_multivar_counts_values_across_sections() -> crate::Result {
+        let config = File::try_from("[core]\nbare = true\n[core]\nbare\n[remote \"origin\"]\nfetch = a\n")?;
+        assert!(config.is_multivar_by_key("core.bare"), "implicit values count as well");
+        assert!(config.is_multivar("CORE", None, "Bare"), "names are case-insensitive");
+        assert!(!config.is_multivar_by_key("remote.origin.fetch"));
+        assert!(!config.is_multivar_by_key("core.missing"));
+        assert!(!config.is_multivar_by_key("missing.key"));
+        Ok(())
+    }
+
+    #[test]
+    fn single_valued_keys_with_multiple_values_warn_and_the_last_one_wins() -> crate::Result {
+        log::set_logger(&CollectWarnings).ok();
+        log::set_max_level(log::LevelFilter::Warn);
+
+        let config = File::try_from("[core]\nbare = true\n[core]\nbare = false\nignoreCase = true\n")?;
+        assert_eq!(
+            config.boolean_single_by_key("core.ignoreCase", true).transpose()?,
+            Some(true)
+        );
+        assert_eq!(warnings_about("core.ignoreCase"), 0, "single values don't warn");
+
+        assert_eq!(
+            config.boolean_single_by_key("core.bare", false).transpose()?,
+            Some(false)
+        );
+        assert_eq!(warnings_about("core.bare"), 0, "warnings can be turned off");
+
+        assert_eq!(
+            config.boolean_single_by_key("core.bare", true).transpose()?,
+            Some(false),
+            "the last value wins"
+        );
+        assert_eq!(warnings_about("core.bare"), 1);
+        Ok(())
+    }
+}
@@ -30,6 +30,15 @@ fn tilde_slash_expands_the_current_user_home() -> crate::Result {
     assert_section_value(Condition::new("gitdir:~/subdir/worktree/"), env)
 }
 
+#[test]
+fn tilde_slash_with_subdirectory_matches_all_repositories_below_it() -> crate::Result {
+    let env = GitEnv::repo_name(std::path::Path::new("work").join("project"))?;
+    assert_section_value(Condition::new("gitdir:~/work/"), env)?;
+
+    let env = GitEnv::repo_name(std::path::Path::new("play").join("project"))?;
+    assert_section_value(Condition::new("gitdir:~/work/").expect_original_value(), env)
+}
+
 #[test]
 fn tilde_alone_does_not_match_even_if_home_is_git_directory() -> crate::Result {
     let env = GitEnv::repo_in_home()?;
@@ -244,6 +253,113 @@ fn dot_path_matching_symlink_with_icase() -> crate::Result {
     )
 }
 
+#[test]
+fn symlinked_git_dir_pointing_outside_of_the_tree_matches_its_target() -> crate::Result {
+    let dir = tempfile::tempdir()?;
+    let target = dir.path().join("outside").join("repo");
+    std::fs::create_dir_all(target.join(".git"))?;
+    let link = dir.path().join("inside").join("link");
+    super::create_symlink(&link, &target);
+
+    let include_path = dir.path().join("include.config");
+    std::fs::write(&include_path, "[section]\n  value = override-value")?;
+    let config_path = dir.path().join("config");
+    let git_dir = link.join(".git");
+    for (pattern, expected) in [
+        ("inside/link/", "override-value"),
+        ("outside/repo/", "override-value"),
+        ("outside/other/", "base-value"),
+    ] {
+        std::fs::write(
+            &config_path,
+            format!(
+                "[section]\n  value = base-value\n[includeIf \"gitdir:{pattern}\"]\n  path = {}",
+                escape_backslashes(&include_path)
+            ),
+        )?;
+        let meta = gix_config::file::Metadata::try_from_path(&config_path, gix_config::Source::Api)?;
+        let config = gix_config::File::from_paths_metadata(Some(meta), super::options_with_git_dir(&git_dir))?
+            .expect("non-empty");
+        assert_eq!(
+            config.string("section", None, "value").as_deref(),
+            Some(expected.into()),
+            "both the path through the symlink and its resolved target can be matched, on all platforms: {pattern}"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn interpolated_patterns_match_like_their_expansion() -> crate::Result {
+    use gix_config::{
+        file::{includes, init},
+        path,
+    };
+
+    thread_local! {
+        static USER_HOME: std::cell::RefCell<Option<std::path::PathBuf>> = Default::default();
+    }
+    fn home_for_user(name: &str) -> Option<std::path::PathBuf> {
+        (name == "user")
+            .then(|| USER_HOME.with(|home| home.borrow().clone()))
+            .flatten()
+    }
+
+    let dir = tempfile::tempdir()?;
+    let root = dir.path();
+    USER_HOME.with(|home| *home.borrow_mut() = Some(root.to_owned()));
+    let git_dir = root.join("repos").join("project").join(".git");
+    std::fs::create_dir_all(&git_dir)?;
+
+    let include_path = root.join("include.config");
+    std::fs::write(&include_path, "[section]\n  value = override-value")?;
+    let config_path = root.join("config");
+    let mut cases = vec![
+        ("~/repos/", "override-value"),
+        ("~/repos/**", "override-value"),
+        ("~/other/", "base-value"),
+        ("%(prefix)/repos/", "override-value"),
+        ("%(prefix)/repos/project/.git", "override-value"),
+        ("%(prefix)/repos/**", "override-value"),
+        ("%(prefix)/other/**", "base-value"),
+        ("$HOME/repos/", "base-value"),
+    ];
+    if cfg!(not(any(windows, target_os = "android"))) {
+        cases.extend([("~user/repos/**", "override-value"), ("~user/other/", "base-value")]);
+    }
+    for (pattern, expected) in cases {
+        std::fs::write(
+            &config_path,
+            format!(
+                "[section]\n  value = base-value\n[includeIf \"gitdir:{pattern}\"]\n  path = {}",
+                escape_backslashes(&include_path)
+            ),
+        )?;
+        let options = init::Options {
+            includes: includes::Options::follow(
+                path::interpolate::Context {
+                    git_install_dir: Some(root),
+                    home_dir: Some(root),
+                    home_for_user: Some(home_for_user),
+                },
+                includes::conditional::Context {
+                    git_dir: Some(&git_dir),
+                    ..Default::default()
+                },
+            ),
+            ..Default::default()
+        };
+        let meta = gix_config::file::Metadata::try_from_path(&config_path, gix_config::Source::Api)?;
+        let config = gix_config::File::from_paths_metadata(Some(meta), options)?.expect("non-empty");
+        assert_eq!(
+            config.string("section", None, "value").as_deref(),
+            Some(expected.into()),
+            "{pattern:?} is interpolated before matching, but environment variables like $HOME are not, just like in git"
+        );
+    }
+    Ok(())
+}
+
 fn original_value_on_windows(c: Condition) -> Condition {
     if cfg!(windows) {
         c.expect_original_value()
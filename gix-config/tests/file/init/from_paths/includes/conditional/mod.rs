@@ -121,6 +121,48 @@ fn include_and_includeif_correct_inclusion_order_and_delayed_resolve_include() -
     Ok(())
 }
 
+#[test]
+fn dot_slash_gitdir_pattern_in_included_file_is_relative_to_the_included_file() -> crate::Result {
+    let dir = tempdir()?;
+    let root_dir = dir.path().join("root");
+    let nested_dir = dir.path().join("nested");
+    fs::create_dir_all(root_dir.join("repo").join(".git"))?;
+    fs::create_dir_all(nested_dir.join("repo").join(".git"))?;
+
+    let value_path = nested_dir.join("value");
+    fs::write(value_path.as_path(), "[section]\n  value = included-conditionally")?;
+    let included_path = nested_dir.join("included");
+    fs::write(
+        included_path.as_path(),
+        format!(
+            "[includeIf \"gitdir:./repo/\"]\n  path = {}",
+            escape_backslashes(&value_path)
+        ),
+    )?;
+    let config_path = root_dir.join("config");
+    fs::write(
+        config_path.as_path(),
+        format!(
+            "[section]\n  value = base\n[include]\n  path = {}",
+            escape_backslashes(&included_path)
+        ),
+    )?;
+
+    for (git_dir, expected) in [
+        (nested_dir.join("repo").join(".git"), "included-conditionally"),
+        (root_dir.join("repo").join(".git"), "base"),
+    ] {
+        let meta = gix_config::file::Metadata::try_from_path(&config_path, gix_config::Source::Api)?;
+        let config = File::from_paths_metadata(Some(meta), options_with_git_dir(&git_dir))?.expect("non-empty");
+        assert_eq!(
+            config.string("section", None, "value"),
+            Some(cow_str(expected)),
+            "'./' is anchored at the directory of the file containing the condition, not the one of the including file"
+        );
+    }
+    Ok(())
+}
+
 fn options_with_git_dir(git_dir: &Path) -> init::Options<'_> {
     init::Options {
         includes: includes::Options::follow(
@@ -156,5 +198,5 @@ fn create_symlink(from: impl AsRef<Path>, to: impl AsRef<Path>) {
     #[cfg(not(windows))]
     std::os::unix::fs::symlink(to, from).unwrap();
     #[cfg(windows)]
-    std::os::windows::fs::symlink_file(to, from).unwrap();
+    std::os::windows::fs::symlink_dir(to, from).unwrap();
 }
@@ -173,6 +173,69 @@ fn double_star_globs_cross_component_boundaries() -> Result {
     Ok(())
 }
 
+#[test]
+fn patterns_ending_with_slash_do_not_match_the_branch_of_the_same_name() -> Result {
+    let mut env = GitEnv::new()?;
+    env = assert_section_value(
+        Options {
+            condition: "main/",
+            branch_name: "refs/heads/main/foo",
+            expect: Value::OverrideByInclude,
+        },
+        env,
+    )?;
+    assert_section_value_msg(
+        Options {
+            condition: "main/",
+            branch_name: "refs/heads/main",
+            expect: Value::Base,
+        },
+        env,
+        "the trailing slash is expanded to 'main/**', which needs at least one more component".into(),
+    )?;
+    Ok(())
+}
+
+#[test]
+fn trailing_globs_match_one_or_all_levels_of_a_hierarchy() -> Result {
+    let mut env = GitEnv::new()?;
+    env = assert_section_value(
+        Options {
+            condition: "release/*",
+            branch_name: "refs/heads/release/1.0",
+            expect: Value::OverrideByInclude,
+        },
+        env,
+    )?;
+    env = assert_section_value_msg(
+        Options {
+            condition: "release/*",
+            branch_name: "refs/heads/release/1.0/hotfix",
+            expect: Value::Base,
+        },
+        env,
+        "single stars match a single component only".into(),
+    )?;
+    env = assert_section_value(
+        Options {
+            condition: "feature/**",
+            branch_name: "refs/heads/feature/a/b",
+            expect: Value::OverrideByInclude,
+        },
+        env,
+    )?;
+    assert_section_value_msg(
+        Options {
+            condition: "Feature/**",
+            branch_name: "refs/heads/feature/a",
+            expect: Value::Base,
+        },
+        env,
+        "like in git, branch names are matched case-sensitively".into(),
+    )?;
+    Ok(())
+}
+
 enum Value {
     Base,
     OverrideByInclude,
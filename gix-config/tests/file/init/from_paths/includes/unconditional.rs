@@ -324,3 +324,52 @@ fn nested() -> crate::Result {
     assert_eq!(config.boolean("core", None, "a"), Some(Ok(false)));
     Ok(())
 }
+
+#[test]
+fn directories_are_only_followed_if_enabled_and_include_files_sorted_by_name() -> crate::Result {
+    let dir = tempdir()?;
+    let include_dir = dir.path().join("config.d");
+    fs::create_dir(&include_dir)?;
+    fs::write(
+        include_dir.join("b.inc"),
+        "
+[core]
+  i = 2
+  b = true",
+    )?;
+    fs::write(
+        include_dir.join("a.inc"),
+        "
+[core]
+  i = 1",
+    )?;
+
+    let a_path = dir.path().join("a");
+    fs::write(
+        a_path.as_path(),
+        format!(
+            "
+[core]
+  i = 0
+[include]
+  path = {}",
+            escape_backslashes(&include_dir),
+        ),
+    )?;
+
+    let config = File::from_paths_metadata(into_meta(vec![a_path.clone()]), follow_options())?.expect("non-empty");
+    assert_eq!(
+        config.integers("core", None, "i"),
+        Some(Ok(vec![0])),
+        "directories are ignored by default, just like git does"
+    );
+
+    let options = init::Options {
+        includes: follow_options().includes.follow_directories(true),
+        ..Default::default()
+    };
+    let config = File::from_paths_metadata(into_meta(vec![a_path]), options)?.expect("non-empty");
+    assert_eq!(config.integers("core", None, "i"), Some(Ok(vec![0, 1, 2])));
+    assert_eq!(config.boolean("core", None, "b"), Some(Ok(true)));
+    Ok(())
+}
@@ -109,6 +109,28 @@ fn complex_lossless_roundtrip() {
     );
 }
 
+#[test]
+fn values_with_continuation_lines_roundtrip() {
+    let input =
+        "[blame]\n\tignoreRevsFile = first \\\nsecond \\\nthird ; comment\n\tquoted = \"one \\\ntwo \\\nthree\"\n";
+    let config = gix_config::File::try_from(input).unwrap();
+    assert_eq!(
+        config.string_by_key("blame.ignoreRevsFile").expect("present").as_ref(),
+        "first second third",
+        "the backslash and the newline are removed, joining all three lines"
+    );
+    assert_eq!(
+        config.string_by_key("blame.quoted").expect("present").as_ref(),
+        "one two three",
+        "continuations also work within quotes"
+    );
+    assert_eq!(
+        config.to_bstring(),
+        input,
+        "the value is written back exactly as it was read"
+    );
+}
+
 mod to_filter {
     use bstr::ByteSlice;
     use gix_config::file::Metadata;
@@ -144,3 +166,55 @@ mod to_filter {
         Ok(())
     }
 }
+
+mod without_includes {
+    use bstr::ByteSlice;
+    use gix_config::file::{includes, init, Metadata};
+
+    use crate::file::cow_str;
+
+    #[test]
+    fn include_directives_are_kept_and_included_values_are_not_inlined() -> crate::Result {
+        let dir = tempfile::tempdir()?;
+        let root_path = dir.path().join("config");
+        std::fs::write(
+            &root_path,
+            "[core]\n\ta = base\n[include]\n\tpath = included.config\n[user]\n\tname = local\n",
+        )?;
+        std::fs::write(
+            dir.path().join("included.config"),
+            "[core]\n\ta = included\n\tb = only-included\n",
+        )?;
+
+        let mut config = gix_config::File::from_paths_metadata(
+            Some(Metadata::try_from_path(root_path, gix_config::Source::Local)?),
+            init::Options {
+                includes: includes::Options::follow(Default::default(), Default::default()),
+                ..Default::default()
+            },
+        )?
+        .expect("non-empty");
+        assert_eq!(
+            config.string("core", None, "a"),
+            Some(cow_str("included")),
+            "the include overrides the local value"
+        );
+
+        config.set_raw_value_filter("core", None, "a", "edited", &mut |meta| meta.level == 0)?;
+        config.set_raw_value_filter("user", None, "name", "edited", &mut |meta| meta.level == 0)?;
+        assert_eq!(
+            config.string("core", None, "a"),
+            Some(cow_str("included")),
+            "the included value still takes precedence after editing the local one"
+        );
+
+        let mut buf = Vec::<u8>::new();
+        config.write_to_without_includes(&mut buf)?;
+        assert_eq!(
+            buf.to_str_lossy(),
+            "[core]\n\ta = edited\n[include]\n\tpath = included.config\n[user]\n\tname = edited\n",
+            "the include directive survives, and the edited local values are written without any of the included ones"
+        );
+        Ok(())
+    }
+}
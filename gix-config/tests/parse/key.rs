@@ -37,3 +37,83 @@ fn section_name_subsection_and_key() {
         })
     );
 }
+
+mod validated {
+    use gix_config::parse::{validated_key, Key};
+
+    fn parse(input: &str) -> Result<Key<'_>, validated_key::Error> {
+        validated_key::parse(input.into())
+    }
+
+    #[test]
+    fn two_part_keys() -> crate::Result {
+        assert_eq!(
+            parse("core.bare")?,
+            Key {
+                section_name: "core",
+                subsection_name: None,
+                value_name: "bare"
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn three_part_keys() -> crate::Result {
+        assert_eq!(
+            parse("remote.origin.url")?,
+            Key {
+                section_name: "remote",
+                subsection_name: Some("origin".into()),
+                value_name: "url"
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn dotted_subsections_split_on_first_and_last_dot() -> crate::Result {
+        let expected = Key {
+            section_name: "remote",
+            subsection_name: Some("my.sub".into()),
+            value_name: "url",
+        };
+        assert_eq!(parse("remote.my.sub.url")?, expected);
+        assert_eq!(parse(r#"remote."my.sub".url"#)?, expected, "quotes are removed");
+        assert_eq!(
+            parse(r#"remote."".url"#)?.subsection_name,
+            Some("".into()),
+            "quoted subsections can be empty"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_keys() {
+        assert!(matches!(parse("core"), Err(validated_key::Error::MissingDot { .. })));
+        assert!(matches!(
+            parse("co_re.bare"),
+            Err(validated_key::Error::InvalidSectionName { .. })
+        ));
+        assert!(matches!(
+            parse(".bare"),
+            Err(validated_key::Error::InvalidSectionName { .. })
+        ));
+        assert!(matches!(
+            parse("core.1bare"),
+            Err(validated_key::Error::InvalidKeyName { .. })
+        ));
+        assert!(matches!(
+            parse("core."),
+            Err(validated_key::Error::InvalidKeyName { .. })
+        ));
+        assert!(matches!(
+            parse(r#"remote."origin.url"#),
+            Err(validated_key::Error::UnbalancedQuotes { .. })
+        ));
+        assert!(matches!(
+            parse("remote.ori\ngin.url"),
+            Err(validated_key::Error::InvalidSubsectionName { .. })
+        ));
+    }
+}
@@ -0,0 +1,118 @@
+use std::{
+    collections::BTreeMap,
+    sync::{Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+use crate::protocol::{Context, CredentialKey};
+
+type Entries = BTreeMap<CredentialKey, (gix_sec::identity::Account, Instant)>;
+
+/// An in-memory credential cache shared by [cascades][crate::helper::Cascade] to avoid invoking helpers for each request,
+/// similar to what `git credential-cache` does across processes.
+///
+/// Credentials are stored by the key of the [context][crate::protocol::Context::to_key()] they were requested for and
+/// expire after a configurable time-to-live. The cache is thread-safe and is typically shared using an `Arc`.
+#[derive(Debug)]
+pub struct Cache {
+    ttl: Duration,
+    entries: Mutex<Entries>,
+}
+
+/// Initialization
+impl Cache {
+    /// Create a new cache whose entries expire `ttl` after they were stored, like the `--timeout` of `credential.cacheOptions`.
+    ///
+    /// A `ttl` of zero disables caching entirely.
+    pub fn new(ttl: Duration) -> Self {
+        Cache {
+            ttl,
+            entries: Default::default(),
+        }
+    }
+}
+
+/// Access
+impl Cache {
+    /// Return the time-to-live of each entry.
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Return the credentials stored for `key` if they didn't expire yet.
+    ///
+    /// If `key` doesn't have a username, credentials stored for any username of the same target are returned, similar
+    /// to how `git credential-cache` matches them. If credentials of more than one user are stored for the target though,
+    /// `None` is returned as there is no telling which of them is meant.
+    pub fn get(&self, key: &CredentialKey) -> Option<gix_sec::identity::Account> {
+        if self.ttl.is_zero() {
+            return None;
+        }
+        let ttl = self.ttl;
+        let mut entries = self.entries();
+        entries.retain(|_, (_, stored_at)| stored_at.elapsed() < ttl);
+        let mut matches = entries
+            .iter()
+            .filter(|(stored, _)| {
+                same_target(stored, key) && (key.username.is_none() || stored.username == key.username)
+            })
+            .map(|(_, (account, _))| account);
+        let account = matches.next()?;
+        if matches.any(|other| other.username != account.username) {
+            return None;
+        }
+        Some(account.clone())
+    }
+
+    /// Return all credentials that didn't expire yet, ordered by the key they were stored for, with passwords removed
+    /// unless `include_passwords` is true.
+    pub fn list(&self, include_passwords: bool) -> Vec<Context> {
+        let ttl = self.ttl;
+        let mut entries = self.entries();
+        entries.retain(|_, (_, stored_at)| stored_at.elapsed() < ttl);
+        entries
+            .iter()
+            .map(|(key, (account, _))| Context {
+                protocol: key.protocol.clone(),
+                host: key.host.clone(),
+                path: key.path.clone(),
+                username: Some(account.username.clone()),
+                password: include_passwords.then(|| account.password.clone()),
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    /// Store `account` for `key`, replacing previous credentials, unless caching is disabled.
+    pub fn insert(&self, key: CredentialKey, account: gix_sec::identity::Account) {
+        if self.ttl.is_zero() {
+            return;
+        }
+        self.entries().insert(key, (account, Instant::now()));
+    }
+
+    /// Remove all credentials that `key` refers to.
+    ///
+    /// As keys used for lookup may lack a username, entries without a username are removed along with the ones matching
+    /// the username of `key`, and all usernames are matched if `key` doesn't have one.
+    pub fn remove(&self, key: &CredentialKey) {
+        self.entries().retain(|stored, _| {
+            let same_user = stored.username.is_none() || key.username.is_none() || stored.username == key.username;
+            !(same_target(stored, key) && same_user)
+        });
+    }
+
+    /// Remove all entries.
+    pub fn clear(&self) {
+        self.entries().clear();
+    }
+
+    fn entries(&self) -> MutexGuard<'_, Entries> {
+        // Entries are always in a consistent state, even if a panic occurred while the lock was held.
+        self.entries.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+fn same_target(a: &CredentialKey, b: &CredentialKey) -> bool {
+    a.protocol == b.protocol && a.host == b.host && a.path == b.path
+}
@@ -7,6 +7,7 @@ impl Default for Cascade {
             stderr: true,
             use_http_path: false,
             query_user_only: false,
+            cache: None,
         }
     }
 }
@@ -59,6 +60,15 @@ impl Cascade {
         self.query_user_only = toggle;
         self
     }
+
+    /// Use `cache` to remember complete credentials when getting them, and to provide them without invoking any program
+    /// on subsequent requests for the same context until they expire.
+    ///
+    /// Storing credentials also puts them into `cache`, while erasing credentials removes them from it.
+    pub fn cache(mut self, cache: std::sync::Arc<helper::Cache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
 }
 
 /// Finalize
@@ -67,13 +77,24 @@ impl Cascade {
     /// The latter can also be used to disable the prompt entirely when setting the `mode` to [`Disable`][gix_prompt::Mode::Disable];=.
     ///
     /// When _getting_ credentials, all programs are asked until the credentials are complete, stopping the cascade.
-    /// When _storing_ or _erasing_ all programs are instructed in order.
+    /// If the url to get credentials for already contains a username and password, no program is asked at all.
+    /// When _storing_ or _erasing_ all programs are instructed in order, even if the url contains a username and password.
+    ///
+    /// If a [cache][Cascade::cache()] is set, it's consulted before any program is asked when _getting_ credentials, and
+    /// complete credentials are stored in it, just like when _storing_ them. _Erasing_ also removes them from the cache.
+    ///
+    /// _Listing_ isn't supported and fails, use [`helper::list()`] with each program instead.
     #[allow(clippy::result_large_err)]
     pub fn invoke(&mut self, mut action: helper::Action, mut prompt: gix_prompt::Options<'_>) -> protocol::Result {
+        if matches!(action, helper::Action::List(_)) {
+            return Err(helper::Error::ListAction.into());
+        }
+        let mut credentials_in_url = false;
         let mut url = action
             .context_mut()
             .map(|ctx| {
                 ctx.destructure_url_in_place(self.use_http_path).map(|ctx| {
+                    credentials_in_url = ctx.username.is_some() && ctx.password.is_some();
                     if self.query_user_only && ctx.password.is_none() {
                         ctx.password = Some("".into());
                     }
@@ -83,7 +104,51 @@ impl Cascade {
             .transpose()?
             .and_then(|ctx| ctx.url.take());
 
-        for program in &mut self.programs {
+        let (mut cache_key, mut credentials_in_cache) = (None, false);
+        if let Some(cache) = self.cache.as_deref() {
+            match &mut action {
+                helper::Action::Get(ctx) if !credentials_in_url => {
+                    let key = ctx.to_key(self.use_http_path);
+                    match cache.get(&key) {
+                        Some(account) => {
+                            ctx.username = Some(account.username);
+                            ctx.password = Some(account.password);
+                            credentials_in_cache = true;
+                        }
+                        None => cache_key = Some(key),
+                    }
+                }
+                helper::Action::Get(_) => {}
+                helper::Action::Store(payload) => {
+                    if let Ok(mut ctx) = Context::from_bytes(payload) {
+                        if ctx.url.is_some() {
+                            ctx.destructure_url_in_place(self.use_http_path)?;
+                        }
+                        let key = ctx.to_key(self.use_http_path);
+                        if let Some((username, password)) = ctx.username.zip(ctx.password) {
+                            cache.insert(key, gix_sec::identity::Account { username, password });
+                        }
+                    }
+                }
+                helper::Action::Erase(payload) => {
+                    if let Ok(mut ctx) = Context::from_bytes(payload) {
+                        if ctx.url.is_some() {
+                            ctx.destructure_url_in_place(self.use_http_path)?;
+                        }
+                        cache.remove(&ctx.to_key(self.use_http_path));
+                    }
+                }
+                helper::Action::List(_) => {}
+            }
+        }
+
+        let is_get = matches!(action, helper::Action::Get(_));
+        let programs = if is_get && (credentials_in_url || credentials_in_cache) {
+            &mut [][..]
+        } else {
+            &mut self.programs[..]
+        };
+        for program in programs {
             program.stderr = self.stderr;
             match helper::invoke::raw(program, &action) {
                 Ok(None) => {}
@@ -104,6 +169,12 @@ impl Cascade {
                             }
                         }
                         if let Some(src) = ctx.url {
+                            // The url is processed last and overrides all fields it can provide, as `git` does
+                            // when it's the last line of the helper output.
+                            dst_ctx.protocol = None;
+                            dst_ctx.host = None;
+                            dst_ctx.path = None;
+                            dst_ctx.username = None;
                             dst_ctx.url = Some(src);
                             url = dst_ctx.destructure_url_in_place(self.use_http_path)?.url.take();
                         }
@@ -126,7 +197,7 @@ impl Cascade {
             if let Some(ctx) = action.context_mut() {
                 ctx.url = url;
                 if ctx.username.is_none() {
-                    let message = ctx.to_prompt("Username");
+                    let message = ctx.askpass_prompt(protocol::context::prompt::Field::Username);
                     prompt.mode = gix_prompt::Mode::Visible;
                     ctx.username = gix_prompt::ask(&message, &prompt)
                         .map_err(|err| protocol::Error::Prompt {
@@ -136,7 +207,7 @@ impl Cascade {
                         .into();
                 }
                 if ctx.password.is_none() {
-                    let message = ctx.to_prompt("Password");
+                    let message = ctx.askpass_prompt(protocol::context::prompt::Field::Password);
                     prompt.mode = gix_prompt::Mode::Hidden;
                     ctx.password = gix_prompt::ask(&message, &prompt)
                         .map_err(|err| protocol::Error::Prompt {
@@ -148,6 +219,14 @@ impl Cascade {
             }
         }
 
+        if let Some((cache, key)) = self.cache.as_deref().zip(cache_key) {
+            if let Some(ctx) = action.context() {
+                if let Some((username, password)) = ctx.username.clone().zip(ctx.password.clone()) {
+                    cache.insert(key, gix_sec::identity::Account { username, password });
+                }
+            }
+        }
+
         protocol::helper_outcome_to_result(
             action.context().map(|ctx| helper::Outcome {
                 username: ctx.username.clone(),
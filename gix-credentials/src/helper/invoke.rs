@@ -1,12 +1,14 @@
 use std::io::Read;
 
+use bstr::ByteSlice;
+
 use crate::helper::{Action, Context, Error, NextAction, Outcome, Result};
 
 impl Action {
     /// Send ourselves to the given `write` which is expected to be credentials-helper compatible
     pub fn send(&self, mut write: impl std::io::Write) -> std::io::Result<()> {
         match self {
-            Action::Get(ctx) => ctx.write_to(write),
+            Action::Get(ctx) | Action::List(ctx) => ctx.write_to(write),
             Action::Store(last) | Action::Erase(last) => {
                 write.write_all(last).ok();
                 write.write_all(&[b'\n']).ok();
@@ -22,8 +24,18 @@ impl Action {
 /// Note that it may also only contain the username _or_ password, and should start out with everything the helper needs.
 /// On successful usage, use [`NextAction::store()`], otherwise [`NextAction::erase()`], which is when this function
 /// returns `Ok(None)` as no outcome is expected.
+///
+/// [`Action::List`] is rejected with an error as its output can't be represented as outcome, use [`list()`] instead.
 pub fn invoke(helper: &mut crate::Program, action: &Action) -> Result {
-    match raw(helper, action)? {
+    if matches!(action, Action::List(_)) {
+        return Err(Error::ListAction);
+    }
+    outcome_from_output(raw(helper, action)?)
+}
+
+/// Turn the `stdout` of a helper program, if the action expects output, into an outcome.
+pub(crate) fn outcome_from_output(stdout: Option<Vec<u8>>) -> Result {
+    match stdout {
         None => Ok(None),
         Some(stdout) => {
             let ctx = Context::from_bytes(stdout.as_slice())?;
@@ -39,10 +51,36 @@ pub fn invoke(helper: &mut crate::Program, action: &Action) -> Result {
     }
 }
 
+/// Invoke the custom `helper` to list all credentials it stores that match the fields set in `context`, with passwords
+/// removed unless `include_passwords` is true.
+///
+/// Helpers that don't know the `list` operation are expected to ignore it, which lists nothing, while the
+/// [built-in](crate::program::Kind::Builtin) `git credential` program can't list credentials at all and causes an error.
+pub fn list(
+    helper: &mut crate::Program,
+    context: Context,
+    include_passwords: bool,
+) -> std::result::Result<Vec<Context>, Error> {
+    let stdout = raw(helper, &Action::List(context))?.unwrap_or_default();
+    let mut out = Vec::new();
+    // Entries are separated by empty lines, just like the output of `get` is terminated by one.
+    for entry in stdout.split_str("\n\n").filter(|entry| !entry.trim().is_empty()) {
+        let mut ctx = Context::from_bytes(entry.trim_start())?;
+        if !include_passwords {
+            ctx.password = None;
+        }
+        out.push(ctx);
+    }
+    Ok(out)
+}
+
 pub(crate) fn raw(helper: &mut crate::Program, action: &Action) -> std::result::Result<Option<Vec<u8>>, Error> {
+    if matches!(action, Action::List(_)) && helper.kind == crate::program::Kind::Builtin {
+        return Err(Error::ListUnsupported);
+    }
     let (stdin, stdout) = helper.start(action)?;
-    if let (Action::Get(_), None) = (&action, &stdout) {
-        panic!("BUG: `Helper` impls must return an output handle to read output from if Action::Get is provided")
+    if action.expects_output() && stdout.is_none() {
+        panic!("BUG: `Helper` impls must return an output handle to read output from if Action::Get or Action::List is provided")
     }
     action.send(stdin)?;
     let stdout = stdout
@@ -52,16 +90,19 @@ pub(crate) fn raw(helper: &mut crate::Program, action: &Action) -> std::result::
         })
         .transpose()
         .map_err(|err| Error::CredentialsHelperFailed { source: err })?;
-    helper.finish().map_err(|err| {
-        if err.kind() == std::io::ErrorKind::Other {
-            Error::CredentialsHelperFailed { source: err }
-        } else {
-            err.into()
-        }
-    })?;
+    helper.finish().map_err(finish_error)?;
 
-    match matches!(action, Action::Get(_)).then(|| stdout).flatten() {
+    match action.expects_output().then(|| stdout).flatten() {
         None => Ok(None),
         Some(stdout) => Ok(Some(stdout)),
     }
 }
+
+/// Classify `err` obtained when finishing a helper program, with failures of the program itself being distinguished.
+pub(crate) fn finish_error(err: std::io::Error) -> Error {
+    if err.kind() == std::io::ErrorKind::Other {
+        Error::CredentialsHelperFailed { source: err }
+    } else {
+        err.into()
+    }
+}
@@ -0,0 +1,53 @@
+use std::io::{Read, Write};
+
+use crate::helper::{invoke, Action, Error, Result};
+
+/// Like [`invoke()`][crate::helper::invoke()], but performs all blocking IO with the `helper` program on a thread pool
+/// to not block the executor.
+///
+/// If the returned future is dropped before completion, the helper program is killed and reaped.
+pub async fn invoke_async(helper: &mut crate::Program, action: &Action) -> Result {
+    if matches!(action, Action::List(_)) {
+        return Err(Error::ListAction);
+    }
+    let mut input = Vec::new();
+    action.send(&mut input)?;
+    let (mut stdin, stdout) = helper.start(action)?;
+    if action.expects_output() && stdout.is_none() {
+        panic!("BUG: `Helper` impls must return an output handle to read output from if Action::Get or Action::List is provided")
+    }
+    let guard = KillOnDrop { helper };
+
+    let stdout = blocking::unblock(move || {
+        stdin.write_all(&input)?;
+        drop(stdin);
+        stdout
+            .map(|mut stdout| {
+                let mut buf = Vec::new();
+                stdout.read_to_end(&mut buf).map(|_| buf)
+            })
+            .transpose()
+    })
+    .await
+    .map_err(|err| Error::CredentialsHelperFailed { source: err })?;
+
+    let mut child = guard.helper.child.take().expect("child is present until we take it");
+    let status = blocking::unblock(move || child.wait()).await?;
+    crate::program::check_status(status).map_err(invoke::finish_error)?;
+
+    invoke::outcome_from_output(action.expects_output().then_some(stdout).flatten())
+}
+
+/// Kill and reap the child of `helper` if it's still present when dropped, which happens if the future is dropped early.
+struct KillOnDrop<'a> {
+    helper: &'a mut crate::Program,
+}
+
+impl Drop for KillOnDrop<'_> {
+    fn drop(&mut self) {
+        if let Some(mut child) = self.helper.child.take() {
+            child.kill().ok();
+            child.wait().ok();
+        }
+    }
+}
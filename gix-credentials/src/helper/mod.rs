@@ -18,6 +18,9 @@ pub struct Cascade {
     /// If true, default false, when getting credentials, we will set a bogus password to only obtain the user name.
     /// Storage and cancellation work the same, but without a password set.
     pub query_user_only: bool,
+    /// If set, credentials obtained when getting them are kept in this cache and returned on subsequent requests without
+    /// invoking any program, until they expire or are erased.
+    pub cache: Option<std::sync::Arc<Cache>>,
 }
 
 /// The outcome of the credentials helper [invocation][crate::helper::invoke()].
@@ -61,6 +64,10 @@ pub enum Error {
     Io(#[from] std::io::Error),
     #[error(transparent)]
     CredentialsHelperFailed { source: std::io::Error },
+    #[error("The built-in `git credential` program can't list stored credentials")]
+    ListUnsupported,
+    #[error("Stored credentials can only be listed with `helper::list()`")]
+    ListAction,
 }
 
 /// The action to perform by the credentials [helper][`crate::helper::invoke()`].
@@ -72,6 +79,13 @@ pub enum Action {
     Store(BString),
     /// Reject the credentials as identified by the previous input provided as `BString`. containing information from [`Context`].
     Erase(BString),
+    /// List all credentials stored by a custom helper that match the fields set in the given context, which may be empty to
+    /// list all of them.
+    ///
+    /// This isn't part of the protocol `git` uses, so only custom helpers which implement the `list` operation will output
+    /// anything while the built-in `git credential` program isn't supported. It can only be invoked with
+    /// [`list()`][crate::helper::list()], while [`invoke()`][crate::helper::invoke()] and the [`Cascade`] reject it.
+    List(Context),
 }
 
 /// Initialization
@@ -92,7 +106,7 @@ impl Action {
     pub fn payload(&self) -> Option<&BStr> {
         use bstr::ByteSlice;
         match self {
-            Action::Get(_) => None,
+            Action::Get(_) | Action::List(_) => None,
             Action::Store(p) | Action::Erase(p) => Some(p.as_bstr()),
         }
     }
@@ -102,7 +116,7 @@ impl Action {
     pub fn context(&self) -> Option<&Context> {
         match self {
             Action::Get(ctx) => Some(ctx),
-            Action::Erase(_) | Action::Store(_) => None,
+            Action::Erase(_) | Action::Store(_) | Action::List(_) => None,
         }
     }
 
@@ -110,13 +124,13 @@ impl Action {
     pub fn context_mut(&mut self) -> Option<&mut Context> {
         match self {
             Action::Get(ctx) => Some(ctx),
-            Action::Erase(_) | Action::Store(_) => None,
+            Action::Erase(_) | Action::Store(_) | Action::List(_) => None,
         }
     }
 
     /// Returns true if this action expects output from the helper.
     pub fn expects_output(&self) -> bool {
-        matches!(self, Action::Get(_))
+        matches!(self, Action::Get(_) | Action::List(_))
     }
 
     /// The name of the argument to describe this action. If `is_external` is true, the target program is
@@ -129,6 +143,7 @@ impl Action {
             Action::Store(_) => "approve",
             Action::Erase(_) if is_external => "erase",
             Action::Erase(_) => "reject",
+            Action::List(_) => "list",
         }
     }
 }
@@ -169,6 +184,15 @@ impl NextAction {
 }
 
 mod cascade;
+
+///
+pub mod cache;
+pub use cache::Cache;
 pub(crate) mod invoke;
 
-pub use invoke::invoke;
+pub use invoke::{invoke, list};
+
+#[cfg(feature = "async-client")]
+mod invoke_async;
+#[cfg(feature = "async-client")]
+pub use invoke_async::invoke_async;
@@ -115,14 +115,19 @@ impl Program {
     pub(crate) fn finish(&mut self) -> std::io::Result<()> {
         let mut child = self.child.take().expect("Call `start()` before calling finish()");
         let status = child.wait()?;
-        if status.success() {
-            Ok(())
-        } else {
-            Err(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                format!("Credentials helper program failed with status code {:?}", status.code()),
-            ))
-        }
+        check_status(status)
+    }
+}
+
+/// Turn an unsuccessful exit `status` of a helper program into an error.
+pub(crate) fn check_status(status: std::process::ExitStatus) -> std::io::Result<()> {
+    if status.success() {
+        Ok(())
+    } else {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            format!("Credentials helper program failed with status code {:?}", status.code()),
+        ))
     }
 }
 
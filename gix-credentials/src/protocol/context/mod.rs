@@ -8,12 +8,121 @@ pub enum Error {
     Encoding { key: String, value: BString },
 }
 
+///
+pub mod validate {
+    /// Control which checks [`Context::validate()`][crate::protocol::Context::validate()] performs.
+    ///
+    /// The default matches what is expected of well-formed contexts.
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    pub struct Options {
+        /// If `true`, a `file` context may carry a host other than `localhost`. Defaults to `false`.
+        pub allow_host_with_file_protocol: bool,
+        /// If `true`, an `ssh` context must carry a host. Defaults to `true`.
+        pub require_host_with_ssh_protocol: bool,
+    }
+
+    impl Default for Options {
+        fn default() -> Self {
+            Options {
+                allow_host_with_file_protocol: false,
+                require_host_with_ssh_protocol: true,
+            }
+        }
+    }
+
+    /// The error returned by [`Context::validate()`][crate::protocol::Context::validate()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Contexts with the 'file' protocol must not have a host, got {host:?}")]
+        HostWithFileProtocol { host: String },
+        #[error("Contexts with the 'ssh' protocol must have a host")]
+        MissingHostWithSshProtocol,
+    }
+}
+
+///
+pub mod prompt {
+    /// The field to obtain with a prompt built by [`Context::askpass_prompt()`][crate::protocol::Context::askpass_prompt()].
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    pub enum Field {
+        /// Ask for the username.
+        Username,
+        /// Ask for the password.
+        Password,
+    }
+}
+
 mod access {
     use bstr::BString;
 
-    use crate::protocol::Context;
+    use crate::protocol::{
+        context::{prompt, validate},
+        Context, CredentialKey,
+    };
 
     impl Context {
+        /// Produce a normalized key to match this context against stored credentials, similar to how `git` compares
+        /// protocol, host, path and username.
+        ///
+        /// Protocol and host are lower-cased and the port is dropped from the host if it's the default port of the protocol.
+        /// For `http` and `https`, the path is only included if `use_http_path` is set, i.e. `credential.useHttpPath` is true.
+        ///
+        /// Note that only the `protocol`, `host`, `path` and `username` fields are used, so a `url` must have been
+        /// [destructured][Context::destructure_url_in_place()] before.
+        pub fn to_key(&self, use_http_path: bool) -> CredentialKey {
+            use bstr::ByteSlice;
+            let protocol = self.protocol.as_deref().map(str::to_ascii_lowercase);
+            let host = self.host.as_deref().map(|host| {
+                let host = host.to_ascii_lowercase();
+                match (host.rsplit_once(':'), protocol.as_deref().and_then(default_port)) {
+                    (Some((host, port)), Some(default_port)) if port.parse::<u16>().ok() == Some(default_port) => {
+                        host.to_owned()
+                    }
+                    _ => host,
+                }
+            });
+            let is_http = matches!(protocol.as_deref(), Some("http" | "https"));
+            let path = if is_http && !use_http_path {
+                None
+            } else {
+                self.path
+                    .as_ref()
+                    .map(|path| path.trim_with(|b| b == '/'))
+                    .filter(|path| !path.is_empty())
+                    .map(Into::into)
+            };
+            CredentialKey {
+                protocol,
+                host,
+                path,
+                username: self.username.clone(),
+            }
+        }
+
+        /// Check that `protocol` and `host` agree with each other as the scheme requires, with `options` controlling which
+        /// checks are performed, to catch malformed contexts before passing them to helpers.
+        ///
+        /// `file` contexts must not have a host other than `localhost`, and `ssh` contexts must have a host. Contexts
+        /// without a protocol or with any other protocol are always valid.
+        ///
+        /// Note that a `url` must have been [destructured][Context::destructure_url_in_place()] before.
+        pub fn validate(&self, options: validate::Options) -> Result<(), validate::Error> {
+            let host = self.host.as_deref().filter(|host| !host.is_empty());
+            match self.protocol.as_deref().map(str::to_ascii_lowercase).as_deref() {
+                Some("file") if !options.allow_host_with_file_protocol => match host {
+                    Some(host) if !host.eq_ignore_ascii_case("localhost") => {
+                        Err(validate::Error::HostWithFileProtocol { host: host.to_owned() })
+                    }
+                    _ => Ok(()),
+                },
+                Some("ssh") if options.require_host_with_ssh_protocol && host.is_none() => {
+                    Err(validate::Error::MissingHostWithSshProtocol)
+                }
+                _ => Ok(()),
+            }
+        }
+
         /// Convert all relevant fields into a URL for consumption.
         pub fn to_url(&self) -> Option<BString> {
             use bstr::{ByteSlice, ByteVec};
@@ -34,6 +143,36 @@ mod access {
             }
             buf.into()
         }
+
+        /// Return our `url` with its user information like `user:password@` removed, for safe inclusion in
+        /// progress and error messages, or `None` if there is no `url`.
+        ///
+        /// If the `url` can't be parsed, everything up to the last `@` in front of the host is removed as best effort.
+        pub fn sanitized_url(&self) -> Option<BString> {
+            use bstr::ByteSlice;
+            let url = self.url.as_ref()?.as_bstr();
+            Some(match gix_url::parse(url) {
+                Ok(mut url) => {
+                    url.set_user(None);
+                    url.to_bstring()
+                }
+                Err(_) => {
+                    let authority_start = url.find("://").map_or(0, |pos| pos + 3);
+                    let authority_end = url[authority_start..]
+                        .find_byte(b'/')
+                        .map_or(url.len(), |pos| authority_start + pos);
+                    match url[authority_start..authority_end].rfind_byte(b'@') {
+                        Some(pos) => {
+                            let mut out: BString = url[..authority_start].into();
+                            out.extend_from_slice(&url[authority_start + pos + 1..]);
+                            out
+                        }
+                        None => url.to_owned(),
+                    }
+                }
+            })
+        }
+
         /// Compute a prompt to obtain the given value.
         pub fn to_prompt(&self, field: &str) -> String {
             match self.to_url() {
@@ -41,6 +180,34 @@ mod access {
                 None => format!("{field}: "),
             }
         }
+
+        /// Compute the prompt `git` presents to the user or passes to the `askpass` program to obtain `field`,
+        /// like `Username for 'https://host': ` or `Password for 'https://user@host': `.
+        ///
+        /// As `git` always obtains the username first, the username prompt is returned if the password is requested
+        /// but the username is still missing.
+        ///
+        /// Note that a `url` must have been [destructured][Context::destructure_url_in_place()] before.
+        pub fn askpass_prompt(&self, field: prompt::Field) -> String {
+            let field = match field {
+                prompt::Field::Password if self.username.is_some() => "Password",
+                prompt::Field::Username | prompt::Field::Password => "Username",
+            };
+            match self.to_url() {
+                Some(url) => format!("{field} for '{url}': "),
+                None => format!("{field}: "),
+            }
+        }
+    }
+
+    fn default_port(protocol: &str) -> Option<u16> {
+        Some(match protocol {
+            "http" => 80,
+            "https" => 443,
+            "ssh" => 22,
+            "git" => 9418,
+            _ => return None,
+        })
     }
 }
 
@@ -52,21 +219,33 @@ mod mutate {
     /// In-place mutation
     impl Context {
         /// Destructure the url at our `url` field into parts like protocol, host, username and path and store
-        /// them in our respective fields. If `use_http_path` is set, http paths are significant even though
+        /// them in our respective fields, but only if these aren't set yet so explicitly provided fields take precedence.
+        /// The host includes the port if there is one. If `use_http_path` is set, http paths are significant even though
         /// normally this isn't the case.
         #[allow(clippy::result_large_err)]
         pub fn destructure_url_in_place(&mut self, use_http_path: bool) -> Result<&mut Self, protocol::Error> {
             let url = gix_url::parse(self.url.as_ref().ok_or(protocol::Error::UrlMissing)?.as_ref())?;
-            self.protocol = Some(url.scheme.as_str().into());
-            self.username = url.user().map(ToOwned::to_owned);
-            self.host = url.host().map(ToOwned::to_owned).map(|mut host| {
-                if let Some(port) = url.port {
-                    use std::fmt::Write;
-                    write!(host, ":{port}").expect("infallible");
-                }
-                host
-            });
-            if !matches!(url.scheme, gix_url::Scheme::Http | gix_url::Scheme::Https) || use_http_path {
+            if self.protocol.is_none() {
+                self.protocol = Some(url.scheme.as_str().into());
+            }
+            if self.username.is_none() {
+                self.username = url.user().map(ToOwned::to_owned);
+            }
+            if self.password.is_none() {
+                self.password = url.password().map(ToOwned::to_owned);
+            }
+            if self.host.is_none() {
+                self.host = url.host().map(ToOwned::to_owned).map(|mut host| {
+                    if let Some(port) = url.port {
+                        use std::fmt::Write;
+                        write!(host, ":{port}").expect("infallible");
+                    }
+                    host
+                });
+            }
+            if self.path.is_none()
+                && (!matches!(url.scheme, gix_url::Scheme::Http | gix_url::Scheme::Https) || use_http_path)
+            {
                 let path = url.path.trim_with(|b| b == '/');
                 self.path = (!path.is_empty()).then(|| path.into());
             }
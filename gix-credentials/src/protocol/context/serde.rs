@@ -36,6 +36,11 @@ mod write {
                     write_key(&mut out, key, value.as_bytes().as_bstr()).ok();
                 }
             }
+            for value in &self.wwwauth {
+                let key = "wwwauth[]";
+                validate(key, value.as_ref()).map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
+                write_key(&mut out, key, value.as_ref()).ok();
+            }
             Ok(())
         }
 
@@ -104,6 +109,9 @@ pub mod decode {
                     "quit" => {
                         ctx.quit = gix_config_value::Boolean::try_from(value.as_ref()).ok().map(Into::into);
                     }
+                    // Like all multi-valued attributes, an empty value resets the list.
+                    "wwwauth[]" if value.is_empty() => ctx.wwwauth.clear(),
+                    "wwwauth[]" => ctx.wwwauth.push(value),
                     _ => {}
                 }
             }
@@ -54,6 +54,27 @@ pub struct Context {
     pub url: Option<BString>,
     /// If true, the caller should stop asking for credentials immediately without calling more credential helpers in the chain.
     pub quit: Option<bool>,
+    /// The values of all `WWW-Authenticate` headers of the server's response, in order, to let helpers choose an
+    /// authentication scheme.
+    ///
+    /// They are passed as repeated `wwwauth[]` attributes, and are only ever sent to helpers.
+    pub wwwauth: Vec<BString>,
+}
+
+/// A normalized representation of the target of a [`Context`], suitable to match stored credentials or to key a cache.
+///
+/// Two contexts that `git` considers to refer to the same target produce the same key.
+/// It's created with [`Context::to_key()`].
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct CredentialKey {
+    /// The lower-cased protocol, like `https`.
+    pub protocol: Option<String>,
+    /// The lower-cased host, with the port if it isn't the default port of `protocol`.
+    pub host: Option<String>,
+    /// The path without leading or trailing slashes, if it is significant for matching.
+    pub path: Option<BString>,
+    /// The username as given, if one is known.
+    pub username: Option<String>,
 }
 
 /// Convert the outcome of a helper invocation to a helper result, assuring that the identity is complete in the process.
@@ -78,7 +99,7 @@ pub fn helper_outcome_to_result(outcome: Option<helper::Outcome>, action: helper
                 Error::IdentityMissing { context: redact(ctx) }
             }),
         },
-        (helper::Action::Store(_) | helper::Action::Erase(_), _ignore) => Ok(None),
+        (helper::Action::Store(_) | helper::Action::Erase(_) | helper::Action::List(_), _ignore) => Ok(None),
     }
 }
 
@@ -0,0 +1,4 @@
+#!/bin/bash
+
+echo username=user
+echo quit=true
@@ -10,6 +10,7 @@ fn encode_decode_roundtrip_works_only_for_serializing_fields() {
             username: Some("user".into()),
             password: Some("pass".into()),
             url: Some("https://github.com/byron/gitoxide".into()),
+            wwwauth: vec!["Bearer realm=\"example\"".into(), "Basic realm=\"example\"".into()],
             ..Default::default()
         },
         Context::default(),
@@ -44,6 +45,19 @@ mod write_to {
         );
     }
 
+    #[test]
+    fn wwwauth_is_written_as_repeated_attribute_in_order() {
+        let ctx = Context {
+            host: Some("example.com".into()),
+            wwwauth: vec!["Negotiate".into(), "Basic realm=\"example\"".into()],
+            ..Default::default()
+        };
+        assert_eq!(
+            ctx.to_bstring(),
+            "host=example.com\nwwwauth[]=Negotiate\nwwwauth[]=Basic realm=\"example\"\n"
+        );
+    }
+
     #[test]
     fn null_bytes_and_newlines_are_invalid() {
         for input in [&b"foo\0"[..], b"foo\n"] {
@@ -110,6 +124,73 @@ username=bob";
                 "{input}"
             )
         }
+        for invalid_value in ["maybe", "1x"] {
+            let input = format!("quit={invalid_value}");
+            assert_eq!(
+                Context::from_bytes(input.as_bytes()).unwrap().quit,
+                None,
+                "{input}: values that aren't booleans are ignored"
+            )
+        }
+    }
+
+    #[test]
+    fn wwwauth_values_are_collected_in_order_and_reset_by_empty_values() {
+        assert_eq!(
+            Context::from_bytes(
+                b"wwwauth[]=Basic\nwwwauth[]=\nwwwauth[]=Negotiate\nwwwauth[]=Bearer\nother[]=ignored\n"
+            )
+            .unwrap(),
+            Context {
+                wwwauth: vec!["Negotiate".into(), "Bearer".into()],
+                ..Default::default()
+            }
+        )
+    }
+
+    #[test]
+    fn empty_values_are_kept() {
+        assert_eq!(
+            Context::from_bytes(b"username=\npassword=\n").unwrap(),
+            Context {
+                username: Some("".into()),
+                password: Some("".into()),
+                ..Default::default()
+            }
+        )
+    }
+
+    #[test]
+    fn url_is_not_destructured() {
+        assert_eq!(
+            Context::from_bytes(b"url=https://user@example.com/path\n\n").unwrap(),
+            Context {
+                url: Some("https://user@example.com/path".into()),
+                ..Default::default()
+            },
+            "this is left to `destructure_url_in_place()`"
+        )
+    }
+
+    #[test]
+    fn last_line_may_lack_a_newline() {
+        assert_eq!(
+            Context::from_bytes(b"protocol=https\nhost=example.com").unwrap(),
+            Context {
+                protocol: Some("https".into()),
+                host: Some("example.com".into()),
+                ..Default::default()
+            }
+        )
+    }
+
+    #[test]
+    fn lines_without_separator_are_rejected() {
+        let err = Context::from_bytes(b"protocol=https\nhost\n").unwrap_err();
+        assert!(matches!(
+            err,
+            gix_credentials::protocol::context::decode::Error::Syntax { line } if line == "host"
+        ));
     }
 
     #[test]
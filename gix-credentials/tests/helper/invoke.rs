@@ -43,6 +43,45 @@ fn store_and_reject() {
     }
 }
 
+#[test]
+fn list() -> crate::Result {
+    let mut helper = Program::from_custom_definition(
+        "!f() { test \"$1\" = list && printf 'protocol=https\\nhost=a.example\\nusername=a\\npassword=pass-a\\n\\nprotocol=https\\nhost=b.example\\nusername=b\\npassword=pass-b\\n\\n'; }; f",
+    );
+    let entry = |host: &str, username: &str, password: Option<&str>| Context {
+        protocol: Some("https".into()),
+        host: Some(host.into()),
+        username: Some(username.into()),
+        password: password.map(Into::into),
+        ..Default::default()
+    };
+    assert_eq!(
+        helper::list(&mut helper, Context::default(), false)?,
+        [entry("a.example", "a", None), entry("b.example", "b", None)],
+        "passwords are removed by default"
+    );
+    assert_eq!(
+        helper::list(&mut helper, Context::default(), true)?,
+        [
+            entry("a.example", "a", Some("pass-a")),
+            entry("b.example", "b", Some("pass-b"))
+        ]
+    );
+    assert_eq!(
+        helper::list(&mut script_helper("custom-helper"), Context::default(), true).ok(),
+        None,
+        "helpers failing on unknown operations fail the listing"
+    );
+    assert!(
+        matches!(
+            helper::invoke(&mut helper, &helper::Action::List(Context::default())).unwrap_err(),
+            helper::Error::ListAction
+        ),
+        "listing has no outcome, so invoking it directly is an error instead of silently returning nothing"
+    );
+    Ok(())
+}
+
 mod program {
     use gix_credentials::{helper, program::Kind, Program};
 
@@ -63,6 +102,14 @@ mod program {
         );
     }
 
+    #[test]
+    fn builtin_cannot_list() {
+        assert!(matches!(
+            helper::list(&mut Program::from_kind(Kind::Builtin), Default::default(), false).unwrap_err(),
+            helper::Error::ListUnsupported
+        ));
+    }
+
     #[test]
     fn script() {
         assert_eq!(
@@ -124,6 +171,64 @@ mod program {
     }
 }
 
+#[cfg(feature = "async-client")]
+mod invoke_async {
+    use gix_credentials::{helper, Program};
+
+    use crate::helper::invoke::script_helper;
+
+    #[test]
+    fn get() {
+        let mut outcome = futures_lite::future::block_on(helper::invoke_async(
+            &mut script_helper("last-pass"),
+            &helper::Action::get_for_url("https://github.com/byron/gitoxide"),
+        ))
+        .unwrap()
+        .expect("mock provides credentials");
+        assert_eq!(
+            outcome.consume_identity().expect("complete"),
+            gix_sec::identity::Account {
+                username: "user".into(),
+                password: "pass".into()
+            }
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn dropping_the_future_kills_and_reaps_the_helper() -> crate::Result {
+        let dir = gix_testtools::tempfile::tempdir()?;
+        let pid_path = dir.path().join("pid");
+        let mut helper = Program::from_custom_definition(format!(
+            "!f() {{ echo $$ > '{}'; exec sleep 60; }}; f",
+            pid_path.display()
+        ));
+        {
+            let action = helper::Action::get_for_url("https://example.com");
+            let mut invocation = Box::pin(helper::invoke_async(&mut helper, &action));
+            while !pid_path.is_file() || std::fs::read(&pid_path)?.last() != Some(&b'\n') {
+                assert!(
+                    futures_lite::future::block_on(futures_lite::future::poll_once(&mut invocation)).is_none(),
+                    "the helper keeps running until it is killed"
+                );
+                std::thread::sleep(std::time::Duration::from_millis(10));
+            }
+        }
+
+        let pid = std::fs::read_to_string(&pid_path)?.trim().to_owned();
+        let is_alive = std::process::Command::new("kill")
+            .args(["-0", &pid])
+            .stderr(std::process::Stdio::null())
+            .status()?
+            .success();
+        assert!(
+            !is_alive,
+            "the child was killed and reaped, so not even a zombie remains"
+        );
+        Ok(())
+    }
+}
+
 pub fn script_helper(name: &str) -> Program {
     let mut script = gix_path::to_unix_separators_on_windows(gix_path::into_bstr(
         gix_path::realpath(fixture_path(format!("{name}.sh"))).unwrap(),
@@ -68,6 +68,17 @@ pub fn sink(object_hash: gix_hash::Kind) -> Sink {
 
 mod sink;
 
+/// A reader over the decompressed data of a single object, whose kind and size are known before reading it.
+///
+/// Obtain it with [`store::Handle::open_streaming()`] or [`loose::Store::try_stream()`].
+pub struct Stream {
+    kind: gix_object::Kind,
+    size: u64,
+    inner: Box<dyn std::io::Read>,
+}
+
+mod stream;
+
 ///
 pub mod find;
 
@@ -131,6 +142,8 @@ pub struct Store {
     use_multi_pack_index: bool,
     /// The hash kind to use for some operations
     object_hash: gix_hash::Kind,
+    /// A function to call with the new generation whenever it changes, invalidating all previously handed out pack ids.
+    pub(crate) on_generation_change: parking_lot::Mutex<Option<std::sync::Arc<store::GenerationChangeFn>>>,
 }
 
 /// Create a new cached handle to the object store with support for additional options.
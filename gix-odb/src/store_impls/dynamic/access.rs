@@ -1,4 +1,4 @@
-use crate::Store;
+use crate::{store::GenerationChangeFn, Store};
 
 impl Store {
     /// The root path at which we expect to find all objects and packs, and which is the source of the
@@ -21,4 +21,24 @@ impl Store {
     pub fn replacements(&self) -> impl Iterator<Item = (gix_hash::ObjectId, gix_hash::ObjectId)> + '_ {
         self.replacements.iter().copied()
     }
+
+    /// Return the current generation of the store, which changes whenever a refresh from disk invalidates
+    /// previously handed out pack ids.
+    ///
+    /// It's cheap to call and can be polled by those who keep their own caches keyed by pack id.
+    pub fn current_generation(&self) -> u32 {
+        self.index.load().generation
+    }
+
+    /// Set `callback` to be called with the new generation whenever it changes, or remove a previously set callback with `None`,
+    /// returning the previous callback.
+    ///
+    /// A change of generation means that all previously handed out pack ids are invalidated. The callback is called after
+    /// all internal locks were released, so it's fine to use the store from within.
+    pub fn set_generation_change_callback(
+        &self,
+        callback: Option<Box<GenerationChangeFn>>,
+    ) -> Option<std::sync::Arc<GenerationChangeFn>> {
+        std::mem::replace(&mut *self.on_generation_change.lock(), callback.map(Into::into))
+    }
 }
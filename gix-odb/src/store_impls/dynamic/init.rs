@@ -122,6 +122,7 @@ impl Store {
             num_handles_stable: Default::default(),
             num_handles_unstable: Default::default(),
             num_disk_state_consolidation: Default::default(),
+            on_generation_change: Default::default(),
         })
     }
 }
@@ -1,5 +1,5 @@
 use std::{
-    collections::{BTreeMap, VecDeque},
+    collections::{BTreeMap, BTreeSet, VecDeque},
     ffi::OsStr,
     ops::Deref,
     path::{Path, PathBuf},
@@ -10,6 +10,8 @@ use std::{
     time::SystemTime,
 };
 
+use gix_features::progress::RawProgress;
+
 use crate::store::{handle, types, RefreshMode};
 
 pub(crate) struct Snapshot {
@@ -62,13 +64,64 @@ use crate::store::types::{Generation, IndexAndPacks, MutableIndexAndPack, PackId
 impl super::Store {
     /// Load all indices, refreshing from disk only if needed.
     pub(crate) fn load_all_indices(&self) -> Result<Snapshot, Error> {
+        self.load_all_indices_with_thread_limit(None)
+    }
+
+    /// Load all pack indices ahead of time using up to `thread_limit` threads, or as many threads as there are logical cores
+    /// if `None`, instead of loading them lazily one at a time as objects are looked up.
+    ///
+    /// This is useful if it's known that most objects will be looked up anyway, for instance before negotiating a fetch.
+    pub fn load_all_indices_in_parallel(&self, thread_limit: Option<usize>) -> Result<(), Error> {
+        self.load_all_indices_with_thread_limit(thread_limit).map(|_| ())
+    }
+
+    fn load_all_indices_with_thread_limit(&self, thread_limit: Option<usize>) -> Result<Snapshot, Error> {
         let mut snapshot = self.collect_snapshot();
         while let Some(new_snapshot) = self.load_one_index(RefreshMode::Never, snapshot.marker)? {
-            snapshot = new_snapshot
+            snapshot = new_snapshot;
+            self.load_remaining_indices_in_parallel(thread_limit);
         }
         Ok(snapshot)
     }
 
+    /// Load all indices that aren't loaded yet using up to `thread_limit` threads, or as many as there are logical cores,
+    /// as memory-mapping thousands of index files one by one adds noticeable latency.
+    ///
+    /// This does nothing if the store isn't initialized, or if the `parallel` feature of `gix-features` isn't enabled.
+    fn load_remaining_indices_in_parallel(&self, thread_limit: Option<usize>) {
+        let index = self.index.load();
+        if !index.is_initialized() {
+            return;
+        }
+        let num_unloaded = index
+            .slot_indices
+            .len()
+            .saturating_sub(index.next_index_to_load.load(Ordering::SeqCst));
+        let num_threads = gix_features::parallel::num_threads(thread_limit).min(num_unloaded);
+        if num_threads < 2 {
+            return;
+        }
+        gix_features::parallel::threads(|scope| {
+            for thread_id in 0..num_threads {
+                gix_features::parallel::build_thread()
+                    .name(format!("gix-odb.load-index.{thread_id}"))
+                    .spawn_scoped(scope, || while self.load_next_index(self.index.load()) {})
+                    .expect("valid name");
+            }
+        });
+    }
+
+    /// Refresh our view of the objects directory and its alternates from disk, reporting the amount of pack indices
+    /// that were discovered to `progress`.
+    ///
+    /// This happens lazily on first access, but with many packs it can take a noticeable amount of time, which is
+    /// when doing it ahead of time with progress can provide feedback to the user.
+    pub fn refresh_with_progress(&self, progress: &mut dyn RawProgress) -> Result<(), Error> {
+        let needs_init = !self.index.load().is_initialized();
+        self.consolidate_with_disk_state(needs_init, false /*load one new index*/, Some(progress))?;
+        Ok(())
+    }
+
     /// If `None` is returned, there is new indices and the caller should give up. This is a possibility even if it's allowed to refresh
     /// as here might be no change to pick up.
     pub(crate) fn load_one_index(
@@ -78,7 +131,11 @@ impl super::Store {
     ) -> Result<Option<Snapshot>, Error> {
         let index = self.index.load();
         if !index.is_initialized() {
-            return self.consolidate_with_disk_state(true /* needs_init */, false /*load one new index*/);
+            return self.consolidate_with_disk_state(
+                true,  /* needs_init */
+                false, /*load one new index*/
+                None,
+            );
         }
 
         if marker.generation != index.generation || marker.state_id != index.state_id() {
@@ -94,8 +151,13 @@ impl super::Store {
                 match refresh_mode {
                     RefreshMode::Never => Ok(None),
                     RefreshMode::AfterAllIndicesLoaded => {
-                        self.consolidate_with_disk_state(false /* needs init */, true /*load one new index*/)
+                        self.consolidate_with_disk_state(
+                            false, /* needs init */
+                            true,  /*load one new index*/
+                            None,
+                        )
                     }
+                    RefreshMode::AfterAllIndicesLoadedAppendOnly => self.append_new_indices(),
                 }
             }
         }
@@ -174,10 +236,12 @@ impl super::Store {
 
     /// refresh and possibly clear out our existing data structures, causing all pack ids to be invalidated.
     /// `load_new_index` is an optimization to at least provide one newly loaded pack after refreshing the slot map.
+    /// If set, `progress` is informed about each discovered index.
     pub(crate) fn consolidate_with_disk_state(
         &self,
         needs_init: bool,
         load_new_index: bool,
+        mut progress: Option<&mut dyn RawProgress>,
     ) -> Result<Option<Snapshot>, Error> {
         let index = self.index.load();
         let previous_index_state = Arc::as_ptr(&index) as usize;
@@ -227,11 +291,20 @@ impl super::Store {
             Arc::clone(&index.loose_dbs)
         };
 
+        // Obtain these before looking at the directory content to rather see a change too often than missing one.
+        let pack_dir_mtimes = Self::pack_dir_mtimes(&db_paths);
         let indices_by_modification_time = Self::collect_indices_and_mtime_sorted_by_size(
             db_paths,
             index.slot_indices.len().into(),
             self.use_multi_pack_index.then_some(self.object_hash),
         )?;
+        if let Some(progress) = progress.as_deref_mut() {
+            progress.set_name("discover pack indices".into());
+            progress.init(
+                Some(indices_by_modification_time.len()),
+                gix_features::progress::count("pack indices"),
+            );
+        }
         let mut idx_by_index_path: BTreeMap<_, _> = index
             .slot_indices
             .iter()
@@ -249,6 +322,9 @@ impl super::Store {
         // Figure out this number based on what we see while handling the existing indices
         let mut num_loaded_indices = 0;
         for (index_info, mtime) in indices_by_modification_time.into_iter().map(|(a, b, _)| (a, b)) {
+            if let Some(progress) = progress.as_deref_mut() {
+                progress.inc();
+            }
             match idx_by_index_path.remove(index_info.path()) {
                 Some(slot_idx) => {
                     let slot = &self.files[slot_idx];
@@ -381,6 +457,7 @@ impl super::Store {
                     .then(|| Arc::clone(&index.loaded_indices))
                     .unwrap_or_else(|| Arc::new(num_loaded_indices.into())),
                 num_indices_currently_being_loaded: Default::default(),
+                pack_dir_mtimes,
             });
             self.index.store(new_index);
         }
@@ -405,9 +482,10 @@ impl super::Store {
                 slot.generation.store(generation, Ordering::SeqCst);
             }
         }
+        self.unload_unused_packs_inner(&write);
 
         let new_index = self.index.load();
-        Ok(if index.state_id() == new_index.state_id() {
+        let res = if index.state_id() == new_index.state_id() {
             // there was no change, and nothing was loaded in the meantime, reflect that in the return value to not get into loops
             None
         } else {
@@ -415,7 +493,92 @@ impl super::Store {
                 self.load_next_index(new_index);
             }
             Some(self.collect_snapshot())
-        })
+        };
+
+        // Inform others only after releasing our lock so they can use the store without deadlocking.
+        drop(write);
+        if generation != index.generation {
+            let on_generation_change = self.on_generation_change.lock().clone();
+            if let Some(callback) = on_generation_change {
+                callback(generation);
+            }
+        }
+        Ok(res)
+    }
+
+    /// Like [`consolidate_with_disk_state()`][Self::consolidate_with_disk_state()], but only add indices which appeared in the
+    /// `pack` directories of our object databases, if their modification time changed since we last looked.
+    /// Known indices are never changed or removed to keep all pack ids stable, and alternates aren't re-read either.
+    fn append_new_indices(&self) -> Result<Option<Snapshot>, Error> {
+        let index = self.index.load();
+        let previous_index_state = Arc::as_ptr(&index) as usize;
+
+        // IMPORTANT: get a lock after we recorded the previous state.
+        let write = self.write.lock();
+        let index = self.index.load();
+        if previous_index_state != Arc::as_ptr(&index) as usize {
+            // Someone else took the look before and changed the index. Return it without doing any additional work.
+            return Ok(Some(self.collect_snapshot()));
+        }
+
+        let db_paths: Vec<_> = index.loose_dbs.iter().map(|ldb| ldb.path().to_owned()).collect();
+        let pack_dir_mtimes = Self::pack_dir_mtimes(&db_paths);
+        if pack_dir_mtimes == index.pack_dir_mtimes {
+            return Ok(None);
+        }
+
+        let known_index_paths: BTreeSet<_> = index
+            .slot_indices
+            .iter()
+            .filter_map(|&idx| Option::as_ref(&self.files[idx].files.load()).map(|f| f.index_path().to_owned()))
+            .collect();
+        let mut new_slot_map_indices = index.slot_indices.clone();
+        let mut free_slot_indices = (0..self.files.len()).filter(|idx| !index.slot_indices.contains(idx));
+        for (index_info, mtime, _size) in Self::collect_indices_and_mtime_sorted_by_size(
+            db_paths,
+            None,
+            self.use_multi_pack_index.then_some(self.object_hash),
+        )? {
+            if known_index_paths.contains(index_info.path()) {
+                continue;
+            }
+            // Only use empty slots to never change what existing pack ids refer to.
+            let slot_index = free_slot_indices
+                .by_ref()
+                .find(|&idx| self.files[idx].files.load().is_none())
+                .ok_or(Error::InsufficientSlots {
+                    current: self.files.len(),
+                    needed: 1,
+                })?;
+            Self::set_slot_to_index(&write, &self.files[slot_index], index_info, mtime, index.generation);
+            new_slot_map_indices.push(slot_index);
+        }
+
+        let has_new_indices = new_slot_map_indices.len() != index.slot_indices.len();
+        // The counters can't be shared with the previous index as they must not exceed the amount of its slot indices.
+        let new_index = Arc::new(SlotMapIndex {
+            slot_indices: new_slot_map_indices,
+            loose_dbs: Arc::clone(&index.loose_dbs),
+            generation: index.generation,
+            next_index_to_load: Arc::new(index.next_index_to_load.load(Ordering::SeqCst).into()),
+            loaded_indices: Arc::new(index.loaded_indices.load(Ordering::SeqCst).into()),
+            num_indices_currently_being_loaded: Default::default(),
+            pack_dir_mtimes,
+        });
+        self.index.store(Arc::clone(&new_index));
+        let res = has_new_indices.then(|| {
+            self.load_next_index(self.index.load());
+            self.collect_snapshot()
+        });
+        drop(write);
+        Ok(res)
+    }
+
+    fn pack_dir_mtimes(db_paths: &[PathBuf]) -> Vec<Option<SystemTime>> {
+        db_paths
+            .iter()
+            .map(|path| path.join("pack").metadata().and_then(|md| md.modified()).ok())
+            .collect()
     }
 
     pub(crate) fn collect_indices_and_mtime_sorted_by_size(
@@ -613,6 +776,49 @@ impl super::Store {
         self.num_handles_stable.load(Ordering::SeqCst) > 0
     }
 
+    /// Unmap the data of all packs which aren't used by any handle, while keeping their indices loaded, and return the
+    /// amount of unloaded packs. Nothing is unloaded if there are handles which require stable pack ids.
+    ///
+    /// Note that this must be called with a lock to the relevant state held.
+    pub(crate) fn unload_unused_packs_inner(&self, guard: &parking_lot::MutexGuard<'_, ()>) -> usize {
+        if self.maintain_stable_indices(guard) {
+            return 0;
+        }
+        let index = self.index.load();
+        let mut num_unloaded = 0;
+        for slot in index.slot_indices.iter().map(|idx| &self.files[*idx]) {
+            let _lock = slot.write.lock();
+            let unused_packs: Vec<_> = match Option::as_ref(&slot.files.load()) {
+                Some(IndexAndPacks::Index(bundle)) => bundle.data.is_unused().then_some(0).into_iter().collect(),
+                Some(IndexAndPacks::MultiIndex(multi)) => multi
+                    .data
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(idx, data)| data.is_unused().then_some(idx))
+                    .collect(),
+                None => continue,
+            };
+            if unused_packs.is_empty() {
+                continue;
+            }
+
+            let mut files = slot.files.load_full();
+            let files_mut = Arc::make_mut(&mut files);
+            match files_mut.as_mut() {
+                Some(IndexAndPacks::Index(bundle)) => bundle.data.unload(),
+                Some(IndexAndPacks::MultiIndex(multi)) => {
+                    for idx in &unused_packs {
+                        multi.data[*idx].unload();
+                    }
+                }
+                None => unreachable!("BUG: slots are only emptied while holding the write lock"),
+            }
+            slot.files.store(files);
+            num_unloaded += unused_packs.len();
+        }
+        num_unloaded
+    }
+
     pub(crate) fn collect_snapshot(&self) -> Snapshot {
         let index = self.index.load();
         let indices = if index.is_initialized() {
@@ -0,0 +1,170 @@
+use std::{collections::BTreeSet, io::Write, sync::atomic::AtomicBool};
+
+use gix_features::progress::Progress;
+
+use crate::store::{handle::SingleOrMultiIndex, load_index};
+
+///
+pub mod write_multi_pack_index {
+    /// The error returned by [`Store::write_multi_pack_index()`][crate::Store::write_multi_pack_index()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        LoadIndex(#[from] crate::store::load_index::Error),
+        #[error(transparent)]
+        Write(#[from] gix_pack::multi_index::write::Error),
+        #[error(transparent)]
+        Io(#[from] std::io::Error),
+        #[error("Could not move the multi-pack index into place")]
+        Persist(#[from] tempfile::PersistError),
+    }
+}
+
+/// Options for use in [`Store::multi_pack_index_hint()`][crate::Store::multi_pack_index_hint()].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// The minimal amount of packs that are not yet covered by a multi-pack index to recommend writing one.
+    ///
+    /// Defaults to 2, as that's when lookups start having to search more than one index.
+    pub min_packs_without_multi_index: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            min_packs_without_multi_index: 2,
+        }
+    }
+}
+
+/// A hint on whether or not a multi-pack index should be written, as returned by
+/// [`Store::multi_pack_index_hint()`][crate::Store::multi_pack_index_hint()].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MultiPackIndexHint {
+    /// A multi-pack index wouldn't speed up lookups, either because it already covers most packs or because there are
+    /// too few packs.
+    NoAction,
+    /// Writing a multi-pack index of all packs would speed up lookups.
+    Recommended {
+        /// The amount of packs which are not part of a multi-pack index.
+        num_packs_without_multi_index: usize,
+        /// The amount of objects in all of these packs.
+        num_objects_without_multi_index: u64,
+    },
+}
+
+impl super::Store {
+    /// Unmap the data of all packs which aren't currently used by any handle to free their memory, while keeping their
+    /// indices loaded, and return the amount of unloaded packs. Unloaded packs will be mapped again on next access.
+    ///
+    /// Nothing is unloaded as long as there are handles which [prevent pack unloading][super::Handle::prevent_pack_unload()],
+    /// as these rely on pack ids to remain valid.
+    ///
+    /// Note that this also happens automatically whenever the store refreshes its view of the object database.
+    pub fn unload_unused_packs(&self) -> usize {
+        let write = self.write.lock();
+        self.unload_unused_packs_inner(&write)
+    }
+
+    /// Write a multi-pack index covering all packs in the `pack` directory of our objects directory, and refresh our view
+    /// of the object database so that lookups are served by it. Return the checksum of the newly written multi-pack index,
+    /// or `None` if there were no packs to write it for.
+    ///
+    /// Packs are taken from all indices currently on disk, including those already covered by an existing multi-pack index,
+    /// which is rewritten to incorporate packs that were added since. Packs in alternate object databases are never included.
+    /// The new file is written next to the packs and atomically moved into place, with progress sent to `progress` and
+    /// interruptions checked via `should_interrupt`.
+    ///
+    /// Note that the index is written even if the store is configured
+    /// to [not use multi-pack indices](super::Store::use_multi_pack_index()), but it will only be used for lookups once
+    /// that changes.
+    pub fn write_multi_pack_index<P>(
+        &self,
+        progress: P,
+        should_interrupt: &AtomicBool,
+    ) -> Result<Option<gix_hash::ObjectId>, write_multi_pack_index::Error>
+    where
+        P: Progress,
+    {
+        let pack_dir = self.path.join("pack");
+        let snapshot = self.refresh_and_load_all_indices()?;
+        let index_paths: BTreeSet<_> = snapshot
+            .indices
+            .iter()
+            .flat_map(|lookup| match &lookup.file {
+                SingleOrMultiIndex::Single { index, .. } => vec![index.path().to_owned()],
+                SingleOrMultiIndex::Multi { index, .. } => index
+                    .index_names()
+                    .iter()
+                    .map(|name| index.path().parent().expect("multi-index is in a directory").join(name))
+                    .collect(),
+            })
+            .filter(|path| path.parent() == Some(pack_dir.as_path()))
+            .collect();
+        if index_paths.is_empty() {
+            return Ok(None);
+        }
+
+        let mut out = tempfile::NamedTempFile::new_in(&pack_dir)?;
+        let mut writer = std::io::BufWriter::new(out.as_file_mut());
+        let outcome = gix_pack::multi_index::File::write_from_index_paths(
+            index_paths.into_iter().collect(),
+            &mut writer,
+            progress,
+            should_interrupt,
+            gix_pack::multi_index::write::Options {
+                object_hash: self.object_hash,
+            },
+        )?;
+        writer.flush()?;
+        drop(writer);
+        out.persist(pack_dir.join("multi-pack-index"))?;
+
+        self.consolidate_with_disk_state(false /* needs init */, false /* load one new index */, None)?;
+        Ok(Some(outcome.multi_index_checksum))
+    }
+
+    /// Return a hint about whether writing a multi-pack index would speed up object lookups, based on the amount of
+    /// packs which are not yet covered by one and as configured by `options`.
+    ///
+    /// Note that this refreshes our view of the object database and loads all indices to inspect them. If the store is configured
+    /// to [not use multi-pack indices](super::Store::use_multi_pack_index()), writing them won't help and
+    /// [`MultiPackIndexHint::NoAction`] is returned.
+    pub fn multi_pack_index_hint(&self, options: Options) -> Result<MultiPackIndexHint, load_index::Error> {
+        if !self.use_multi_pack_index() {
+            return Ok(MultiPackIndexHint::NoAction);
+        }
+        let snapshot = self.refresh_and_load_all_indices()?;
+        let (num_packs, num_objects) = snapshot
+            .indices
+            .iter()
+            .filter_map(|lookup| match &lookup.file {
+                SingleOrMultiIndex::Single { index, .. } => Some(u64::from(index.num_objects())),
+                SingleOrMultiIndex::Multi { .. } => None,
+            })
+            .fold((0, 0), |(num_packs, num_objects), objects| {
+                (num_packs + 1, num_objects + objects)
+            });
+
+        Ok(
+            if num_packs != 0 && num_packs >= options.min_packs_without_multi_index {
+                MultiPackIndexHint::Recommended {
+                    num_packs_without_multi_index: num_packs,
+                    num_objects_without_multi_index: num_objects,
+                }
+            } else {
+                MultiPackIndexHint::NoAction
+            },
+        )
+    }
+
+    /// Pick up packs that were added or removed on disk since our view of the object database was last refreshed,
+    /// and load all of their indices.
+    fn refresh_and_load_all_indices(&self) -> Result<load_index::Snapshot, load_index::Error> {
+        let needs_init = !self.index.load().is_initialized();
+        self.consolidate_with_disk_state(needs_init, false /* load one new index */, None)?;
+        self.load_all_indices()
+    }
+}
@@ -35,6 +35,12 @@ pub enum RefreshMode {
     /// clearing internal caches which is likely to change pack ids and it will trigger unloading of packs as they are missing on disk.
     #[default]
     AfterAllIndicesLoaded,
+    /// Like [`AfterAllIndicesLoaded`][RefreshMode::AfterAllIndicesLoaded], but only pick up pack indices that were added
+    /// since the last refresh, which is cheap as it only happens if the modification time of a `pack` directory changed.
+    /// Removed or changed indices won't be noticed, and all pack ids remain valid.
+    ///
+    /// Note that on filesystems with coarse modification times, indices that are added in quick succession may be missed.
+    AfterAllIndicesLoadedAppendOnly,
     /// Use this if you expect a lot of missing objects that shouldn't trigger refreshes even after all packs are loaded.
     /// This comes at the risk of not learning that the packs have changed in the mean time.
     Never,
@@ -55,6 +61,8 @@ pub mod prefix;
 
 mod header;
 
+mod stream;
+
 ///
 pub mod iter;
 
@@ -79,7 +87,14 @@ mod load_one;
 
 mod metrics;
 
+///
+pub mod maintenance;
+
 mod access;
 
+/// A function called with the new generation of a store whenever it changes, see
+/// [`Store::set_generation_change_callback()`][crate::Store::set_generation_change_callback()].
+pub type GenerationChangeFn = dyn Fn(u32) + Send + Sync;
+
 ///
 pub mod structure;
@@ -24,6 +24,35 @@ pub mod lookup {
     pub type Outcome = Result<gix_hash::ObjectId, ()>;
 }
 
+///
+pub mod lookup_hex {
+    /// Returned by [`Store::lookup_prefix()`][crate::Store::lookup_prefix()]
+    #[derive(thiserror::Error, Debug)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Could not parse the hexadecimal prefix")]
+        ParseHex(#[from] gix_hash::prefix::from_hex::Error),
+        #[error("The prefix is not valid for the requested hash kind")]
+        Prefix(#[from] gix_hash::prefix::Error),
+        #[error(transparent)]
+        Lookup(#[from] super::lookup::Error),
+    }
+
+    /// The outcome of [`Store::lookup_prefix()`][crate::Store::lookup_prefix()].
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum PrefixLookup {
+        /// No object matched the prefix.
+        NotFound,
+        /// Exactly one object matched the prefix.
+        Unique(gix_hash::ObjectId),
+        /// More than one object matched the prefix.
+        Ambiguous {
+            /// All objects matching the prefix, sorted in ascending order.
+            candidates: Vec<gix_hash::ObjectId>,
+        },
+    }
+}
+
 ///
 pub mod disambiguate {
     /// A potentially ambiguous prefix for use with `Handle::disambiguate_prefix()`.
@@ -74,6 +103,50 @@ pub mod disambiguate {
     }
 }
 
+impl super::Store {
+    /// Resolve the abbreviated object id `hex_prefix` to the full id of an object of kind `hash_kind`, after loading
+    /// all indices currently available on disk.
+    ///
+    /// Unlike [`Handle::lookup_prefix()`], all candidates are collected from packs and loose objects alike so that
+    /// ambiguous prefixes can be presented to the user.
+    pub fn lookup_prefix(
+        &self,
+        hex_prefix: &str,
+        hash_kind: gix_hash::Kind,
+    ) -> Result<lookup_hex::PrefixLookup, lookup_hex::Error> {
+        let prefix = gix_hash::Prefix::from_hex(hex_prefix)?;
+        let prefix = if prefix.as_oid().kind() == hash_kind {
+            prefix
+        } else {
+            let mut id = gix_hash::ObjectId::null(hash_kind);
+            let dst = id.as_mut_slice();
+            let copy_len = ((prefix.hex_len() + 1) / 2).min(dst.len());
+            dst[..copy_len].copy_from_slice(&prefix.as_oid().as_bytes()[..copy_len]);
+            gix_hash::Prefix::new(id, prefix.hex_len())?
+        };
+
+        let snapshot = self.load_all_indices().map_err(lookup::Error::from)?;
+        let mut candidates = HashSet::default();
+        for index in &snapshot.indices {
+            index.lookup_prefix(prefix, Some(&mut candidates));
+        }
+        for lodb in snapshot.loose_dbs.iter() {
+            lodb.lookup_prefix(prefix, Some(&mut candidates))
+                .map_err(lookup::Error::from)?;
+        }
+
+        let mut candidates: Vec<_> = candidates.into_iter().collect();
+        Ok(match candidates.len() {
+            0 => lookup_hex::PrefixLookup::NotFound,
+            1 => lookup_hex::PrefixLookup::Unique(candidates[0]),
+            _ => {
+                candidates.sort();
+                lookup_hex::PrefixLookup::Ambiguous { candidates }
+            }
+        })
+    }
+}
+
 impl<S> Handle<S>
 where
     S: Deref<Target = super::Store> + Clone,
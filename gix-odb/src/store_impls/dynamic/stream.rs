@@ -0,0 +1,144 @@
+use std::{io, ops::Deref, sync::Arc};
+
+use gix_features::zlib;
+
+use crate::{store::handle, Find, Stream};
+
+impl<S> super::Handle<S>
+where
+    S: Deref<Target = super::Store> + Clone,
+{
+    /// Return a reader over the decompressed data of the object identified by `id`, which also knows the object's kind and size,
+    /// or an error of kind [`NotFound`][io::ErrorKind::NotFound] if it doesn't exist.
+    ///
+    /// Loose objects and packed objects which aren't deltified are decompressed incrementally while reading, keeping memory usage
+    /// bounded no matter the size of the object. This is typically the case for objects larger than `core.bigFileThreshold`.
+    /// Deltified objects are fully resolved in memory first as applying deltas requires random access to their base objects.
+    pub fn open_streaming(&self, id: impl AsRef<gix_hash::oid>) -> io::Result<Stream> {
+        let id = id.as_ref();
+        let mut lookup_id = id;
+        if !self.ignore_replacements {
+            if let Ok(pos) = self
+                .store
+                .replacements
+                .binary_search_by(|(map_this, _)| map_this.as_ref().cmp(id))
+            {
+                lookup_id = self.store.replacements[pos].1.as_ref();
+            }
+        }
+        if let Some(stream) = self.try_stream_inner(lookup_id)? {
+            return Ok(stream);
+        }
+
+        let mut buf = Vec::new();
+        let kind = self
+            .try_find(id, &mut buf)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("Object {id} could not be found")))?
+            .kind;
+        Ok(Stream::new(kind, buf.len() as u64, io::Cursor::new(buf)))
+    }
+
+    /// Return a stream for `id` if it is a loose object or an undeltified packed object, or `None` if it needs to be
+    /// resolved in memory or couldn't be found.
+    fn try_stream_inner(&self, id: &gix_hash::oid) -> io::Result<Option<Stream>> {
+        let mut snapshot = self.snapshot.borrow_mut();
+        loop {
+            let marker = snapshot.marker;
+            for index in snapshot.indices.iter_mut() {
+                if let Some(handle::index_lookup::Outcome {
+                    object_index: handle::IndexForObjectInPack { pack_id, pack_offset },
+                    index_file: _,
+                    pack: possibly_pack,
+                }) = index.lookup(id)
+                {
+                    let pack = match possibly_pack {
+                        Some(pack) => pack.clone(),
+                        None => match self.store.load_pack(pack_id, marker)? {
+                            Some(pack) => {
+                                *possibly_pack = Some(pack.clone());
+                                pack
+                            }
+                            // Let the regular lookup deal with refreshing the index.
+                            None => return Ok(None),
+                        },
+                    };
+                    let entry = pack.entry(pack_offset);
+                    return Ok(entry.header.as_kind().map(|kind| {
+                        let end = pack.pack_end() as u64;
+                        let data = PackEntryData {
+                            pack,
+                            pos: entry.data_offset,
+                            end,
+                        };
+                        let inflate = zlib::stream::inflate::ReadBoxed {
+                            inner: data,
+                            decompressor: Box::new(zlib::Decompress::new(true)),
+                        };
+                        Stream::new(
+                            kind,
+                            entry.decompressed_size,
+                            io::Read::take(inflate, entry.decompressed_size),
+                        )
+                    }));
+                }
+            }
+
+            for lodb in snapshot.loose_dbs.iter() {
+                if let Some(stream) = lodb
+                    .try_stream(id)
+                    .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?
+                {
+                    return Ok(Some(stream));
+                }
+            }
+
+            match self
+                .store
+                .load_one_index(self.refresh, snapshot.marker)
+                .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?
+            {
+                Some(new_snapshot) => {
+                    *snapshot = new_snapshot;
+                    self.clear_cache();
+                }
+                None => return Ok(None),
+            }
+        }
+    }
+}
+
+/// The compressed data of a pack entry, read directly from the memory-mapped pack without copying it.
+struct PackEntryData {
+    pack: Arc<gix_pack::data::File>,
+    pos: u64,
+    end: u64,
+}
+
+impl io::Read for PackEntryData {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let bytes_read = {
+            let data = io::BufRead::fill_buf(self)?;
+            let bytes_read = data.len().min(buf.len());
+            buf[..bytes_read].copy_from_slice(&data[..bytes_read]);
+            bytes_read
+        };
+        io::BufRead::consume(self, bytes_read);
+        Ok(bytes_read)
+    }
+}
+
+impl io::BufRead for PackEntryData {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.pack.entry_slice(self.pos..self.end).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "Pack entry reaches past the end of the pack",
+            )
+        })
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos += amt as u64;
+    }
+}
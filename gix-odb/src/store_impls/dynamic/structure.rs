@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::{collections::BTreeSet, path::PathBuf};
 
 use crate::{store::load_index, types::IndexAndPacks, Store};
 
@@ -44,6 +44,20 @@ pub enum IndexState {
     Unloaded,
 }
 
+/// The amount of bytes used by an object database on disk, as returned by [`Store::on_disk_size()`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DbSize {
+    /// The amount of loose objects.
+    pub num_loose_objects: u64,
+    /// The total size of all loose object files in bytes.
+    pub loose_bytes: u64,
+    /// The amount of packs.
+    pub num_packs: u64,
+    /// The total size of all pack data files and their indices in bytes.
+    pub packed_bytes: u64,
+}
+
 impl Store {
     /// Return information about all files known to us as well as their loading state.
     ///
@@ -55,7 +69,7 @@ impl Store {
         let _span = gix_features::trace::detail!("gix_odb::Store::structure()");
         let index = self.index.load();
         if !index.is_initialized() {
-            self.consolidate_with_disk_state(true, false /*load one new index*/)?;
+            self.consolidate_with_disk_state(true, false /*load one new index*/, None)?;
         }
         let index = self.index.load();
         let mut res: Vec<_> = index
@@ -96,6 +110,53 @@ impl Store {
         Ok(res)
     }
 
+    /// Return the amount of bytes used by all loose objects and all packs along with their indices, as well as their
+    /// respective counts, similar to what `git count-objects` reports.
+    ///
+    /// Packs are those currently known to the store, including those in alternate object databases, without loading
+    /// them, so the result reflects the state as of the last refresh. Loose objects are counted by traversing all loose
+    /// object databases, which is the most expensive part of this call. Multi-pack indices are not counted.
+    pub fn on_disk_size(&self) -> std::io::Result<DbSize> {
+        let _span = gix_features::trace::detail!("gix_odb::Store::on_disk_size()");
+        let index = self.index.load();
+        if !index.is_initialized() {
+            self.consolidate_with_disk_state(true, false /*load one new index*/, None)
+                .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
+        }
+        let index = self.index.load();
+        let mut size = DbSize::default();
+        for db in index.loose_dbs.iter() {
+            for id in db.iter() {
+                let id = id.map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
+                size.num_loose_objects += 1;
+                size.loose_bytes += std::fs::metadata(db.object_path(&id))?.len();
+            }
+        }
+
+        let mut pack_paths = BTreeSet::new();
+        for slot in index.slot_indices.iter().map(|idx| &self.files[*idx]) {
+            match &**slot.files.load() {
+                Some(IndexAndPacks::Index(bundle)) => {
+                    pack_paths.insert(bundle.data.path().to_owned());
+                }
+                Some(IndexAndPacks::MultiIndex(bundle)) => {
+                    pack_paths.extend(bundle.data.iter().map(|pack| pack.path().to_owned()));
+                }
+                None => {}
+            }
+        }
+        for pack_path in pack_paths {
+            size.num_packs += 1;
+            size.packed_bytes += std::fs::metadata(&pack_path)?.len();
+            match std::fs::metadata(pack_path.with_extension("idx")) {
+                Ok(index) => size.packed_bytes += index.len(),
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(size)
+    }
+
     /// Provide a list of all `objects` directories of `alternate` object database paths.
     /// This list might be empty if there are no alternates.
     ///
@@ -103,7 +164,7 @@ impl Store {
     pub fn alternate_db_paths(&self) -> Result<Vec<PathBuf>, load_index::Error> {
         let index = self.index.load();
         if !index.is_initialized() {
-            self.consolidate_with_disk_state(true, false /*load one new index*/)?;
+            self.consolidate_with_disk_state(true, false /*load one new index*/, None)?;
         }
         let index = self.index.load();
         Ok(index
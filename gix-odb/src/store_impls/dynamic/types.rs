@@ -104,6 +104,9 @@ pub struct SlotMapIndex {
     /// The amount of indices that are currently being loaded.
     /// Zero if no loading operation is currently happening, or more otherwise.
     pub(crate) num_indices_currently_being_loaded: Arc<AtomicU16>,
+    /// The modification time of the `pack` directory of each of the `loose_dbs` at the time their indices were collected,
+    /// or `None` if it couldn't be obtained.
+    pub(crate) pack_dir_mtimes: Vec<Option<SystemTime>>,
 }
 
 impl SlotMapIndex {
@@ -228,6 +231,20 @@ impl<T: Clone> OnDiskFile<T> {
     }
 }
 
+impl<T> OnDiskFile<Arc<T>> {
+    /// Return true if we hold a memory map of the file which isn't shared with anyone else.
+    pub(crate) fn is_unused(&self) -> bool {
+        matches!(&self.state, OnDiskFileState::Loaded(v) if Arc::strong_count(v) == 1)
+    }
+
+    /// Drop our memory map of the file so it will be loaded from disk again on next access.
+    pub(crate) fn unload(&mut self) {
+        if let OnDiskFileState::Loaded(_) = self.state {
+            self.state = OnDiskFileState::Unloaded;
+        }
+    }
+}
+
 #[derive(Clone)]
 pub(crate) struct IndexFileBundle {
     pub index: OnDiskFile<Arc<gix_pack::index::File>>,
@@ -180,6 +180,50 @@ impl Store {
         Ok(Some((size, kind)))
     }
 
+    /// Return a reader over the decompressed data of the object identified by `id`, or `None` if it doesn't exist in this database.
+    ///
+    /// Unlike with [`try_find()`][Store::try_find()], the data is decompressed incrementally while reading, keeping memory usage
+    /// bounded no matter the size of the object.
+    pub fn try_stream(&self, id: impl AsRef<gix_hash::oid>) -> Result<Option<crate::Stream>, Error> {
+        let path = hash_path(id.as_ref(), self.path.clone());
+        let file = match fs::File::open(&path) {
+            Ok(f) => f,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => {
+                return Err(Error::Io {
+                    source: err,
+                    action: Self::OPEN_ACTION,
+                    path,
+                })
+            }
+        };
+
+        let mut inflate = zlib::stream::inflate::ReadBoxed {
+            inner: std::io::BufReader::new(file),
+            decompressor: Box::new(zlib::Decompress::new(true)),
+        };
+        let mut header = [0_u8; HEADER_MAX_SIZE];
+        let mut header_len = 0;
+        while header_len < header.len() {
+            let bytes_read = inflate
+                .read(&mut header[header_len..][..1])
+                .map_err(|source| Error::Io {
+                    source,
+                    action: "read",
+                    path: path.to_owned(),
+                })?;
+            if bytes_read == 0 {
+                break;
+            }
+            header_len += 1;
+            if header[header_len - 1] == 0 {
+                break;
+            }
+        }
+        let (kind, size, _header_size) = gix_object::decode::loose_header(&header[..header_len])?;
+        Ok(Some(crate::Stream::new(kind, size as u64, inflate.take(size as u64))))
+    }
+
     fn find_inner<'a>(&self, id: &gix_hash::oid, buf: &'a mut Vec<u8>) -> Result<gix_object::Data<'a>, Error> {
         let path = hash_path(id, self.path.clone());
 
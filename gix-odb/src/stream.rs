@@ -0,0 +1,38 @@
+use std::io;
+
+use crate::Stream;
+
+impl Stream {
+    pub(crate) fn new(kind: gix_object::Kind, size: u64, inner: impl io::Read + 'static) -> Self {
+        Stream {
+            kind,
+            size,
+            inner: Box::new(inner),
+        }
+    }
+
+    /// The kind of the object whose data is being read.
+    pub fn kind(&self) -> gix_object::Kind {
+        self.kind
+    }
+
+    /// The size of the decompressed object data in bytes, which is the amount of bytes this reader will produce.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+}
+
+impl io::Read for Stream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+impl std::fmt::Debug for Stream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Stream")
+            .field("kind", &self.kind)
+            .field("size", &self.size)
+            .finish_non_exhaustive()
+    }
+}
@@ -21,3 +21,4 @@ pub mod header;
 pub mod regression;
 pub mod sink;
 pub mod store;
+pub mod stream;
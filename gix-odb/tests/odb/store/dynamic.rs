@@ -143,6 +143,133 @@ fn multi_index_access() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn multi_pack_index_hint() -> crate::Result {
+    use gix_odb::store::maintenance::{MultiPackIndexHint, Options};
+
+    let dir =
+        gix_testtools::scripted_fixture_read_only_with_args("make_repo_multi_index.sh", Some("without-multi-index"))?;
+    let handle = gix_odb::at(dir.join(".git/objects"))?;
+    assert_eq!(
+        handle.store_ref().multi_pack_index_hint(Options::default())?,
+        MultiPackIndexHint::Recommended {
+            num_packs_without_multi_index: 15,
+            num_objects_without_multi_index: handle.packed_object_count()?,
+        },
+        "many single packs benefit from a multi-pack index"
+    );
+    assert_eq!(
+        handle.store_ref().multi_pack_index_hint(Options {
+            min_packs_without_multi_index: 16
+        })?,
+        MultiPackIndexHint::NoAction,
+        "the threshold is configurable"
+    );
+
+    let dir = gix_testtools::scripted_fixture_read_only("make_repo_multi_index.sh")?;
+    let handle = gix_odb::at(dir.join(".git/objects"))?;
+    assert_eq!(
+        handle.store_ref().multi_pack_index_hint(Options::default())?,
+        MultiPackIndexHint::NoAction,
+        "all packs are covered by the multi-pack index already"
+    );
+    Ok(())
+}
+
+#[test]
+fn write_multi_pack_index() -> crate::Result {
+    let (handle, objects_dir) = db_with_all_object_sources()?;
+    let count_objects = |handle: &gix_odb::Handle| -> crate::Result<usize> {
+        let mut buf = Vec::new();
+        let mut count = 0;
+        for oid in handle.iter()? {
+            handle.find(oid?, &mut buf)?;
+            count += 1;
+        }
+        Ok(count)
+    };
+    let num_objects = count_objects(&handle)?;
+    assert_eq!(
+        handle.store_ref().metrics().known_reachable_indices,
+        2,
+        "the existing multi-pack index only covers two of the three packs"
+    );
+
+    let checksum = handle.store_ref().write_multi_pack_index(
+        gix_features::progress::Discard,
+        &std::sync::atomic::AtomicBool::default(),
+    )?;
+    assert!(checksum.is_some(), "there are packs to write a multi-pack index for");
+
+    let multi_index = gix_odb::pack::multi_index::File::at(objects_dir.path().join("pack/multi-pack-index"))?;
+    assert_eq!(
+        multi_index.num_indices(),
+        3,
+        "the existing multi-pack index was rewritten to include the remaining pack"
+    );
+    assert_eq!(Some(multi_index.checksum()), checksum);
+
+    assert_eq!(
+        handle.store_ref().metrics().known_reachable_indices,
+        1,
+        "the store was refreshed and only knows the multi-pack index now"
+    );
+    assert_eq!(
+        count_objects(&handle)?,
+        num_objects,
+        "all objects are still reachable through the multi-pack index"
+    );
+
+    let empty_dir = gix_testtools::tempfile::tempdir()?;
+    std::fs::create_dir(empty_dir.path().join("pack"))?;
+    let handle = gix_odb::at(empty_dir.path())?;
+    assert_eq!(
+        handle.store_ref().write_multi_pack_index(
+            gix_features::progress::Discard,
+            &std::sync::atomic::AtomicBool::default()
+        )?,
+        None,
+        "without packs there is nothing to write"
+    );
+    Ok(())
+}
+
+#[test]
+fn write_multi_pack_index_includes_packs_added_after_opening_the_store() -> crate::Result {
+    let objects_dir = gix_testtools::tempfile::tempdir()?;
+    let pack_dir = objects_dir.path().join("pack");
+    std::fs::create_dir(&pack_dir)?;
+    let copy_pack = |name: &str| -> std::io::Result<()> {
+        for ext in ["idx", "pack"] {
+            let file_name = format!("{name}.{ext}");
+            std::fs::copy(fixture_path("objects/pack").join(&file_name), pack_dir.join(file_name))?;
+        }
+        Ok(())
+    };
+    copy_pack("pack-a2bf8e71d8c18879e499335762dd95119d93d9f1")?;
+    let handle = gix_odb::at(objects_dir.path())?;
+    handle.packed_object_count()?;
+    assert_eq!(handle.store_ref().metrics().known_reachable_indices, 1);
+
+    copy_pack("pack-c0438c19fb16422b6bbcce24387b3264416d485b")?;
+    let checksum = handle.store_ref().write_multi_pack_index(
+        gix_features::progress::Discard,
+        &std::sync::atomic::AtomicBool::default(),
+    )?;
+    let multi_index = gix_odb::pack::multi_index::File::at(pack_dir.join("multi-pack-index"))?;
+    assert_eq!(
+        multi_index.num_indices(),
+        2,
+        "the pack added after the store was opened is included as well"
+    );
+    assert_eq!(Some(multi_index.checksum()), checksum);
+    assert!(
+        handle.contains(hex_to_id("dd25c539efbb0ab018caa4cda2d133285634e9b5")),
+        "objects of the new pack are found through the multi-pack index"
+    );
+    Ok(())
+}
+
 #[test]
 fn multi_index_keep_open() -> crate::Result {
     let dir = gix_testtools::scripted_fixture_writable("make_repo_multi_index.sh")?;
@@ -523,6 +650,145 @@ fn packed_object_count_causes_all_indices_to_be_loaded() {
     assert_all_indices_loaded(&handle, 1, 2);
 }
 
+#[test]
+fn unused_packs_can_be_unloaded_unless_pack_ids_must_remain_stable() -> crate::Result {
+    let handle = db();
+    let store = handle.store();
+    // Create the stable handle before packs are loaded so its snapshot doesn't keep them in use.
+    let mut stable_handle = store.to_handle();
+    stable_handle.prevent_pack_unload();
+
+    let mut buf = Vec::new();
+    for hex_id in [
+        "501b297447a8255d3533c6858bb692575cdefaa0", // pack 11fd
+        "4dac9989f96bc5b5b1263b582c08f0c5f0b58542", // pack a2bf
+        "dd25c539efbb0ab018caa4cda2d133285634e9b5", // pack c043
+    ] {
+        handle.find(hex_to_id(hex_id), &mut buf)?;
+    }
+    assert_eq!(store.metrics().open_reachable_packs, 3);
+    drop(handle);
+    assert_eq!(
+        store.unload_unused_packs(),
+        0,
+        "nothing is unloaded while handles rely on stable pack ids, even if no handle uses the packs"
+    );
+    assert_eq!(store.metrics().open_reachable_packs, 3);
+
+    drop(stable_handle);
+    assert_eq!(store.unload_unused_packs(), 3);
+    let metrics = store.metrics();
+    assert_eq!(metrics.open_reachable_packs, 0, "the pack data maps were released");
+    assert_eq!(metrics.open_reachable_indices, 3, "indices remain loaded");
+
+    let handle = store.to_handle();
+    handle.find(hex_to_id("dd25c539efbb0ab018caa4cda2d133285634e9b5"), &mut buf)?;
+    assert_eq!(
+        store.metrics().open_reachable_packs,
+        1,
+        "unloaded packs are mapped again on demand"
+    );
+    assert_eq!(
+        store.unload_unused_packs(),
+        0,
+        "packs used by a handle are never unloaded"
+    );
+    drop(handle);
+    assert_eq!(store.unload_unused_packs(), 1, "once unused, it can be unloaded again");
+    Ok(())
+}
+
+#[test]
+fn fresh_stores_discover_their_indices_on_first_use() -> crate::Result {
+    let handle = crate::odb::db_small_packs();
+    let metrics = handle.store_ref().metrics();
+    assert_eq!(metrics.num_refreshes, 0, "nothing is read from disk upon instantiation");
+    assert_eq!(metrics.known_reachable_indices, 0);
+
+    assert_eq!(
+        handle.packed_object_count()?,
+        3,
+        "one pack with one object, another one with two"
+    );
+    let metrics = handle.store_ref().metrics();
+    assert_eq!(
+        metrics.num_refreshes, 1,
+        "a single consolidation with the disk state suffices"
+    );
+    assert_eq!(metrics.known_reachable_indices, 2, "both packs were discovered");
+    assert_eq!(metrics.open_reachable_indices, 2);
+    assert_eq!(metrics.loose_dbs, 1);
+
+    let indices = handle
+        .store_ref()
+        .structure()?
+        .into_iter()
+        .filter(|record| matches!(record, store::structure::Record::Index { .. }))
+        .count();
+    assert_eq!(indices, 2);
+    Ok(())
+}
+
+#[test]
+fn loading_all_indices_at_once_yields_the_same_indices_as_loading_them_one_by_one() -> crate::Result {
+    fn db_with_many_packs(num_packs: usize) -> crate::Result<(gix_odb::Handle, tempfile::TempDir)> {
+        let objects_dir = gix_testtools::tempfile::tempdir()?;
+        let pack_dir = objects_dir.path().join("pack");
+        std::fs::create_dir(&pack_dir)?;
+        let source = fixture_path("objects/pack/pack-11fdfa9e156ab73caae3b6da867192221f2089c2");
+        for pack_id in 0..num_packs {
+            let name = format!("pack-{pack_id:040x}");
+            for extension in ["idx", "pack"] {
+                std::fs::copy(
+                    source.with_extension(extension),
+                    pack_dir.join(&name).with_extension(extension),
+                )?;
+            }
+        }
+        Ok((gix_odb::at(objects_dir.path())?, objects_dir))
+    }
+
+    let num_packs = 64;
+    let (all_at_once, _tmp) = db_with_many_packs(num_packs)?;
+    let (mut one_by_one, _tmp) = db_with_many_packs(num_packs)?;
+
+    let mut objects_in_all_at_once = all_at_once.iter()?.collect::<Result<Vec<_>, _>>()?;
+    let all_at_once_metrics = all_at_once.store_ref().metrics();
+    assert_eq!(all_at_once_metrics.open_reachable_indices, num_packs);
+    assert_eq!(all_at_once_metrics.known_reachable_indices, num_packs);
+
+    one_by_one.refresh_never();
+    assert!(
+        !one_by_one.contains(hex_to_id("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa")),
+        "looking for a missing object loads one index after another"
+    );
+    let one_by_one_metrics = one_by_one.store_ref().metrics();
+    assert_eq!(
+        one_by_one_metrics.open_reachable_indices,
+        all_at_once_metrics.open_reachable_indices
+    );
+    assert_eq!(
+        one_by_one_metrics.known_reachable_indices,
+        all_at_once_metrics.known_reachable_indices
+    );
+
+    let mut objects_in_one_by_one = one_by_one.iter()?.collect::<Result<Vec<_>, _>>()?;
+    objects_in_all_at_once.sort();
+    objects_in_one_by_one.sort();
+    assert_eq!(objects_in_one_by_one, objects_in_all_at_once);
+    assert_eq!(all_at_once.packed_object_count()?, one_by_one.packed_object_count()?);
+
+    let (with_thread_limit, _tmp) = db_with_many_packs(num_packs)?;
+    with_thread_limit.store_ref().load_all_indices_in_parallel(Some(2))?;
+    let metrics = with_thread_limit.store_ref().metrics();
+    assert_eq!(
+        metrics.open_reachable_indices, num_packs,
+        "indices can be loaded ahead of time with a limited amount of threads"
+    );
+    assert_eq!(metrics.known_reachable_indices, num_packs);
+    Ok(())
+}
+
 mod disambiguate_prefix {
     use std::cmp::Ordering;
 
@@ -721,6 +987,51 @@ mod lookup_prefix {
     }
 }
 
+mod store_lookup_prefix {
+    use gix_odb::store::prefix::lookup_hex::PrefixLookup;
+
+    use crate::{odb::hex_to_id, store::dynamic::db_with_all_object_sources};
+
+    #[test]
+    fn unique_prefixes_resolve_to_the_full_id() -> crate::Result {
+        let (handle, _tmp) = db_with_all_object_sources()?;
+        assert_eq!(
+            handle.store_ref().lookup_prefix("a7065b5e9", gix_hash::Kind::Sha1)?,
+            PrefixLookup::Unique(hex_to_id("a7065b5e971a6d8b55875d8cf634a3a37202ab23"))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn ambiguous_prefixes_list_all_candidates() -> crate::Result {
+        let (handle, _tmp) = db_with_all_object_sources()?;
+        assert_eq!(
+            handle.store_ref().lookup_prefix("a706", gix_hash::Kind::Sha1)?,
+            PrefixLookup::Ambiguous {
+                candidates: vec![
+                    hex_to_id("a7065b5e971a6d8b55875d8cf634a3a37202ab23"),
+                    hex_to_id("a706d7cd20fc8ce71489f34b50cf01011c104193")
+                ]
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn non_existing_prefixes_are_not_found() -> crate::Result {
+        let (handle, _tmp) = db_with_all_object_sources()?;
+        assert_eq!(
+            handle.store_ref().lookup_prefix("0000000", gix_hash::Kind::Sha1)?,
+            PrefixLookup::NotFound
+        );
+        assert!(
+            handle.store_ref().lookup_prefix("a7x6", gix_hash::Kind::Sha1).is_err(),
+            "invalid hex is an error"
+        );
+        Ok(())
+    }
+}
+
 #[test]
 fn missing_objects_triggers_everything_is_loaded() {
     let handle = db();
@@ -784,6 +1095,41 @@ fn iterate_over_a_bunch_of_loose_and_packed_objects() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn on_disk_size_counts_loose_objects_and_packs_with_their_indices() -> crate::Result {
+    let (db, tmp) = db_with_all_object_sources()?;
+    let (mut loose_bytes, mut packed_bytes) = (0, 0);
+    for entry in std::fs::read_dir(tmp.path())? {
+        let entry = entry?;
+        if entry.file_name() == "pack" {
+            for entry in std::fs::read_dir(entry.path())? {
+                let path = entry?.path();
+                if path.extension().map_or(false, |ext| ext == "pack" || ext == "idx") {
+                    packed_bytes += std::fs::metadata(path)?.len();
+                }
+            }
+        } else {
+            for entry in std::fs::read_dir(entry.path())? {
+                loose_bytes += entry?.metadata()?.len();
+            }
+        }
+    }
+
+    let size = db.store_ref().on_disk_size()?;
+    assert_eq!(
+        size,
+        store::structure::DbSize {
+            num_loose_objects: 7,
+            loose_bytes,
+            num_packs: 3,
+            packed_bytes,
+        },
+        "packs covered by the multi-pack index are counted once, and the multi-pack index itself isn't counted"
+    );
+    assert!(size.loose_bytes > 0 && size.packed_bytes > size.loose_bytes);
+    Ok(())
+}
+
 #[test]
 fn auto_refresh_with_and_without_id_stability() -> crate::Result {
     let tmp = gix_testtools::tempfile::TempDir::new()?;
@@ -936,6 +1282,76 @@ fn auto_refresh_with_and_without_id_stability() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn append_only_refreshes_keep_pack_ids_stable() -> crate::Result {
+    use gix_pack::Find;
+    let tmp = gix_testtools::tempfile::TempDir::new()?;
+    assert!(
+        Command::new("git")
+            .arg("-C")
+            .arg(tmp.path())
+            .arg("init")
+            .arg("--bare")
+            .status()?
+            .success(),
+        "git should work"
+    );
+    let pack_dir = tmp.path().join("objects/pack");
+    gix_testtools::copy_recursively_into_existing_dir(fixture_path("objects/pack"), &pack_dir)?;
+    let hide_pack = |name: &str| {
+        let stem = pack_dir.join(name);
+        std::fs::rename(stem.with_extension("idx"), stem.with_extension("idx.bak")).unwrap();
+        std::fs::rename(stem.with_extension("pack"), stem.with_extension("pack.bak")).unwrap();
+    };
+    let unhide_pack = |name: &str| {
+        let stem = pack_dir.join(name);
+        std::fs::rename(stem.with_extension("idx.bak"), stem.with_extension("idx")).unwrap();
+        std::fs::rename(stem.with_extension("pack.bak"), stem.with_extension("pack")).unwrap();
+    };
+    hide_pack("pack-11fdfa9e156ab73caae3b6da867192221f2089c2");
+    hide_pack("pack-a2bf8e71d8c18879e499335762dd95119d93d9f1");
+
+    let mut handle = gix_odb::at(tmp.path().join("objects"))?;
+    handle.refresh = store::RefreshMode::AfterAllIndicesLoadedAppendOnly;
+    handle.prevent_pack_unload();
+    let mut buf = Vec::new();
+    let existing_id = hex_to_id("dd25c539efbb0ab018caa4cda2d133285634e9b5");
+    let location = handle.location_by_oid(existing_id, &mut buf).expect("object exists");
+    assert_eq!(handle.store_ref().metrics().num_refreshes, 1, "the initial refresh");
+
+    unhide_pack("pack-11fdfa9e156ab73caae3b6da867192221f2089c2");
+    // Don't depend on the granularity of the filesystem's modification times.
+    filetime::set_file_mtime(
+        &pack_dir,
+        filetime::FileTime::from_system_time(std::time::SystemTime::now() + std::time::Duration::from_secs(10)),
+    )?;
+    assert!(
+        handle
+            .location_by_oid(hex_to_id("501b297447a8255d3533c6858bb692575cdefaa0"), &mut buf)
+            .is_some(),
+        "the newly added pack is picked up"
+    );
+    let metrics = handle.store_ref().metrics();
+    assert_eq!(
+        metrics.num_refreshes, 1,
+        "appending new indices doesn't need a full consolidation with the disk state"
+    );
+    assert_eq!(metrics.known_reachable_indices, 2);
+    assert_eq!(metrics.known_packs, 2);
+    assert_eq!(metrics.unreachable_indices, 0);
+
+    assert!(
+        handle.entry_by_location(&location).is_some(),
+        "previously obtained locations remain valid"
+    );
+    assert_eq!(
+        handle.location_by_oid(existing_id, &mut buf),
+        Some(location),
+        "the pack id of existing objects didn't change"
+    );
+    Ok(())
+}
+
 mod verify {
     use std::sync::atomic::AtomicBool;
 
@@ -990,3 +1406,141 @@ mod verify {
         );
     }
 }
+
+mod generation_change_callback {
+    use std::sync::{Arc, Mutex};
+
+    use gix_odb::{store, Find};
+    use gix_testtools::fixture_path;
+
+    use crate::hex_to_id;
+
+    #[test]
+    fn fires_once_with_the_new_generation_when_a_slot_is_reused() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let pack_dir = tmp.path().join("objects/pack");
+        std::fs::create_dir_all(&pack_dir)?;
+        gix_testtools::copy_recursively_into_existing_dir(fixture_path("objects/pack"), &pack_dir)?;
+        let hide_pack = |name: &str| {
+            let stem = pack_dir.join(name);
+            std::fs::rename(stem.with_extension("idx"), stem.with_extension("idx.bak")).unwrap();
+            std::fs::rename(stem.with_extension("pack"), stem.with_extension("pack.bak")).unwrap();
+        };
+        let unhide_pack = |name: &str| {
+            let stem = pack_dir.join(name);
+            std::fs::rename(stem.with_extension("idx.bak"), stem.with_extension("idx")).unwrap();
+            std::fs::rename(stem.with_extension("pack.bak"), stem.with_extension("pack")).unwrap();
+        };
+        hide_pack("pack-11fdfa9e156ab73caae3b6da867192221f2089c2");
+        hide_pack("pack-a2bf8e71d8c18879e499335762dd95119d93d9f1");
+
+        let handle = gix_odb::at_opts(
+            tmp.path().join("objects"),
+            Vec::new(),
+            store::init::Options {
+                slots: store::init::Slots::Given(1),
+                ..Default::default()
+            },
+        )?;
+        let generations = Arc::new(Mutex::new(Vec::new()));
+        let previous = handle.store_ref().set_generation_change_callback(Some(Box::new({
+            let generations = Arc::clone(&generations);
+            move |generation| generations.lock().unwrap().push(generation)
+        })));
+        assert!(previous.is_none(), "there is no callback by default");
+
+        let mut buf = Vec::new();
+        assert!(handle
+            .find(hex_to_id("dd25c539efbb0ab018caa4cda2d133285634e9b5"), &mut buf)
+            .is_ok());
+        assert_eq!(handle.store_ref().current_generation(), 0);
+        assert!(
+            generations.lock().unwrap().is_empty(),
+            "filling empty slots doesn't change the generation"
+        );
+
+        hide_pack("pack-c0438c19fb16422b6bbcce24387b3264416d485b");
+        unhide_pack("pack-11fdfa9e156ab73caae3b6da867192221f2089c2");
+        assert!(
+            handle
+                .find(hex_to_id("501b297447a8255d3533c6858bb692575cdefaa0"), &mut buf)
+                .is_ok(),
+            "the only slot is reused for the new pack"
+        );
+        assert_eq!(handle.store_ref().current_generation(), 1);
+        assert_eq!(
+            *generations.lock().unwrap(),
+            vec![1],
+            "the callback fires exactly once with the new generation"
+        );
+        assert!(handle.store_ref().set_generation_change_callback(None).is_some());
+        Ok(())
+    }
+}
+
+mod refresh_with_progress {
+    use gix_features::progress::{Id, MessageLevel, RawProgress, Unit, UNKNOWN};
+
+    use crate::odb::{db, db_small_packs};
+
+    #[derive(Default)]
+    struct Counter {
+        max: Option<usize>,
+        step: usize,
+    }
+
+    impl RawProgress for Counter {
+        fn init(&mut self, max: Option<usize>, _unit: Option<Unit>) {
+            self.max = max;
+            self.step = 0;
+        }
+
+        fn set(&mut self, step: usize) {
+            self.step = step;
+        }
+
+        fn step(&self) -> usize {
+            self.step
+        }
+
+        fn inc_by(&mut self, step: usize) {
+            self.step += step;
+        }
+
+        fn set_name(&mut self, _name: String) {}
+
+        fn name(&self) -> Option<String> {
+            None
+        }
+
+        fn id(&self) -> Id {
+            UNKNOWN
+        }
+
+        fn message(&self, _level: MessageLevel, _message: String) {}
+    }
+
+    #[test]
+    fn each_discovered_index_is_reported() -> crate::Result {
+        for handle in [db(), db_small_packs()] {
+            let num_indices = std::fs::read_dir(handle.store_ref().path().join("pack"))?
+                .filter_map(Result::ok)
+                .filter(|entry| entry.path().extension().map_or(false, |ext| ext == "idx"))
+                .count();
+            assert_ne!(num_indices, 0, "fixtures have packs");
+
+            let mut progress = Counter::default();
+            handle.store_ref().refresh_with_progress(&mut progress)?;
+            assert_eq!(progress.max, Some(num_indices));
+            assert_eq!(progress.step, num_indices, "one step per discovered index");
+
+            let mut progress = Counter::default();
+            handle.store_ref().refresh_with_progress(&mut progress)?;
+            assert_eq!(
+                progress.step, num_indices,
+                "existing indices are discovered again on each refresh"
+            );
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+use std::io::Read;
+
+use gix_odb::Write;
+
+use crate::{hex_to_id, odb::db};
+
+fn pseudo_random_bytes(len: usize) -> Vec<u8> {
+    (0..len)
+        .scan(42_u32, |state, _| {
+            *state = state.wrapping_mul(1103515245).wrapping_add(12345);
+            Some((*state >> 16) as u8)
+        })
+        .collect()
+}
+
+#[test]
+fn loose_object() -> crate::Result {
+    let tmp = gix_testtools::tempfile::tempdir()?;
+    let db = gix_odb::at(tmp.path())?;
+    let data = pseudo_random_bytes(1024 * 1024);
+    let id = db.write_buf(gix_object::Kind::Blob, &data)?;
+
+    let mut stream = db.open_streaming(id)?;
+    assert_eq!(stream.kind(), gix_object::Kind::Blob);
+    assert_eq!(stream.size(), data.len() as u64, "the size is known before reading");
+    let mut actual = Vec::new();
+    stream.read_to_end(&mut actual)?;
+    assert!(actual == data, "the streamed data matches what was written");
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn loose_objects_are_read_from_disk_incrementally() -> crate::Result {
+    use std::os::unix::fs::PermissionsExt;
+
+    let tmp = gix_testtools::tempfile::tempdir()?;
+    let db = gix_odb::at(tmp.path())?;
+    let data = pseudo_random_bytes(4 * 1024 * 1024);
+    let id = db.write_buf(gix_object::Kind::Blob, &data)?;
+
+    let mut stream = db.open_streaming(id)?;
+    let mut buf = vec![0; 64 * 1024];
+    stream.read_exact(&mut buf)?;
+    assert_eq!(buf, data[..buf.len()]);
+
+    let hex = id.to_string();
+    let path = tmp.path().join(&hex[..2]).join(&hex[2..]);
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644))?;
+    std::fs::OpenOptions::new()
+        .write(true)
+        .open(&path)?
+        .set_len(128 * 1024)?;
+
+    let mut rest = Vec::new();
+    let res = stream.read_to_end(&mut rest);
+    assert!(
+        res.is_err() || buf.len() + rest.len() < data.len(),
+        "only a bounded amount of data was read when opening the stream, so truncating the file afterwards is noticeable"
+    );
+    Ok(())
+}
+
+#[test]
+fn pack_object() -> crate::Result {
+    let db = db();
+    let id = hex_to_id("dd25c539efbb0ab018caa4cda2d133285634e9b5"); // pack c043
+    let mut stream = db.open_streaming(id)?;
+    assert_eq!(stream.kind(), gix_object::Kind::Blob);
+    assert_eq!(stream.size(), 860);
+
+    let mut actual = Vec::new();
+    stream.read_to_end(&mut actual)?;
+    let mut buf = Vec::new();
+    let expected = gix_odb::Find::try_find(&db, id, &mut buf)?.expect("object exists");
+    assert_eq!(actual, expected.data);
+    Ok(())
+}
+
+#[test]
+fn missing_objects_are_not_found() {
+    let err = db()
+        .open_streaming(hex_to_id("0000000000000000000000000000000000000000"))
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+}
@@ -0,0 +1,101 @@
+use gix_hash::ObjectId;
+
+use crate::{data, data::decode::entry::ResolvedBase, Bundle};
+
+/// An object of a pack with all deltas resolved, as returned by [`Iter`].
+#[derive(Debug, PartialEq, Eq, Hash, Ord, PartialOrd, Clone)]
+pub struct Object {
+    /// The id of the object.
+    pub id: ObjectId,
+    /// The kind of the object.
+    pub kind: gix_object::Kind,
+    /// The decompressed and fully resolved data of the object.
+    pub data: Vec<u8>,
+    /// The offset at which the object's entry starts in the pack.
+    pub pack_offset: data::Offset,
+}
+
+/// An iterator over all objects of a [`Bundle`] in the order they are stored in the pack, created by
+/// [`Bundle::objects_in_pack_order()`].
+pub struct Iter<'a, Find, Cache> {
+    bundle: &'a Bundle,
+    entries: std::vec::IntoIter<(data::Offset, ObjectId)>,
+    odb: Find,
+    cache: Cache,
+    buf: Vec<u8>,
+}
+
+/// Iteration
+impl Bundle {
+    /// Return an iterator over all objects in this bundle in the order they appear in the pack, yielding their id, kind and
+    /// data with all deltas resolved.
+    ///
+    /// As delta bases are typically stored before the objects that refer to them, pack order allows `cache` to hold on to
+    /// recently resolved bases and avoid decoding them repeatedly, so a cache is recommended for all but the smallest packs.
+    ///
+    /// Bases that aren't contained in this pack, as is possible in thin packs, are looked up in `odb`.
+    pub fn objects_in_pack_order<Find, Cache>(&self, odb: Find, cache: Cache) -> Iter<'_, Find, Cache>
+    where
+        Find: crate::Find,
+        Cache: crate::cache::DecodeEntry,
+    {
+        let mut entries: Vec<_> = self.index.iter().map(|e| (e.pack_offset, e.oid)).collect();
+        entries.sort_by_key(|(pack_offset, _)| *pack_offset);
+        Iter {
+            bundle: self,
+            entries: entries.into_iter(),
+            odb,
+            cache,
+            buf: Vec::new(),
+        }
+    }
+}
+
+impl<Find, Cache> Iterator for Iter<'_, Find, Cache>
+where
+    Find: crate::Find,
+    Cache: crate::cache::DecodeEntry,
+{
+    type Item = Result<Object, data::decode::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (pack_offset, id) = self.entries.next()?;
+        let bundle = self.bundle;
+        let odb = &self.odb;
+        let res = bundle.pack.decode_entry(
+            bundle.pack.entry(pack_offset),
+            &mut self.buf,
+            |base_id, out| match bundle.index.lookup(base_id) {
+                Some(idx) => Some(ResolvedBase::InPack(
+                    bundle.pack.entry(bundle.index.pack_offset_at_index(idx)),
+                )),
+                None => odb
+                    .try_find(base_id, out)
+                    .ok()
+                    .flatten()
+                    .map(|(base, _location)| ResolvedBase::OutOfPack {
+                        kind: base.kind,
+                        end: base.data.len(),
+                    }),
+            },
+            &mut self.cache,
+        );
+        Some(res.map(|outcome| Object {
+            id,
+            kind: outcome.kind,
+            data: self.buf.clone(),
+            pack_offset,
+        }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl<Find, Cache> ExactSizeIterator for Iter<'_, Find, Cache>
+where
+    Find: crate::Find,
+    Cache: crate::cache::DecodeEntry,
+{
+}
@@ -75,6 +75,46 @@ mod locate {
     }
 }
 
+mod objects_in_pack_order {
+    use gix_odb::pack;
+
+    use crate::{fixture_path, pack::PACKS_AND_INDICES};
+
+    #[test]
+    fn all_objects_are_yielded_with_deltas_resolved() -> Result<(), Box<dyn std::error::Error>> {
+        let odb = gix_odb::at(fixture_path("objects"))?;
+        for (index_path, _data_path) in PACKS_AND_INDICES {
+            let bundle = pack::Bundle::at(fixture_path(index_path), gix_hash::Kind::Sha1)?;
+            let objects = bundle.objects_in_pack_order(&odb, pack::cache::Never);
+            assert_eq!(objects.len(), bundle.index.num_objects() as usize);
+
+            let mut buf = Vec::new();
+            let mut num_deltas = 0;
+            let mut previous_offset = None;
+            for object in objects {
+                let object = object?;
+                assert!(
+                    previous_offset < Some(object.pack_offset),
+                    "objects are yielded in pack order"
+                );
+                previous_offset = Some(object.pack_offset);
+                if bundle.pack.entry(object.pack_offset).header.is_delta() {
+                    num_deltas += 1;
+                }
+
+                gix_object::Data::new(object.kind, &object.data).verify_checksum(object.id)?;
+                let (expected, _location) = bundle
+                    .find(object.id, &mut buf, &mut pack::cache::Never)?
+                    .expect("id present");
+                assert_eq!(object.kind, expected.kind);
+                assert_eq!(object.data, expected.data);
+            }
+            assert_ne!(num_deltas, 0, "there are delta chains to resolve");
+        }
+        Ok(())
+    }
+}
+
 mod write_to_directory {
     use std::{fs, path::Path, sync::atomic::AtomicBool};
 
@@ -3,6 +3,10 @@ use crate::MAX_LINE_LEN;
 use crate::{PacketLineRef, StreamingPeekableIter, U16_HEX_BYTES};
 
 /// Allow the read-progress handler to determine how to continue.
+///
+/// The handler is also called with error text sent by the server, with `is_error` set. As the server terminates after
+/// sending an error, non-empty error text then fails the read with an [`Error`] containing it, even if the handler returned
+/// [`Continue`][ProgressAction::Continue].
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum ProgressAction {
     /// Continue reading the next progress if available.
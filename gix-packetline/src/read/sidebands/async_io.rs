@@ -331,6 +331,13 @@ where
                                                     )))
                                                 }
                                             };
+                                            // The server sends messages on this band right before terminating, so the stream ends here.
+                                            if !text.is_empty() {
+                                                return Poll::Ready(Err(io::Error::new(
+                                                    io::ErrorKind::Other,
+                                                    crate::read::Error { message: text.into() },
+                                                )));
+                                            }
                                         }
                                     };
                                 }
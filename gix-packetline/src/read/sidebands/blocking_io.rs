@@ -175,6 +175,13 @@ where
                                         ))
                                     }
                                 };
+                                // The server sends messages on this band right before terminating, so the stream ends here.
+                                if !text.is_empty() {
+                                    return Err(io::Error::new(
+                                        io::ErrorKind::Other,
+                                        crate::read::Error { message: text.into() },
+                                    ));
+                                }
                             }
                         };
                     }
@@ -272,3 +272,42 @@ async fn handling_of_err_lines() {
         "An error can also be the reason, which is not distinguishable from an EOF"
     );
 }
+
+#[maybe_async::test(feature = "blocking-io", async(feature = "async-io", async_std::test))]
+async fn error_band_terminates_the_stream_with_the_remote_message() -> crate::Result {
+    let input = b"0019\x02Counting objects: 1\r0009\x01PACK0020\x02Counting objects: 2, done.\n0023\x03fatal: the remote end hung up\n0009\x01more0000";
+    let mut rd = gix_packetline::StreamingPeekableIter::new(&input[..], &[PacketLineRef::Flush]);
+    let mut seen = Vec::<(bool, BString)>::new();
+    let mut reader = rd.as_read_with_sidebands(|is_err: bool, text: &[u8]| {
+        seen.push((is_err, text.as_bstr().into()));
+        ProgressAction::Continue
+    });
+
+    let mut out = Vec::new();
+    let err = reader.read_to_end(&mut out).await.unwrap_err();
+    assert_eq!(out.as_bstr(), "PACK", "data before the error is still received");
+    assert_eq!(err.to_string(), "fatal: the remote end hung up");
+    assert_eq!(
+        err.into_inner()
+            .expect("inner err")
+            .downcast::<gix_packetline::read::Error>()
+            .expect("it's this type")
+            .message,
+        "fatal: the remote end hung up",
+        "the remote message is passed on as is"
+    );
+    drop(reader);
+    assert_eq!(
+        seen,
+        [
+            (false, "Counting objects: 1\r"),
+            (false, "Counting objects: 2, done."),
+            (true, "fatal: the remote end hung up")
+        ]
+        .iter()
+        .map(|(is_err, text)| (*is_err, text.as_bytes().as_bstr().to_owned()))
+        .collect::<Vec<_>>(),
+        "progress and errors are still seen by the handler"
+    );
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use bstr::ByteSlice;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+fn match_pattern(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Pattern::matches()");
+    // The same pattern, once taking the fast path for paths without wildcards, and once forced through the glob engine
+    // by a character class that matches only a single character.
+    for (name, spec) in [
+        ("literal path", "gix-pathspec/src/pattern.rs"),
+        ("literal path with :(literal)", ":(literal)gix-pathspec/src/pattern.rs"),
+        ("equivalent glob", "gix-pathspec/src/pattern.r[s]"),
+    ] {
+        let pattern = gix_pathspec::parse(spec.as_bytes()).expect("valid pathspec");
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                PATHS
+                    .iter()
+                    .filter(|path| pattern.matches(black_box(path.as_bytes().as_bstr()), false))
+                    .count()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, match_pattern);
+criterion_main!(benches);
+
+const PATHS: &[&str] = &[
+    "Cargo.toml",
+    "README.md",
+    "gix-pathspec/Cargo.toml",
+    "gix-pathspec/src/lib.rs",
+    "gix-pathspec/src/parse.rs",
+    "gix-pathspec/src/pattern.rs",
+    "gix-pathspec/src/search.rs",
+    "gix-pathspec/src/write.rs",
+    "gix-pathspec/tests/pathspec.rs",
+    "gix-pathspec/tests/pattern/mod.rs",
+    "gix-pathspec/tests/search/mod.rs",
+    "gix-glob/src/lib.rs",
+    "gix-glob/src/pattern.rs",
+    "gix-glob/src/wildmatch.rs",
+    "gix/src/lib.rs",
+    "gix/src/repository/mod.rs",
+];
@@ -9,6 +9,13 @@ use bstr::BString;
 ///
 pub mod parse;
 
+///
+pub mod pattern;
+
+mod search;
+
+mod write;
+
 /// The output of a pathspec [parsing][parse()] operation. It can be used to match against a one or more paths.
 #[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
 pub struct Pattern {
@@ -22,10 +29,18 @@ pub struct Pattern {
     pub search_mode: MatchMode,
     /// All attributes that were included in the `ATTR` part of the pathspec, if present.
     ///
-    /// `:(attr:a=one b=):path` would yield attribute `a` and `b`.
+    /// `:(attr:a=one b=):path` would yield attribute `a` and `b`. Multiple `attr:` keywords are merged in order of
+    /// appearance, so `:(attr:a,attr:b a):path` yields `a` and `b` as well.
     pub attributes: Vec<gix_attributes::Assignment>,
 }
 
+/// A list of [patterns](Pattern) to use when deciding which paths are selected by a pathspec.
+#[derive(Default, PartialEq, Eq, Debug, Hash, Clone)]
+pub struct Search {
+    /// All patterns of the search, positive and [excluding](MagicSignature::EXCLUDE) ones alike, in order of definition.
+    pub patterns: Vec<Pattern>,
+}
+
 bitflags! {
     /// Flags to represent 'magic signatures' which are parsed behind colons, like `:top:`.
     #[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone, Copy)]
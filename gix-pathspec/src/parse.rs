@@ -12,8 +12,10 @@ pub enum Error {
     EmptyString,
     #[error("Found {keyword:?} in signature, which is not a valid keyword")]
     InvalidKeyword { keyword: BString },
-    #[error("Unimplemented short keyword: {short_keyword:?}")]
-    Unimplemented { short_keyword: char },
+    #[error("Short keyword {short_keyword:?} is reserved by git for future use")]
+    ReservedShortKeyword { short_keyword: char },
+    #[error("Keyword {keyword:?} is valid in git but not supported by gitoxide: {hint}")]
+    Unimplemented { keyword: BString, hint: &'static str },
     #[error("Missing ')' at the end of pathspec signature")]
     MissingClosingParenthesis,
     #[error("Attribute has non-ascii characters or starts with '-': {attribute:?}")]
@@ -24,15 +26,99 @@ pub enum Error {
     TrailingEscapeCharacter,
     #[error("Attribute specification cannot be empty")]
     EmptyAttribute,
-    #[error("Only one attribute specification is allowed in the same pathspec")]
-    MultipleAttributeSpecifications,
-    #[error("'literal' and 'glob' keywords cannot be used together in the same pathspec")]
-    IncompatibleSearchModes,
+    #[error("Attribute {attribute:?} is required with conflicting states in the same pathspec")]
+    ConflictingAttributeStates { attribute: BString },
+    #[error("'{left}' and '{right}' keywords cannot be used together in the same pathspec")]
+    IncompatibleMagic { left: &'static str, right: &'static str },
+    #[error("Pathspec {input:?} was serialized to {serialized:?} which doesn't parse into the same pattern")]
+    RoundTrip { input: BString, serialized: BString },
+}
+
+/// Options for use in [`Pattern::from_bytes_opts()`].
+#[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Options {
+    /// If `true`, default `false`, serialize each parsed pattern with [`Pattern::to_bstring()`] and parse it again
+    /// to fail with [`Error::RoundTrip`] if the result differs.
+    ///
+    /// This is a safety net to detect information that is silently lost during parsing, at the cost of parsing twice.
+    pub check_round_trip: bool,
 }
 
 impl Pattern {
     /// Try to parse a path-spec pattern from the given `input` bytes.
     pub fn from_bytes(input: &[u8]) -> Result<Self, Error> {
+        Self::from_bytes_opts(input, Options::default())
+    }
+
+    /// Try to parse a path-spec pattern from the given `input` bytes, with `options` to control additional checks.
+    pub fn from_bytes_opts(input: &[u8], Options { check_round_trip }: Options) -> Result<Self, Error> {
+        let pattern = Self::from_bytes_inner(input)?;
+        if check_round_trip {
+            let serialized = pattern.to_bstring();
+            if Self::from_bytes_inner(&serialized).ok().as_ref() != Some(&pattern) {
+                return Err(Error::RoundTrip {
+                    input: input.into(),
+                    serialized,
+                });
+            }
+        }
+        Ok(pattern)
+    }
+
+    /// Translate a single `line` of a `.gitignore` file into a pattern that [matches][Pattern::matches()] the same paths,
+    /// or `None` if it is blank, a comment or otherwise doesn't contain a pattern.
+    ///
+    /// A leading `!` negates the pattern, which is represented as [`MagicSignature::EXCLUDE`], and a trailing `/`
+    /// makes it match only directories. Patterns with a leading or inner `/` are anchored at the root, and all others
+    /// match at any depth. Wildcards don't match `/` unless they are `**`, just like with [`MatchMode::PathAwareGlob`].
+    ///
+    /// Note that the resulting path is relative to the directory containing the `.gitignore` file.
+    pub fn from_gitignore_line(line: &BStr) -> Option<Self> {
+        let mut line = line.as_bytes();
+        if line.first() == Some(&b'#') {
+            return None;
+        }
+        // Trailing spaces are ignored unless they are escaped with a backslash.
+        while let Some(stripped) = line.strip_suffix(b" ") {
+            if stripped.ends_with(b"\\") {
+                break;
+            }
+            line = stripped;
+        }
+
+        let mut signature = MagicSignature::empty();
+        if let Some(negated) = line.strip_prefix(b"!") {
+            signature |= MagicSignature::EXCLUDE;
+            line = negated;
+        } else if line.starts_with(b"\\!") || line.starts_with(b"\\#") {
+            line = &line[1..];
+        }
+
+        let (anchored_path, is_anchored) = match line.strip_prefix(b"/") {
+            Some(path) => (path, true),
+            None => {
+                let without_trailing_slash = line.strip_suffix(b"/").unwrap_or(line);
+                (line, without_trailing_slash.contains(&b'/'))
+            }
+        };
+        if anchored_path.is_empty() || anchored_path == b"/" {
+            return None;
+        }
+
+        let mut path = BString::default();
+        if !is_anchored {
+            path.push_str("**/");
+        }
+        path.push_str(anchored_path);
+        Some(Pattern {
+            path,
+            signature,
+            search_mode: MatchMode::PathAwareGlob,
+            attributes: Vec::new(),
+        })
+    }
+
+    fn from_bytes_inner(input: &[u8]) -> Result<Self, Error> {
         if input.is_empty() {
             return Err(Error::EmptyString);
         }
@@ -45,22 +131,59 @@ impl Pattern {
         };
 
         let mut cursor = 0;
+        let mut search_modes = Vec::new();
         if input.first() == Some(&b':') {
             cursor += 1;
             p.signature |= parse_short_keywords(input, &mut cursor)?;
             if let Some(b'(') = input.get(cursor) {
                 cursor += 1;
-                parse_long_keywords(input, &mut p, &mut cursor)?;
+                parse_long_keywords(input, &mut p, &mut search_modes, &mut cursor)?;
             }
         }
+        check_compatibility(p.signature, &search_modes)?;
 
         p.path = BString::from(&input[cursor..]);
         Ok(p)
     }
 }
 
+/// Pairs of magic keywords that git refuses to combine in a single pathspec.
+///
+/// Note that `icase` is compatible with `literal` and `glob`, and that `top` is allowed with `exclude`.
+const INCOMPATIBLE_MAGIC: &[(&str, &str)] = &[("literal", "glob")];
+
+/// Fail if the magic of a pathspec, i.e. its `signature` and all requested `search_modes`, contains a combination of keywords
+/// git doesn't permit.
+fn check_compatibility(signature: MagicSignature, search_modes: &[MatchMode]) -> Result<(), Error> {
+    let keywords: Vec<&str> = [
+        (MagicSignature::TOP, "top"),
+        (MagicSignature::ICASE, "icase"),
+        (MagicSignature::EXCLUDE, "exclude"),
+    ]
+    .iter()
+    .filter(|(flag, _)| signature.contains(*flag))
+    .map(|(_, keyword)| *keyword)
+    .chain(search_modes.iter().filter_map(|mode| match mode {
+        MatchMode::Literal => Some("literal"),
+        MatchMode::PathAwareGlob => Some("glob"),
+        MatchMode::ShellGlob => None,
+    }))
+    .collect();
+
+    for (left, right) in INCOMPATIBLE_MAGIC {
+        if keywords.contains(left) && keywords.contains(right) {
+            return Err(Error::IncompatibleMagic {
+                left: *left,
+                right: *right,
+            });
+        }
+    }
+    Ok(())
+}
+
 fn parse_short_keywords(input: &[u8], cursor: &mut usize) -> Result<MagicSignature, Error> {
-    let unimplemented_chars = b"\"#%&'-',;<=>@_`~";
+    // All characters git considers to be magic but doesn't assign any meaning to yet.
+    let reserved_chars = b"\"#%&'-,;<=>@_`~";
 
     let mut signature = MagicSignature::empty();
     while let Some(&b) = input.get(*cursor) {
@@ -69,8 +192,8 @@ fn parse_short_keywords(input: &[u8], cursor: &mut usize) -> Result<MagicSignatu
             b'/' => MagicSignature::TOP,
             b'^' | b'!' => MagicSignature::EXCLUDE,
             b':' => break,
-            _ if unimplemented_chars.contains(&b) => {
-                return Err(Error::Unimplemented {
+            _ if reserved_chars.contains(&b) => {
+                return Err(Error::ReservedShortKeyword {
                     short_keyword: b.into(),
                 });
             }
@@ -84,7 +207,12 @@ fn parse_short_keywords(input: &[u8], cursor: &mut usize) -> Result<MagicSignatu
     Ok(signature)
 }
 
-fn parse_long_keywords(input: &[u8], p: &mut Pattern, cursor: &mut usize) -> Result<(), Error> {
+fn parse_long_keywords(
+    input: &[u8],
+    p: &mut Pattern,
+    search_modes: &mut Vec<MatchMode>,
+    cursor: &mut usize,
+) -> Result<(), Error> {
     let end = input.find(")").ok_or(Error::MissingClosingParenthesis)?;
 
     let input = &input[*cursor..end];
@@ -103,20 +231,22 @@ fn parse_long_keywords(input: &[u8], p: &mut Pattern, cursor: &mut usize) -> Res
             b"top" => p.signature |= MagicSignature::TOP,
             b"icase" => p.signature |= MagicSignature::ICASE,
             b"exclude" => p.signature |= MagicSignature::EXCLUDE,
-            b"literal" => match p.search_mode {
-                MatchMode::PathAwareGlob => return Err(Error::IncompatibleSearchModes),
-                _ => p.search_mode = MatchMode::Literal,
-            },
-            b"glob" => match p.search_mode {
-                MatchMode::Literal => return Err(Error::IncompatibleSearchModes),
-                _ => p.search_mode = MatchMode::PathAwareGlob,
-            },
+            b"literal" => {
+                search_modes.push(MatchMode::Literal);
+                p.search_mode = MatchMode::Literal;
+            }
+            b"glob" => {
+                search_modes.push(MatchMode::PathAwareGlob);
+                p.search_mode = MatchMode::PathAwareGlob;
+            }
+            _ if keyword.starts_with(b"prefix:") => {
+                return Err(Error::Unimplemented {
+                    keyword: BString::from(keyword),
+                    hint: "git uses it internally to pass pathspecs relative to a subdirectory to subprocesses",
+                });
+            }
             _ if keyword.starts_with(attr_prefix) => {
-                if p.attributes.is_empty() {
-                    p.attributes = parse_attributes(&keyword[attr_prefix.len()..])?;
-                } else {
-                    return Err(Error::MultipleAttributeSpecifications);
-                }
+                merge_attributes(&mut p.attributes, parse_attributes(&keyword[attr_prefix.len()..])?)?;
             }
             _ => {
                 return Err(Error::InvalidKeyword {
@@ -160,6 +290,26 @@ fn parse_attributes(input: &[u8]) -> Result<Vec<gix_attributes::Assignment>, Err
         .map_err(|e| Error::InvalidAttribute { attribute: e.attribute })
 }
 
+/// Add `new` attributes to `attributes` in order, skipping those that are already present with the same state.
+/// It's an error if an attribute is already present with a different state.
+fn merge_attributes(
+    attributes: &mut Vec<gix_attributes::Assignment>,
+    new: Vec<gix_attributes::Assignment>,
+) -> Result<(), Error> {
+    for attr in new {
+        match attributes.iter().find(|existing| existing.name == attr.name) {
+            Some(existing) if existing.state != attr.state => {
+                return Err(Error::ConflictingAttributeStates {
+                    attribute: attr.name.as_str().into(),
+                })
+            }
+            Some(_) => {}
+            None => attributes.push(attr),
+        }
+    }
+    Ok(())
+}
+
 fn unescape_attribute_values(input: &BStr) -> Result<Cow<'_, BStr>, Error> {
     if !input.contains(&b'=') {
         return Ok(Cow::Borrowed(input));
@@ -0,0 +1,199 @@
+use std::borrow::Cow;
+
+use bstr::{BStr, ByteSlice};
+use gix_attributes::StateRef;
+
+use crate::{MagicSignature, MatchMode, Pattern};
+
+/// Access
+impl Pattern {
+    /// Return the portion of our `path` that doesn't contain any wildcard characters and that thus must match literally.
+    ///
+    /// With [`MatchMode::Literal`], this is the entire path.
+    pub fn literal_prefix(&self) -> &BStr {
+        match self.search_mode {
+            MatchMode::Literal => self.path.as_bstr(),
+            MatchMode::ShellGlob | MatchMode::PathAwareGlob => {
+                let end = self
+                    .path
+                    .find_byteset(GLOB_CHARACTERS)
+                    .unwrap_or_else(|| self.path.len());
+                self.path[..end].as_bstr()
+            }
+        }
+    }
+
+    /// Return our `path` with the path separators native to the current platform, for display purposes only.
+    ///
+    /// Matching is always performed with the unaltered `path`, which uses forward slashes.
+    pub fn display_path(&self) -> Cow<'_, BStr> {
+        gix_path::to_native_separators(self.path.as_bstr())
+    }
+
+    /// Return `true` if any path inside of the directory `dir` could possibly be matched by this pattern, or `false`
+    /// if it's certain that nothing below `dir` will match.
+    ///
+    /// `dir` is a repository-relative path to a directory, without trailing slash, which allows tree walkers to skip
+    /// entire subtrees. The answer is conservative, thus `true` doesn't mean that there will be a match.
+    ///
+    /// Note that [exclude patterns](MagicSignature::EXCLUDE) are treated like any other pattern, so the caller has to
+    /// handle their negation.
+    pub fn could_match_in(&self, dir: &BStr) -> bool {
+        let prefix = self.literal_prefix();
+        let common_len = prefix.len().min(dir.len());
+        let (prefix_start, dir_start) = (&prefix[..common_len], &dir[..common_len]);
+        let starts_equal = if self.signature.contains(MagicSignature::ICASE) {
+            prefix_start.eq_ignore_ascii_case(dir_start)
+        } else {
+            prefix_start == dir_start
+        };
+        if !starts_equal {
+            return false;
+        }
+        match prefix.get(dir.len()) {
+            // The prefix ends within or right at the end of `dir`. Without wildcards, the pattern names a path
+            // which only contains `dir` if it is a leading directory of it.
+            None => {
+                let is_literal = prefix.len() == self.path.len();
+                !is_literal
+                    || prefix.is_empty()
+                    || prefix.len() == dir.len()
+                    || prefix.ends_with(b"/")
+                    || dir[prefix.len()] == b'/'
+            }
+            // The prefix continues past `dir`, which is only possible if it continues into `dir`.
+            Some(b) => dir.is_empty() || *b == b'/',
+        }
+    }
+}
+
+/// Matching
+impl Pattern {
+    /// Return `true` if the repository-relative `path` is matched by this pattern, with `is_dir` being `true` if `path`
+    /// is a directory.
+    ///
+    /// A path matches if the pattern matches it entirely, or if the pattern matches one of its leading directories.
+    /// With [`MatchMode::ShellGlob`] a `*` also matches `/`, whereas with [`MatchMode::PathAwareGlob`] only `**` does,
+    /// and with [`MatchMode::Literal`] there are no wildcards at all. A trailing slash in the pattern only matches
+    /// directories and their contents. As there is no notion of a current working directory, patterns are always
+    /// anchored at the root of the repository just like with [`MagicSignature::TOP`].
+    ///
+    /// Note that [exclude patterns](MagicSignature::EXCLUDE) return whether they match `path`, without negating the
+    /// result. It's up to the caller to apply the exclusion, typically when searching with multiple patterns.
+    pub fn matches(&self, path: &BStr, is_dir: bool) -> bool {
+        let ignore_case = self.signature.contains(MagicSignature::ICASE);
+        let (pattern, needs_dir) = match self.path.strip_suffix(b"/") {
+            Some(pattern) => (pattern.as_bstr(), true),
+            None => (self.path.as_bstr(), false),
+        };
+        if pattern.is_empty() {
+            return true;
+        }
+
+        let is_literal = self.search_mode == MatchMode::Literal || pattern.find_byteset(GLOB_CHARACTERS).is_none();
+        if is_literal {
+            let path_start = match path.get(..pattern.len()) {
+                Some(start) => start,
+                None => return false,
+            };
+            let starts_equal = if ignore_case {
+                path_start.eq_ignore_ascii_case(pattern)
+            } else {
+                path_start == pattern.as_bytes()
+            };
+            return starts_equal
+                && match path.get(pattern.len()) {
+                    None => is_dir || !needs_dir,
+                    Some(b) => *b == b'/',
+                };
+        }
+
+        let mut mode = match self.search_mode {
+            MatchMode::ShellGlob => gix_glob::wildmatch::Mode::empty(),
+            MatchMode::PathAwareGlob | MatchMode::Literal => gix_glob::wildmatch::Mode::NO_MATCH_SLASH_LITERAL,
+        };
+        if ignore_case {
+            mode |= gix_glob::wildmatch::Mode::IGNORE_CASE;
+        }
+        let mut candidate = path;
+        loop {
+            let is_leading_dir = candidate.len() != path.len();
+            if (is_leading_dir || is_dir || !needs_dir) && gix_glob::wildmatch(pattern, candidate, mode) {
+                return true;
+            }
+            match candidate.rfind_byte(b'/') {
+                Some(pos) => candidate = candidate[..pos].as_bstr(),
+                None => return false,
+            }
+        }
+    }
+}
+
+/// The reason for a [`Pattern`] not to match a path, as returned by [`Pattern::matches_with_attributes()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mismatch {
+    /// The path itself isn't matched by the pattern.
+    Path,
+    /// The path is matched, but an attribute required by the pattern has a different state for the path.
+    Attribute {
+        /// The attribute and its state as required by the pattern.
+        expected: gix_attributes::Assignment,
+        /// The actual state of the attribute for the path.
+        actual: gix_attributes::State,
+    },
+}
+
+impl std::fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Mismatch::Path => f.write_str("the path doesn't match"),
+            Mismatch::Attribute { expected, actual } => {
+                let name = expected.name.as_str();
+                write!(f, "expected {} but file has ", expected.as_ref())?;
+                match actual.as_ref() {
+                    StateRef::Set => write!(f, "{name} set"),
+                    StateRef::Unset => write!(f, "{name} unset"),
+                    StateRef::Value(value) => write!(f, "{name}={}", value.as_bstr()),
+                    StateRef::Unspecified => write!(f, "{name} unspecified"),
+                }
+            }
+        }
+    }
+}
+
+impl std::error::Error for Mismatch {}
+
+/// Matching with attributes
+impl Pattern {
+    /// Like [`matches()`](Self::matches()), but also check that the `attributes` of `path` satisfy all attribute
+    /// requirements of this pattern, returning the reason for the first mismatch.
+    ///
+    /// `attributes` are all attributes that apply to `path`, with those that aren't listed being unspecified.
+    /// This is more expensive than `matches()` and meant to help understanding why a path wasn't matched.
+    pub fn matches_with_attributes(
+        &self,
+        path: &BStr,
+        is_dir: bool,
+        attributes: &[gix_attributes::Assignment],
+    ) -> Result<(), Mismatch> {
+        if !self.matches(path, is_dir) {
+            return Err(Mismatch::Path);
+        }
+        for expected in &self.attributes {
+            let actual = attributes
+                .iter()
+                .rev()
+                .find(|attr| attr.name == expected.name)
+                .map_or(gix_attributes::State::Unspecified, |attr| attr.state.clone());
+            if actual != expected.state {
+                return Err(Mismatch::Attribute {
+                    expected: expected.clone(),
+                    actual,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+const GLOB_CHARACTERS: &[u8] = br"*?[\";
@@ -0,0 +1,90 @@
+use bstr::{BStr, BString, ByteSlice};
+
+use crate::{MagicSignature, Pattern, Search};
+
+/// Instantiation
+impl Search {
+    /// Create a search from the given `patterns`.
+    pub fn from_specs(patterns: impl IntoIterator<Item = Pattern>) -> Self {
+        Search {
+            patterns: patterns.into_iter().collect(),
+        }
+    }
+}
+
+/// Access
+impl Search {
+    /// Return the directories which contain all paths that could possibly be matched by this search, without trailing slash,
+    /// so that tree walks can start there instead of at the root.
+    ///
+    /// Directories don't overlap, i.e. no directory is contained in any other one, and they are sorted. If no directory could
+    /// be determined, e.g. because a pattern starts with a wildcard or because there are no positive patterns, the result is
+    /// a single empty path denoting the root of the repository.
+    pub fn common_prefixes(&self) -> Vec<BString> {
+        let mut prefixes = Vec::new();
+        for pattern in self
+            .patterns
+            .iter()
+            .filter(|p| !p.signature.contains(MagicSignature::EXCLUDE))
+        {
+            let dir = directory_prefix(pattern);
+            if dir.is_empty() {
+                return vec![BString::default()];
+            }
+            prefixes.push(dir);
+        }
+        if prefixes.is_empty() {
+            return vec![BString::default()];
+        }
+
+        prefixes.sort();
+        prefixes.dedup();
+        let mut out: Vec<BString> = Vec::with_capacity(prefixes.len());
+        for prefix in prefixes {
+            let is_contained_in_previous = out.last().map_or(false, |previous| {
+                prefix.starts_with(previous) && prefix.get(previous.len()) == Some(&b'/')
+            });
+            if !is_contained_in_previous {
+                out.push(prefix);
+            }
+        }
+        out
+    }
+}
+
+/// Matching
+impl Search {
+    /// Return `true` if the repository-relative `path` is selected by this search, with `is_dir` being `true` if `path`
+    /// is a directory.
+    ///
+    /// Like in git, a path is selected if any of the positive patterns [matches](Pattern::matches()) it and none of the
+    /// [exclude patterns](MagicSignature::EXCLUDE) does, so an exclusion always wins regardless of the order of patterns.
+    /// Without positive patterns, all paths that aren't excluded are selected, which includes the case of an empty search.
+    pub fn matches(&self, path: &BStr, is_dir: bool) -> bool {
+        let mut has_positive_pattern = false;
+        let mut is_included = false;
+        for pattern in &self.patterns {
+            if pattern.signature.contains(MagicSignature::EXCLUDE) {
+                if pattern.matches(path, is_dir) {
+                    return false;
+                }
+            } else {
+                has_positive_pattern = true;
+                is_included = is_included || pattern.matches(path, is_dir);
+            }
+        }
+        is_included || !has_positive_pattern
+    }
+}
+
+/// Return the leading directories of the literal prefix of `pattern`, or an empty path if there is none.
+fn directory_prefix(pattern: &Pattern) -> BString {
+    if pattern.signature.contains(MagicSignature::ICASE) {
+        return BString::default();
+    }
+    let prefix = pattern.literal_prefix();
+    prefix
+        .rfind_byte(b'/')
+        .map(|pos| prefix[..pos].to_owned())
+        .unwrap_or_default()
+}
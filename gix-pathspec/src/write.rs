@@ -0,0 +1,85 @@
+use bstr::BString;
+
+use crate::{MagicSignature, MatchMode, Pattern};
+
+impl Pattern {
+    /// Reproduce ourselves in parseable form.
+    ///
+    /// Magic signatures are always written in their long form, like `:(top,icase)path`, and omitted entirely if there
+    /// is none.
+    pub fn to_bstring(&self) -> BString {
+        let mut buf = Vec::with_capacity(self.path.len() + 16);
+        self.write_to(&mut buf).expect("no io error");
+        buf.into()
+    }
+
+    /// Serialize ourselves in a parseable format to `out`.
+    pub fn write_to(&self, mut out: impl std::io::Write) -> std::io::Result<()> {
+        let mut keywords = Vec::new();
+        for (flag, keyword) in [
+            (MagicSignature::TOP, "top"),
+            (MagicSignature::ICASE, "icase"),
+            (MagicSignature::EXCLUDE, "exclude"),
+        ] {
+            if self.signature.contains(flag) {
+                keywords.push(keyword);
+            }
+        }
+        match self.search_mode {
+            MatchMode::ShellGlob => {}
+            MatchMode::Literal => keywords.push("literal"),
+            MatchMode::PathAwareGlob => keywords.push("glob"),
+        }
+
+        let needs_signature = !keywords.is_empty()
+            || !self.attributes.is_empty()
+            || self.path.is_empty()
+            || self.path.first() == Some(&b':');
+        if needs_signature {
+            out.write_all(b":(")?;
+            out.write_all(keywords.join(",").as_bytes())?;
+            if !self.attributes.is_empty() {
+                if !keywords.is_empty() {
+                    out.write_all(b",")?;
+                }
+                out.write_all(b"attr:")?;
+                for (idx, attribute) in self.attributes.iter().enumerate() {
+                    if idx != 0 {
+                        out.write_all(b" ")?;
+                    }
+                    write_attribute(attribute, &mut out)?;
+                }
+            }
+            out.write_all(b")")?;
+        }
+        out.write_all(&self.path)
+    }
+}
+
+fn write_attribute(attribute: &gix_attributes::Assignment, mut out: impl std::io::Write) -> std::io::Result<()> {
+    use gix_attributes::State;
+    let name = attribute.name.as_str().as_bytes();
+    match &attribute.state {
+        State::Set => out.write_all(name),
+        State::Unset => {
+            out.write_all(b"-")?;
+            out.write_all(name)
+        }
+        State::Unspecified => {
+            out.write_all(b"!")?;
+            out.write_all(name)
+        }
+        State::Value(value) => {
+            out.write_all(name)?;
+            out.write_all(b"=")?;
+            for b in value.as_ref().as_bstr().iter() {
+                // Commas separate keywords, so they must be escaped.
+                if *b == b',' {
+                    out.write_all(b"\\")?;
+                }
+                out.write_all(&[*b])?;
+            }
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,303 @@
+mod could_match_in {
+    use bstr::ByteSlice;
+
+    fn could_match(spec: &str, dir: &str) -> bool {
+        gix_pathspec::parse(spec.as_bytes())
+            .expect("valid pathspec")
+            .could_match_in(dir.as_bytes().as_bstr())
+    }
+
+    #[test]
+    fn literal_patterns_match_their_leading_directories_only() {
+        for dir in ["", "src", "src/lib"] {
+            assert!(could_match("src/lib/foo.rs", dir), "{dir:?}");
+            assert!(could_match(":(literal)src/lib/foo.rs", dir), "{dir:?}");
+        }
+        for dir in ["docs", "sr", "src/li", "src/lib/foo", "src/libs", "srcs/lib"] {
+            assert!(!could_match("src/lib/foo.rs", dir), "{dir:?}");
+        }
+    }
+
+    #[test]
+    fn literal_directory_patterns_match_everything_below_them() {
+        for dir in ["src", "src/lib", "src/lib/deeply/nested"] {
+            assert!(could_match("src", dir), "{dir:?}");
+            assert!(could_match("src/", dir), "{dir:?}");
+        }
+        assert!(!could_match("src", "srcs"));
+        assert!(!could_match("src", "sr"));
+    }
+
+    #[test]
+    fn wildcards_are_only_matched_up_to_their_literal_prefix() {
+        for dir in ["", "src", "src/lib", "src/lib/nested", "src/library"] {
+            assert!(could_match("src/lib*", dir), "{dir:?}");
+        }
+        for dir in ["", "docs", "src/lib"] {
+            assert!(could_match("*.rs", dir), "{dir:?}");
+        }
+        for dir in ["docs", "src/bin", "tests/lib"] {
+            assert!(!could_match("src/lib*", dir), "{dir:?}");
+        }
+        assert!(could_match("src/*/foo.rs", "src/anything"));
+        assert!(!could_match("src/*/foo.rs", "docs/anything"));
+    }
+
+    #[test]
+    fn literal_mode_treats_wildcards_as_ordinary_characters() {
+        assert!(!could_match(":(literal)src/lib*", "src/library"));
+        assert!(could_match(":(literal)src/lib*", "src"));
+    }
+
+    #[test]
+    fn icase_ignores_the_case_of_the_literal_prefix() {
+        assert!(!could_match("src/lib/foo.rs", "SRC/Lib"));
+        assert!(could_match(":(icase)src/lib/foo.rs", "SRC/Lib"));
+        assert!(!could_match(":(icase)src/lib/foo.rs", "DOCS"));
+    }
+}
+
+mod display_path {
+    use bstr::ByteSlice;
+
+    #[test]
+    fn uses_native_separators_without_affecting_matching() {
+        let pattern = gix_pathspec::parse(b":(top,icase)src/lib/*.rs").expect("valid pathspec");
+        let expected = if cfg!(windows) { r"src\lib\*.rs" } else { "src/lib/*.rs" };
+        assert_eq!(pattern.display_path().as_ref(), expected, "magic is never displayed");
+        assert_eq!(pattern.path, "src/lib/*.rs", "the stored path is unaltered");
+        assert!(pattern.could_match_in(b"src/lib".as_bstr()));
+    }
+}
+
+mod matches {
+    use bstr::ByteSlice;
+
+    fn matches(spec: &str, path: &str, is_dir: bool) -> bool {
+        gix_pathspec::parse(spec.as_bytes())
+            .expect("valid pathspec")
+            .matches(path.as_bytes().as_bstr(), is_dir)
+    }
+
+    #[test]
+    fn shell_globs_match_across_directories() {
+        assert!(matches("foo/*.rs", "foo/bar.rs", false));
+        assert!(matches("foo/*.rs", "foo/bar/baz.rs", false), "* matches / as well");
+        assert!(!matches("foo/*.rs", "foo/bar.txt", false));
+        assert!(!matches("foo/*.rs", "other/foo/bar.rs", false));
+    }
+
+    #[test]
+    fn path_aware_globs_only_match_slashes_with_double_stars() {
+        assert!(matches(":(glob)foo/*.rs", "foo/bar.rs", false));
+        assert!(!matches(":(glob)foo/*.rs", "foo/bar/baz.rs", false));
+        assert!(matches(":(glob)foo/**/*.rs", "foo/bar/baz.rs", false));
+    }
+
+    #[test]
+    fn literal_patterns_match_the_path_or_its_leading_directories() {
+        assert!(matches("foo/bar.rs", "foo/bar.rs", false));
+        assert!(matches("foo", "foo/bar.rs", false));
+        assert!(!matches("foo", "foobar.rs", false));
+        assert!(matches(":(literal)foo/*.rs", "foo/*.rs", false));
+        assert!(!matches(":(literal)foo/*.rs", "foo/bar.rs", false));
+    }
+
+    #[test]
+    fn trailing_slashes_only_match_directories_and_their_content() {
+        assert!(matches("foo/", "foo", true));
+        assert!(!matches("foo/", "foo", false));
+        assert!(matches("foo/", "foo/bar.rs", false));
+    }
+
+    #[test]
+    fn icase_ignores_the_case() {
+        assert!(matches(":(icase)README", "readme", false));
+        assert!(!matches("README", "readme", false));
+        assert!(matches(":(icase)*.MD", "docs/readme.md", false));
+    }
+
+    #[test]
+    fn top_anchored_specs_do_not_match_nested_paths() {
+        assert!(matches(":(top)foo.rs", "foo.rs", false));
+        assert!(!matches(":(top)foo.rs", "nested/foo.rs", false));
+    }
+
+    #[test]
+    fn exclude_patterns_report_the_positive_match() {
+        assert!(matches(":(exclude)foo/*.rs", "foo/bar.rs", false));
+        assert!(!matches(":!foo/*.rs", "foo/bar.txt", false));
+    }
+
+    #[test]
+    fn literal_fast_path_agrees_with_the_glob_engine() {
+        let paths = [
+            ("foo", false),
+            ("foo", true),
+            ("FOO", false),
+            ("foobar", false),
+            ("foo/bar", false),
+            ("foo/bar", true),
+            ("foo/bar/baz.rs", false),
+            ("Foo/Bar/baz.rs", false),
+            ("foo/barbaz", false),
+            ("fo", false),
+            ("other/foo/bar", false),
+        ];
+        for (literal, as_glob) in [
+            ("foo", "fo[o]"),
+            ("foo/bar", "foo/ba[r]"),
+            ("foo/bar/", "foo/ba[r]/"),
+            (":(literal)foo/bar", ":(glob)foo/ba[r]"),
+            (":(icase)foo/bar", ":(icase)foo/ba[r]"),
+            (":(top)foo/bar/baz.rs", ":(top)foo/bar/baz.r[s]"),
+        ] {
+            for (path, is_dir) in paths {
+                assert_eq!(
+                    matches(literal, path, is_dir),
+                    matches(as_glob, path, is_dir),
+                    "{literal:?} and {as_glob:?} disagree on {path:?} (dir: {is_dir})"
+                );
+            }
+        }
+    }
+}
+
+mod matches_with_attributes {
+    use bstr::ByteSlice;
+    use gix_pathspec::pattern::Mismatch;
+
+    fn matches(spec: &str, path: &str, attributes: &str) -> Result<(), Mismatch> {
+        let attributes: Vec<gix_attributes::Assignment> = gix_attributes::parse::Iter::new(attributes.into())
+            .map(|attr| attr.expect("valid attribute").into())
+            .collect();
+        gix_pathspec::parse(spec.as_bytes())
+            .expect("valid pathspec")
+            .matches_with_attributes(path.as_bytes().as_bstr(), false, &attributes)
+    }
+
+    #[test]
+    fn missing_attributes_are_reported_as_unspecified() {
+        let err = matches(":(attr:text diff=rust)src", "src/lib.rs", "text").unwrap_err();
+        assert_eq!(err.to_string(), "expected diff=rust but file has diff unspecified");
+        assert!(matches!(err, Mismatch::Attribute { actual, .. } if actual.is_unspecified()));
+    }
+
+    #[test]
+    fn the_first_failing_requirement_is_reported() {
+        assert_eq!(
+            matches(":(attr:text diff=rust)src", "src/lib.rs", "-text diff=c")
+                .unwrap_err()
+                .to_string(),
+            "expected text but file has text unset"
+        );
+        assert_eq!(
+            matches(":(attr:text diff=rust)src", "src/lib.rs", "text diff=c")
+                .unwrap_err()
+                .to_string(),
+            "expected diff=rust but file has diff=c"
+        );
+        assert_eq!(
+            matches(":(attr:!diff)src", "src/lib.rs", "diff")
+                .unwrap_err()
+                .to_string(),
+            "expected !diff but file has diff set"
+        );
+    }
+
+    #[test]
+    fn paths_are_checked_before_attributes() {
+        assert_eq!(matches(":(attr:diff)src", "other/lib.rs", ""), Err(Mismatch::Path));
+    }
+
+    #[test]
+    fn satisfied_requirements_match() {
+        assert_eq!(
+            matches(":(attr:text diff=rust)src", "src/lib.rs", "diff=c text diff=rust"),
+            Ok(())
+        );
+        assert_eq!(matches(":(attr:!diff)src", "src/lib.rs", "text"), Ok(()));
+        assert_eq!(matches("src", "src/lib.rs", ""), Ok(()), "no requirements, no mismatch");
+    }
+}
+
+mod from_gitignore_line {
+    use bstr::ByteSlice;
+    use gix_pathspec::{MagicSignature, MatchMode, Pattern};
+
+    fn pattern(line: &str) -> Option<Pattern> {
+        Pattern::from_gitignore_line(line.as_bytes().as_bstr())
+    }
+
+    fn matches(line: &str, path: &str, is_dir: bool) -> bool {
+        pattern(line)
+            .expect("valid gitignore pattern")
+            .matches(path.as_bytes().as_bstr(), is_dir)
+    }
+
+    #[test]
+    fn blank_lines_and_comments_yield_nothing() {
+        for line in ["", "   ", "# comment", "#", "!", "/"] {
+            assert_eq!(pattern(line), None, "{line:?}");
+        }
+    }
+
+    #[test]
+    fn unanchored_patterns_match_at_any_depth() {
+        let p = pattern("*.o").expect("valid");
+        assert_eq!(p.path, "**/*.o");
+        assert_eq!(p.signature, MagicSignature::empty());
+        assert_eq!(p.search_mode, MatchMode::PathAwareGlob);
+        assert!(matches("*.o", "main.o", false));
+        assert!(matches("*.o", "src/deeply/nested/main.o", false));
+        assert!(!matches("*.o", "main.c", false));
+    }
+
+    #[test]
+    fn anchored_patterns_match_relative_to_the_root_only() {
+        let p = pattern("/target").expect("valid");
+        assert_eq!(p.path, "target");
+        assert!(matches("/target", "target", true));
+        assert!(matches("/target", "target/debug/main", false));
+        assert!(!matches("/target", "crate/target", true));
+
+        assert_eq!(
+            pattern("doc/*.html").expect("valid").path,
+            "doc/*.html",
+            "inner slashes anchor as well"
+        );
+        assert!(matches("doc/*.html", "doc/index.html", false));
+        assert!(
+            !matches("doc/*.html", "doc/api/index.html", false),
+            "single stars don't match slashes"
+        );
+        assert!(!matches("doc/*.html", "src/doc/index.html", false));
+    }
+
+    #[test]
+    fn trailing_slashes_only_match_directories() {
+        let p = pattern("build/").expect("valid");
+        assert_eq!(p.path, "**/build/");
+        assert!(matches("build/", "build", true));
+        assert!(matches("build/", "src/build", true));
+        assert!(matches("build/", "src/build/out.o", false));
+        assert!(!matches("build/", "src/build", false));
+    }
+
+    #[test]
+    fn negation_is_represented_as_exclude() {
+        let p = pattern("!/keep.o").expect("valid");
+        assert_eq!(p.path, "keep.o");
+        assert_eq!(p.signature, MagicSignature::EXCLUDE);
+        assert!(matches("!/keep.o", "keep.o", false), "the positive match is reported");
+    }
+
+    #[test]
+    fn escapes_and_trailing_spaces() {
+        assert_eq!(pattern(r"\!important").expect("valid").path, "**/!important");
+        assert_eq!(pattern(r"\#hash").expect("valid").path, "**/#hash");
+        assert_eq!(pattern("trailing  ").expect("valid").path, "**/trailing");
+        assert_eq!(pattern(r"escaped\ ").expect("valid").path, r"**/escaped\ ");
+        assert!(matches(r"escaped\ ", "escaped ", false));
+    }
+}
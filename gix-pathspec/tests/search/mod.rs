@@ -0,0 +1,135 @@
+mod common_prefixes {
+    use bstr::BString;
+    use gix_pathspec::Search;
+
+    fn common_prefixes(specs: &[&str]) -> Vec<BString> {
+        Search::from_specs(
+            specs
+                .iter()
+                .map(|spec| gix_pathspec::parse(spec.as_bytes()).expect("valid pathspec")),
+        )
+        .common_prefixes()
+    }
+
+    fn bstrings(items: &[&str]) -> Vec<BString> {
+        items.iter().map(|item| (*item).into()).collect()
+    }
+
+    #[test]
+    fn shared_prefixes_are_merged_into_the_outermost_directory() {
+        assert_eq!(
+            common_prefixes(&["src/lib/a.rs", "src/lib/b/*.rs", "src/bin/main.rs"]),
+            bstrings(&["src/bin", "src/lib"])
+        );
+        assert_eq!(
+            common_prefixes(&["src/lib/a.rs", "src/b.rs", "src/lib/nested/c.rs"]),
+            bstrings(&["src"]),
+            "nested directories are contained in their parent"
+        );
+        assert_eq!(common_prefixes(&["src/a.rs", "src/b.rs"]), bstrings(&["src"]));
+        assert_eq!(
+            common_prefixes(&["src/lib/", "src/lib*"]),
+            bstrings(&["src"]),
+            "wildcards and trailing slashes are accounted for"
+        );
+    }
+
+    #[test]
+    fn disjoint_prefixes_are_all_returned() {
+        assert_eq!(
+            common_prefixes(&["src/lib.rs", "docs/*.md", "tests/fixtures/a/b"]),
+            bstrings(&["docs", "src", "tests/fixtures/a"])
+        );
+    }
+
+    #[test]
+    fn patterns_without_leading_directory_require_the_root() {
+        for specs in [
+            &["src/lib.rs", "*.md"][..],
+            &["src/lib.rs", "README.md"],
+            &["src/lib.rs", ":(icase)docs/a.md"],
+            &[],
+            &[":(exclude)src/lib.rs"],
+        ] {
+            assert_eq!(common_prefixes(specs), bstrings(&[""]), "{specs:?}");
+        }
+    }
+
+    #[test]
+    fn excluded_patterns_are_ignored() {
+        assert_eq!(
+            common_prefixes(&["src/lib.rs", ":(exclude)docs/a.md", ":!*.md"]),
+            bstrings(&["src"])
+        );
+    }
+}
+
+mod matches {
+    use bstr::ByteSlice;
+    use gix_pathspec::Search;
+
+    fn search(specs: &[&str]) -> Search {
+        Search::from_specs(
+            specs
+                .iter()
+                .map(|spec| gix_pathspec::parse(spec.as_bytes()).expect("valid pathspec")),
+        )
+    }
+
+    fn selected<'a>(search: &Search, paths: &[&'a str]) -> Vec<&'a str> {
+        paths
+            .iter()
+            .copied()
+            .filter(|path| search.matches(path.as_bytes().as_bstr(), false))
+            .collect()
+    }
+
+    const PATHS: &[&str] = &[
+        "README.md",
+        "src/lib.rs",
+        "src/generated.rs",
+        "src/bin/main.rs",
+        "docs/guide.md",
+        "tests/it.rs",
+    ];
+
+    #[test]
+    fn excludes_veto_matches_of_positive_patterns() {
+        let search = search(&["src/*.rs", "*.md", ":(exclude)src/generated.rs"]);
+        assert_eq!(
+            selected(&search, PATHS),
+            ["README.md", "src/lib.rs", "src/bin/main.rs", "docs/guide.md"]
+        );
+    }
+
+    #[test]
+    fn excludes_win_regardless_of_their_position() {
+        let search = search(&[":!*.md", "src/", "docs/"]);
+        assert_eq!(
+            selected(&search, PATHS),
+            ["src/lib.rs", "src/generated.rs", "src/bin/main.rs"]
+        );
+    }
+
+    #[test]
+    fn only_excludes_select_everything_else() {
+        let search = search(&[":(exclude)src/", ":!tests/it.rs"]);
+        assert_eq!(selected(&search, PATHS), ["README.md", "docs/guide.md"]);
+    }
+
+    #[test]
+    fn empty_search_selects_everything() {
+        assert_eq!(selected(&Search::default(), PATHS), PATHS);
+    }
+
+    #[test]
+    fn paths_not_matched_by_any_positive_pattern_are_not_selected() {
+        let search = search(&["tests/", ":(exclude)src/"]);
+        assert_eq!(selected(&search, PATHS), ["tests/it.rs"]);
+        assert!(
+            search.matches(b"tests".as_bstr(), true),
+            "directories are matched as well"
+        );
+        assert!(!search.matches(b"tests".as_bstr(), false));
+    }
+}
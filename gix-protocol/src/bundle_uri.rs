@@ -0,0 +1,179 @@
+use bstr::{BStr, BString, ByteSlice};
+
+#[cfg(any(feature = "blocking-client", feature = "async-client"))]
+mod error {
+    use bstr::BString;
+
+    /// The error returned by [`bundle_uri()`][crate::bundle_uri()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Io(#[from] std::io::Error),
+        #[error(transparent)]
+        Transport(#[from] gix_transport::client::Error),
+        #[error(transparent)]
+        DecodePacketline(#[from] gix_transport::packetline::decode::Error),
+        #[error("The server did not advertise the 'bundle-uri' capability")]
+        Unsupported,
+        #[error("{line:?} could not be parsed. A bundle-uri line should be '<key>=<value>'.")]
+        MalformedLine { line: BString },
+    }
+
+    impl gix_transport::IsSpuriousError for Error {
+        fn is_spurious(&self) -> bool {
+            match self {
+                Error::Io(err) => err.is_spurious(),
+                Error::Transport(err) => err.is_spurious(),
+                _ => false,
+            }
+        }
+    }
+}
+#[cfg(any(feature = "blocking-client", feature = "async-client"))]
+pub use error::Error;
+
+/// Describes how the bundles of a [`List`] relate to each other.
+#[derive(Default, PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Mode {
+    /// All bundles are needed to obtain the complete data, which is also the mode to use if none was specified.
+    #[default]
+    All,
+    /// Any of the bundles contains the complete data, so it's sufficient to obtain one of them.
+    Any,
+}
+
+/// A single bundle of a [`List`] as identified by its `id`.
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Bundle {
+    /// The identifier of the bundle within its list, i.e. `<id>` in `bundle.<id>.uri`.
+    pub id: BString,
+    /// The location to download the bundle from, which is either absolute or relative to the location of the list.
+    pub uri: BString,
+    /// A token to order bundles by, with bundles containing newer data having a higher token, if the list has one.
+    pub creation_token: Option<u64>,
+}
+
+/// A list of bundles as advertised by the server in response to the `bundle-uri` command, or as read from a bundle list.
+#[derive(Default, PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct List {
+    /// The version of the list format, which is `1` for all lists that can currently be produced.
+    pub version: Option<u32>,
+    /// How the bundles in this list relate to each other.
+    pub mode: Mode,
+    /// All bundles with an URI, in the order in which they were first mentioned.
+    pub bundles: Vec<Bundle>,
+}
+
+impl List {
+    /// Create a new list from `<key>=<value>` `pairs` like `bundle.version=1` or `bundle.<id>.uri=<uri>`, as sent by
+    /// the server or as found in the `bundle` section of a bundle list file.
+    ///
+    /// Keys are case-insensitive and unknown keys or values are ignored to remain compatible with future extensions of
+    /// the format, as are bundles without URI.
+    pub fn from_key_value_pairs<'a>(pairs: impl IntoIterator<Item = (&'a BStr, &'a BStr)>) -> Self {
+        const PREFIX: &[u8] = b"bundle.";
+        let mut list = List::default();
+        let mut bundles = Vec::<Bundle>::new();
+        for (key, value) in pairs {
+            let key = match key.get(..PREFIX.len()) {
+                Some(prefix) if prefix.eq_ignore_ascii_case(PREFIX) => key[PREFIX.len()..].as_bstr(),
+                _ => continue,
+            };
+            match key.rfind_byte(b'.') {
+                None => {
+                    if key.eq_ignore_ascii_case(b"version") {
+                        list.version = value.to_str().ok().and_then(|v| v.parse().ok());
+                    } else if key.eq_ignore_ascii_case(b"mode") {
+                        match value.as_bytes() {
+                            b"all" => list.mode = Mode::All,
+                            b"any" => list.mode = Mode::Any,
+                            _ => {}
+                        }
+                    }
+                }
+                Some(pos) => {
+                    let (id, bundle_key) = (key[..pos].as_bstr(), key[pos + 1..].as_bstr());
+                    let bundle = match bundles.iter_mut().position(|b| b.id == id) {
+                        Some(idx) => &mut bundles[idx],
+                        None => {
+                            bundles.push(Bundle {
+                                id: id.to_owned(),
+                                uri: BString::default(),
+                                creation_token: None,
+                            });
+                            bundles.last_mut().expect("just pushed")
+                        }
+                    };
+                    if bundle_key.eq_ignore_ascii_case(b"uri") {
+                        bundle.uri = value.to_owned();
+                    } else if bundle_key.eq_ignore_ascii_case(b"creationToken") {
+                        bundle.creation_token = value.to_str().ok().and_then(|v| v.parse().ok());
+                    }
+                }
+            }
+        }
+        list.bundles = bundles.into_iter().filter(|b| !b.uri.is_empty()).collect();
+        list
+    }
+}
+
+#[cfg(any(feature = "blocking-client", feature = "async-client"))]
+pub(crate) mod function {
+    use bstr::ByteSlice;
+    use gix_features::progress::Progress;
+    use gix_transport::client::{Capabilities, Transport, TransportV2Ext};
+    use maybe_async::maybe_async;
+
+    use super::{Error, List};
+    use crate::{command::Feature, Command};
+
+    /// Invoke the `bundle-uri` V2 command on `transport` to obtain the list of bundles the server suggests to download
+    /// before fetching the remaining objects. It requires a prior handshake which yielded the server `capabilities`,
+    /// which must include `bundle-uri`.
+    ///
+    /// `features` are sent along with the command and should contain the `(agent, Some(name))` tuple. `progress` is used
+    /// to provide feedback.
+    #[maybe_async]
+    pub async fn bundle_uri(
+        mut transport: impl Transport,
+        capabilities: &Capabilities,
+        features: Vec<Feature>,
+        progress: &mut impl Progress,
+    ) -> Result<List, Error> {
+        if !capabilities.contains(Command::BundleUri.as_str()) {
+            return Err(Error::Unsupported);
+        }
+        progress.step();
+        progress.set_name("list bundles");
+        let mut lines = transport
+            .invoke(
+                Command::BundleUri.as_str(),
+                features.into_iter(),
+                None::<std::iter::Empty<bstr::BString>>,
+            )
+            .await?;
+        let mut pairs = Vec::new();
+        while let Some(line) = lines
+            .readline()
+            .await
+            .transpose()?
+            .transpose()?
+            .and_then(|l| l.as_bstr())
+        {
+            let line = line.trim_end();
+            let (key, value) = line
+                .find_byte(b'=')
+                .map(|pos| (line[..pos].to_owned(), line[pos + 1..].to_owned()))
+                .filter(|(key, value)| !key.is_empty() && !value.is_empty())
+                .ok_or_else(|| Error::MalformedLine { line: line.into() })?;
+            pairs.push((key, value));
+        }
+        Ok(List::from_key_value_pairs(
+            pairs.iter().map(|(key, value)| (key.as_bstr(), value.as_bstr())),
+        ))
+    }
+}
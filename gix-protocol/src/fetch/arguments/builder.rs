@@ -0,0 +1,140 @@
+use bstr::{BStr, BString};
+
+use crate::fetch::Arguments;
+
+/// The error returned by the methods of [`Builder`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Cannot use '{argument}' as the server doesn't advertise the '{capability}' capability")]
+    MissingCapability {
+        argument: BString,
+        capability: &'static str,
+    },
+}
+
+/// A builder for [`Arguments`] which fails as soon as an argument is added that needs a capability the server didn't advertise.
+///
+/// This is unlike the mutators of [`Arguments`] which silently ignore such arguments.
+#[derive(Debug)]
+pub struct Builder {
+    arguments: Arguments,
+    wants: Vec<gix_hash::ObjectId>,
+}
+
+/// Lifecycle
+impl Builder {
+    /// Create a new instance for the given protocol `version` and `features`, which are the available and configured
+    /// features to use as negotiated with the server.
+    pub fn new(version: gix_transport::Protocol, features: Vec<crate::command::Feature>) -> Self {
+        Builder {
+            arguments: Arguments::new(version, features),
+            wants: Vec::new(),
+        }
+    }
+
+    /// Return the arguments, ready to be sent to the server.
+    pub fn build(mut self) -> Arguments {
+        for id in self.wants {
+            self.arguments.want(id);
+        }
+        self.arguments
+    }
+}
+
+/// Arguments
+impl Builder {
+    /// Add the given `id` pointing to a commit to the 'want' list, which is always supported.
+    pub fn want(mut self, id: impl AsRef<gix_hash::oid>) -> Self {
+        self.wants.push(id.as_ref().to_owned());
+        self
+    }
+
+    /// Add the given `id` pointing to a commit to the 'have' list, which is always supported.
+    pub fn have(mut self, id: impl AsRef<gix_hash::oid>) -> Self {
+        self.arguments.have(id);
+        self
+    }
+
+    /// Add the given `ref_path` to the 'want-ref' list, which requires the `ref-in-want` capability.
+    pub fn want_ref(mut self, ref_path: &BStr) -> Result<Self, Error> {
+        require(self.arguments.can_use_ref_in_want(), "ref-in-want", || {
+            format!("want-ref {ref_path}")
+        })?;
+        self.arguments.want_ref(ref_path);
+        Ok(self)
+    }
+
+    /// Add the given `id` pointing to a commit to the 'shallow' list, which requires the `shallow` capability.
+    pub fn shallow(mut self, id: impl AsRef<gix_hash::oid>) -> Result<Self, Error> {
+        let id = id.as_ref();
+        require(self.arguments.can_use_shallow(), "shallow", || format!("shallow {id}"))?;
+        self.arguments.shallow(id);
+        Ok(self)
+    }
+
+    /// Deepen the commit history by `depth` amount of commits, which requires the `shallow` capability.
+    pub fn deepen(mut self, depth: usize) -> Result<Self, Error> {
+        require(self.arguments.can_use_deepen(), "shallow", || format!("deepen {depth}"))?;
+        self.arguments.deepen(depth);
+        Ok(self)
+    }
+
+    /// Deepen the commit history to include all commits from now to (and including) `seconds` as passed since UNIX epoch,
+    /// which requires the `deepen-since` capability.
+    pub fn deepen_since(mut self, seconds: gix_date::SecondsSinceUnixEpoch) -> Result<Self, Error> {
+        require(self.arguments.can_use_deepen_since(), "deepen-since", || {
+            format!("deepen-since {seconds}")
+        })?;
+        self.arguments.deepen_since(seconds);
+        Ok(self)
+    }
+
+    /// Deepen the commit history in a relative instead of absolute fashion, which requires the `deepen-relative` capability.
+    pub fn deepen_relative(mut self) -> Result<Self, Error> {
+        require(self.arguments.can_use_deepen_relative(), "deepen-relative", || {
+            "deepen-relative".into()
+        })?;
+        self.arguments.deepen_relative();
+        Ok(self)
+    }
+
+    /// Do not include commits reachable by the given `ref_path` when deepening the history, which requires the
+    /// `deepen-not` capability.
+    pub fn deepen_not(mut self, ref_path: &BStr) -> Result<Self, Error> {
+        require(self.arguments.can_use_deepen_not(), "deepen-not", || {
+            format!("deepen-not {ref_path}")
+        })?;
+        self.arguments.deepen_not(ref_path);
+        Ok(self)
+    }
+
+    /// Ask the server to omit objects matching the given filter `spec`, like `blob:none` or `tree:0`, which requires
+    /// the `filter` capability.
+    pub fn filter(mut self, spec: &BStr) -> Result<Self, Error> {
+        require(self.arguments.can_use_filter(), "filter", || format!("filter {spec}"))?;
+        self.arguments.filter(spec).expect("capability was checked");
+        Ok(self)
+    }
+
+    /// Allow the server to include tags that point to commits or objects it would return, which requires the
+    /// `include-tag` capability.
+    pub fn include_tag(mut self) -> Result<Self, Error> {
+        require(self.arguments.can_use_include_tag(), "include-tag", || {
+            "include-tag".into()
+        })?;
+        self.arguments.use_include_tag();
+        Ok(self)
+    }
+}
+
+fn require(is_supported: bool, capability: &'static str, argument: impl FnOnce() -> String) -> Result<(), Error> {
+    if is_supported {
+        Ok(())
+    } else {
+        Err(Error::MissingCapability {
+            argument: argument().into(),
+            capability,
+        })
+    }
+}
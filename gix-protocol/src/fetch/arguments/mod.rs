@@ -104,10 +104,15 @@ impl Arguments {
     ///
     /// The server should respond with a corresponding 'wanted-refs' section if it will include the
     /// wanted ref in the packfile response.
+    ///
+    /// Note that this requires the `ref-in-want` capability, see [`can_use_ref_in_want()`][Self::can_use_ref_in_want()].
     pub fn want_ref(&mut self, ref_path: &BStr) {
-        let mut arg = BString::from("want-ref ");
-        arg.push_str(ref_path);
-        self.args.push(arg);
+        debug_assert!(self.ref_in_want, "'ref-in-want' feature required");
+        if self.ref_in_want {
+            let mut arg = BString::from("want-ref ");
+            arg.push_str(ref_path);
+            self.args.push(arg);
+        }
     }
     /// Add the given `id` pointing to a commit to the 'have' list.
     ///
@@ -152,12 +157,17 @@ impl Arguments {
             self.args.push(line);
         }
     }
-    /// Set the given filter `spec` when listing references.
-    pub fn filter(&mut self, spec: &str) {
-        debug_assert!(self.filter, "'filter' feature required");
-        if self.filter {
-            self.prefixed("filter ", spec);
+    /// Ask the server to omit objects matching the given filter `spec`, like `blob:none` or `tree:0`, for use in partial clones.
+    ///
+    /// Fails if the server didn't advertise the `filter` capability, see [`can_use_filter()`][Self::can_use_filter()].
+    pub fn filter(&mut self, spec: &BStr) -> Result<(), filter::Error> {
+        if !self.filter {
+            return Err(filter::Error::Unsupported { spec: spec.to_owned() });
         }
+        let mut line = BString::from("filter ");
+        line.extend_from_slice(spec);
+        self.args.push(line);
+        Ok(())
     }
     /// Permanently allow the server to include tags that point to commits or objects it would return.
     ///
@@ -271,6 +281,25 @@ mod shared {
     }
 }
 
+///
+#[cfg(any(feature = "async-client", feature = "blocking-client"))]
+pub mod builder;
+#[cfg(any(feature = "async-client", feature = "blocking-client"))]
+pub use builder::Builder;
+
+///
+pub mod filter {
+    use bstr::BString;
+
+    /// The error returned by [`Arguments::filter()`][super::Arguments::filter()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Cannot use filter '{spec}' as the server doesn't advertise the 'filter' capability")]
+        Unsupported { spec: BString },
+    }
+}
+
 #[cfg(feature = "async-client")]
 mod async_io;
 
@@ -11,7 +11,7 @@ pub enum Error {
     #[error(transparent)]
     Handshake(#[from] handshake::Error),
     #[error("Could not access repository or failed to read streaming pack file")]
-    Io(#[from] io::Error),
+    Io(io::Error),
     #[error(transparent)]
     Transport(#[from] client::Error),
     #[error(transparent)]
@@ -19,3 +19,17 @@ pub enum Error {
     #[error(transparent)]
     Response(#[from] response::Error),
 }
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        // Messages the remote sent on the error band or as `ERR` line are surfaced as such.
+        if err
+            .get_ref()
+            .map_or(false, |err| err.is::<gix_transport::packetline::read::Error>())
+        {
+            Error::Response(err.into())
+        } else {
+            Error::Io(err)
+        }
+    }
+}
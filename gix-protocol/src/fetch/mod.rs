@@ -1,4 +1,5 @@
-mod arguments;
+///
+pub mod arguments;
 pub use arguments::Arguments;
 
 ///
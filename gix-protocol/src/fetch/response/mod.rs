@@ -100,11 +100,17 @@ impl ShallowUpdate {
 impl Acknowledgement {
     /// Parse an `Acknowledgement` from a `line` as received to the server.
     pub fn from_line(line: &str) -> Result<Acknowledgement, Error> {
+        Self::from_line_with_id(line).map(|(ack, _id)| ack)
+    }
+
+    /// Like [`from_line()`][Self::from_line()], but also return the id of `ACK <id> …` lines, which is the only way
+    /// to learn the id of `ACK <id> ready` lines.
+    pub(crate) fn from_line_with_id(line: &str) -> Result<(Acknowledgement, Option<gix_hash::ObjectId>), Error> {
         let mut tokens = line.trim_end().splitn(3, ' ');
         match (tokens.next(), tokens.next(), tokens.next()) {
             (Some(first), id, description) => Ok(match first {
-                "ready" => Acknowledgement::Ready, // V2
-                "NAK" => Acknowledgement::Nak,     // V1
+                "ready" => (Acknowledgement::Ready, None), // V2
+                "NAK" => (Acknowledgement::Nak, None),     // V1
                 "ACK" => {
                     let id = match id {
                         Some(id) => gix_hash::ObjectId::from_hex(id.as_bytes())
@@ -114,11 +120,11 @@ impl Acknowledgement {
                     if let Some(description) = description {
                         match description {
                             "common" => {}
-                            "ready" => return Ok(Acknowledgement::Ready),
+                            "ready" => return Ok((Acknowledgement::Ready, Some(id))),
                             _ => return Err(Error::UnknownLineType { line: line.to_owned() }),
                         }
                     }
-                    Acknowledgement::Common(id)
+                    (Acknowledgement::Common(id), Some(id))
                 }
                 _ => return Err(Error::UnknownLineType { line: line.to_owned() }),
             }),
@@ -218,15 +224,19 @@ impl Response {
         shallows: &mut Vec<ShallowUpdate>,
         peeked_line: &str,
     ) -> bool {
-        match Acknowledgement::from_line(peeked_line) {
-            Ok(ack) => match ack.id() {
-                Some(id) => {
-                    if !acks.iter().any(|a| a.id() == Some(id)) {
-                        acks.push(ack);
+        match Acknowledgement::from_line_with_id(peeked_line) {
+            // With `multi_ack_detailed`, `ACK <id> ready` also means that `<id>` is common, even if it wasn't
+            // acknowledged as such before.
+            Ok((ack, common_id)) => {
+                if let Some(id) = common_id {
+                    if !acks.iter().any(|a| a.id() == Some(&id)) {
+                        acks.push(Acknowledgement::Common(id));
                     }
                 }
-                None => acks.push(ack),
-            },
+                if common_id.is_none() || ack == Acknowledgement::Ready {
+                    acks.push(ack);
+                }
+            }
             Err(_) => match ShallowUpdate::from_line(peeked_line) {
                 Ok(shallow) => {
                     shallows.push(shallow);
@@ -405,4 +405,63 @@ mod arguments {
             )
         }
     }
+
+    mod builder {
+        use bstr::ByteSlice;
+        use gix_transport::Protocol;
+
+        use crate::fetch::{
+            arguments::{builder, Builder},
+            tests::arguments::{id, transport},
+        };
+
+        fn builder_v2(features: impl IntoIterator<Item = &'static str>) -> Builder {
+            Builder::new(Protocol::V2, features.into_iter().map(|n| (n, None)).collect())
+        }
+
+        #[maybe_async::test(feature = "blocking-client", async(feature = "async-client", async_std::test))]
+        async fn arguments_with_supported_capabilities() -> Result<(), builder::Error> {
+            let mut out = Vec::new();
+            let mut t = transport(&mut out, false);
+            let mut arguments = builder_v2(["filter", "shallow"])
+                .want(id("ff333369de1221f9bfbbe03a3a13e9a09bc1ffff"))
+                .filter(b"blob:none".as_bstr())?
+                .deepen(1)?
+                .build();
+
+            arguments.send(&mut t, true).await.expect("sending to buffer to work");
+            assert_eq!(
+                out.as_bstr(),
+                b"0012command=fetch
+0001000ethin-pack
+000eofs-delta
+0015filter blob:none
+000ddeepen 1
+0032want ff333369de1221f9bfbbe03a3a13e9a09bc1ffff
+0009done
+0000"
+                    .as_bstr()
+            );
+            Ok(())
+        }
+
+        #[test]
+        fn filter_without_capability_fails() {
+            let err = builder_v2(["shallow"])
+                .want(id("ff333369de1221f9bfbbe03a3a13e9a09bc1ffff"))
+                .filter(b"blob:none".as_bstr())
+                .unwrap_err();
+            assert!(matches!(
+                &err,
+                builder::Error::MissingCapability {
+                    argument,
+                    capability: "filter"
+                } if argument == "filter blob:none"
+            ));
+            assert_eq!(
+                err.to_string(),
+                "Cannot use 'filter blob:none' as the server doesn't advertise the 'filter' capability"
+            );
+        }
+    }
 }
@@ -0,0 +1,142 @@
+use bstr::{BStr, BString, ByteSlice};
+
+/// The error returned by [`Report::from_lines()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("The report did not start with an 'unpack' line, got {line:?}")]
+    MissingUnpackStatus { line: Option<BString> },
+    #[error("{line:?} could not be parsed as reference status")]
+    MalformedLine { line: BString },
+    #[error("The option line {line:?} did not follow an 'ok' line")]
+    UnexpectedOption { line: BString },
+    #[error("The object id in option line {line:?} could not be decoded")]
+    DecodeId {
+        line: BString,
+        source: gix_hash::decode::Error,
+    },
+}
+
+/// The outcome of unpacking the pack sent by the client, as reported by the server.
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Unpack {
+    /// The pack was received and unpacked successfully.
+    Ok,
+    /// The pack could not be unpacked, with the `reason` given by the server.
+    Failed {
+        /// The reason as sent by the server.
+        reason: BString,
+    },
+}
+
+/// Whether or not the server updated a reference.
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Status {
+    /// The reference was updated.
+    Ok,
+    /// The server refused to update the reference, with the `reason` given by the server, like `hook declined`.
+    Rejected {
+        /// The reason as sent by the server.
+        reason: BString,
+    },
+}
+
+/// The status of a single reference the client asked to update.
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ref {
+    /// The name of the reference as sent by the client, like `refs/heads/main`.
+    pub name: BString,
+    /// Whether or not the reference was updated.
+    pub status: Status,
+    /// The name of the reference that was actually updated if it differs from `name`, as sent with `report-status-v2`.
+    pub updated_name: Option<BString>,
+    /// The previous value of the updated reference, as sent with `report-status-v2`.
+    pub previous_id: Option<gix_hash::ObjectId>,
+    /// The new value of the updated reference, as sent with `report-status-v2`.
+    pub new_id: Option<gix_hash::ObjectId>,
+    /// If `true`, the update wasn't a fast-forward, as sent with `report-status-v2`.
+    pub forced_update: bool,
+}
+
+/// The response of the server to a push as sent if the `report-status` or `report-status-v2` capability was negotiated.
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Report {
+    /// The outcome of unpacking the pack.
+    pub unpack: Unpack,
+    /// The status of each reference in the order in which the server reported them.
+    ///
+    /// With `report-status-v2`, the same reference may be listed multiple times if a hook decided to update
+    /// multiple references in its stead.
+    pub refs: Vec<Ref>,
+}
+
+impl Report {
+    /// Parse all `lines` of a `report-status` or `report-status-v2` response, without the terminating flush packet
+    /// and with or without trailing newlines.
+    pub fn from_lines<'a>(lines: impl IntoIterator<Item = &'a BStr>) -> Result<Self, Error> {
+        let mut lines = lines
+            .into_iter()
+            .map(|line| line.trim_end_with(|c| c == '\n').as_bstr());
+        let first = lines.next();
+        let unpack = match first.and_then(|line| line.strip_prefix(b"unpack ")) {
+            Some(b"ok") => Unpack::Ok,
+            Some(reason) => Unpack::Failed { reason: reason.into() },
+            None => {
+                return Err(Error::MissingUnpackStatus {
+                    line: first.map(ToOwned::to_owned),
+                })
+            }
+        };
+
+        let mut refs = Vec::<Ref>::new();
+        for line in lines {
+            if let Some(name) = line.strip_prefix(b"ok ") {
+                refs.push(Ref::new(name.into(), Status::Ok));
+            } else if let Some(rest) = line.strip_prefix(b"ng ") {
+                let (name, reason) = rest
+                    .split_once_str(" ")
+                    .ok_or_else(|| Error::MalformedLine { line: line.into() })?;
+                refs.push(Ref::new(name.into(), Status::Rejected { reason: reason.into() }));
+            } else if let Some(option) = line.strip_prefix(b"option ") {
+                let r = refs
+                    .last_mut()
+                    .filter(|r| r.status == Status::Ok)
+                    .ok_or_else(|| Error::UnexpectedOption { line: line.into() })?;
+                let decode_id = |hex: &[u8]| {
+                    gix_hash::ObjectId::from_hex(hex).map_err(|source| Error::DecodeId {
+                        line: line.into(),
+                        source,
+                    })
+                };
+                let (key, value) = option.split_once_str(" ").unwrap_or((option, &[]));
+                match key {
+                    b"refname" => r.updated_name = Some(value.into()),
+                    b"old-oid" => r.previous_id = Some(decode_id(value)?),
+                    b"new-oid" => r.new_id = Some(decode_id(value)?),
+                    b"forced-update" => r.forced_update = true,
+                    _ => {}
+                }
+            } else {
+                return Err(Error::MalformedLine { line: line.into() });
+            }
+        }
+        Ok(Report { unpack, refs })
+    }
+}
+
+impl Ref {
+    fn new(name: BString, status: Status) -> Self {
+        Ref {
+            name,
+            status,
+            updated_name: None,
+            previous_id: None,
+            new_id: None,
+            forced_update: false,
+        }
+    }
+}
@@ -7,3 +7,4 @@ pub fn fixture_bytes(path: &str) -> Vec<u8> {
 
 mod fetch;
 mod remote_progress;
+mod report_status;
@@ -6,4 +6,6 @@ pub fn fixture_bytes(path: &str) -> Vec<u8> {
 }
 
 mod fetch;
+mod handshake;
 mod remote_progress;
+mod report_status;
@@ -116,6 +116,25 @@ mod v1 {
             Ok(())
         }
 
+        #[maybe_async::test(feature = "blocking-client", async(feature = "async-client", async_std::test))]
+        async fn fetch_acks_with_ready_for_an_id_not_previously_in_common() -> crate::Result {
+            let mut provider = mock_reader("v1/fetch-ready-without-common.response");
+            let r = fetch::Response::from_line_reader(Protocol::V1, &mut provider.as_read_without_sidebands(), false)
+                .await?;
+            assert_eq!(
+                r.acknowledgements(),
+                &[
+                    Acknowledgement::Common(id("6504930888c9c5337e7e065c964f87b60d16a7d7")),
+                    Acknowledgement::Common(id("f22743895a3024bb0c958335981439f1fa747d57")),
+                    Acknowledgement::Ready,
+                    Acknowledgement::Nak,
+                ],
+                "the id of 'ACK <id> ready' is common as well, and isn't repeated when acknowledged again"
+            );
+            assert!(!r.has_pack());
+            Ok(())
+        }
+
         #[maybe_async::test(feature = "blocking-client", async(feature = "async-client", async_std::test))]
         async fn fetch_acks_and_pack() -> crate::Result {
             let mut provider = mock_reader("v1/fetch.response");
@@ -139,6 +158,39 @@ mod v1 {
         }
     }
 
+    mod acknowledgement_from_line {
+        use gix_protocol::fetch::response::Acknowledgement;
+
+        use crate::fetch::response::id;
+
+        #[test]
+        fn multi_ack_detailed() {
+            assert_eq!(
+                Acknowledgement::from_line("ACK 6504930888c9c5337e7e065c964f87b60d16a7d7 common\n").expect("valid"),
+                Acknowledgement::Common(id("6504930888c9c5337e7e065c964f87b60d16a7d7"))
+            );
+            assert_eq!(
+                Acknowledgement::from_line("ACK 6504930888c9c5337e7e065c964f87b60d16a7d7 ready\n").expect("valid"),
+                Acknowledgement::Ready
+            );
+            assert_eq!(
+                Acknowledgement::from_line("ACK 6504930888c9c5337e7e065c964f87b60d16a7d7").expect("valid"),
+                Acknowledgement::Common(id("6504930888c9c5337e7e065c964f87b60d16a7d7")),
+                "the final ACK without detail"
+            );
+            assert_eq!(
+                Acknowledgement::from_line("NAK\n").expect("valid"),
+                Acknowledgement::Nak
+            );
+        }
+
+        #[test]
+        fn unknown_details_are_rejected() {
+            assert!(Acknowledgement::from_line("ACK 6504930888c9c5337e7e065c964f87b60d16a7d7 continue").is_err());
+            assert!(Acknowledgement::from_line("ACK not-a-hash common").is_err());
+        }
+    }
+
     mod arguments {
         use bstr::ByteSlice;
         use gix_protocol::{fetch, Command};
@@ -192,6 +244,22 @@ mod v1 {
             assert_eq!(out.as_slice().as_bstr(), "009ewant aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa thin-pack side-band-64k ofs-delta shallow deepen-since deepen-not deepen-relative multi_ack_detailed filter\n000ddeepen 1\n0014deepen-relative\n0018deepen-since 123456\n0013deepen-not tag\n0035shallow 97c5a932b3940a09683e924ef6a92b31a6f7c6de\n00000032have bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb\n0009done\n");
             Ok(())
         }
+
+        #[test]
+        fn filter_is_unavailable_if_not_advertised() -> crate::Result {
+            let (caps, _) = Capabilities::from_bytes(&b"7814e8a05a59c0cf5fb186661d1551c75d1299b5 HEAD\0multi_ack thin-pack side-band side-band-64k ofs-delta shallow no-progress include-tag multi_ack_detailed object-format=sha1 agent=git/2.28.0"[..])?;
+            let features = Command::Fetch.default_features(Protocol::V1, &caps);
+            assert!(features.iter().all(|(name, _)| *name != "filter"));
+            let mut args = fetch::Arguments::new(Protocol::V1, features);
+            assert!(!args.can_use_filter());
+            let err = args.filter("blob:none".into()).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "Cannot use filter 'blob:none' as the server doesn't advertise the 'filter' capability"
+            );
+            assert!(args.is_empty(), "nothing was added");
+            Ok(())
+        }
     }
 }
 mod v2 {
@@ -204,7 +272,7 @@ mod v2 {
         use gix_packetline::read::ProgressAction;
         use gix_protocol::fetch::{
             self,
-            response::{Acknowledgement, ShallowUpdate},
+            response::{Acknowledgement, ShallowUpdate, WantedRef},
         };
         use gix_transport::Protocol;
 
@@ -327,6 +395,29 @@ mod v2 {
             Ok(())
         }
 
+        #[maybe_async::test(feature = "blocking-client", async(feature = "async-client", async_std::test))]
+        async fn wanted_refs() -> crate::Result {
+            let mut provider = mock_reader("v2/fetch-wanted-refs.response");
+            let r = fetch::Response::from_line_reader(Protocol::V2, &mut provider.as_read_without_sidebands(), true)
+                .await?;
+            assert!(r.acknowledgements().is_empty(), "it should go straight to the packfile");
+            assert_eq!(
+                r.wanted_refs(),
+                &[
+                    WantedRef {
+                        id: id("9e320b9180e0b5580af68fa3255b7f3d9ecd5af0"),
+                        path: "refs/heads/main".into(),
+                    },
+                    WantedRef {
+                        id: id("808e50d724f604f69ab93c6da2919c014667bedb"),
+                        path: "refs/heads/feature".into(),
+                    }
+                ]
+            );
+            assert!(r.has_pack());
+            Ok(())
+        }
+
         #[maybe_async::test(feature = "blocking-client", async(feature = "async-client", async_std::test))]
         async fn fetch_with_err_response() {
             let mut provider = mock_reader("v2/fetch-err-line.response");
@@ -343,6 +434,24 @@ mod v2 {
             }
         }
 
+        #[test]
+        fn remote_errors_from_the_pack_stream_are_surfaced_as_upload_pack_errors() {
+            let err = std::io::Error::new(
+                std::io::ErrorKind::Other,
+                gix_packetline::read::Error {
+                    message: "fatal: the remote end hung up".into(),
+                },
+            );
+            match fetch::Error::from(err) {
+                fetch::Error::Response(fetch::response::Error::UploadPack(err)) => {
+                    assert_eq!(err.message, "fatal: the remote end hung up")
+                }
+                err => panic!("unexpected error: {err:?}"),
+            }
+            let err = fetch::Error::from(std::io::Error::from(std::io::ErrorKind::UnexpectedEof));
+            assert!(matches!(err, fetch::Error::Io(_)), "other IO errors are passed through");
+        }
+
         #[maybe_async::test(feature = "blocking-client", async(feature = "async-client", async_std::test))]
         async fn fetch_acks_and_pack() -> crate::Result {
             let mut provider = mock_reader("v2/fetch.response");
@@ -414,5 +523,35 @@ mod v2 {
 0032have bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb\n0009done\n0000");
             Ok(())
         }
+
+        #[maybe_async::test(feature = "blocking-client", async(feature = "async-client", async_std::test))]
+        async fn filter() -> crate::Result {
+            let (caps, _) = Capabilities::from_bytes(&b"7814e8a05a59c0cf5fb186661d1551c75d1299b5 HEAD\0multi_ack thin-pack filter side-band side-band-64k ofs-delta shallow deepen-since deepen-not deepen-relative no-progress include-tag multi_ack_detailed symref=HEAD:refs/heads/master object-format=sha1 agent=git/2.28.0"[..])?;
+            for (spec, expected_line) in [
+                ("blob:none", "0015filter blob:none\n"),
+                ("tree:0", "0012filter tree:0\n"),
+            ] {
+                let mut args =
+                    fetch::Arguments::new(Protocol::V2, Command::Fetch.default_features(Protocol::V1, &caps));
+                args.filter(spec.into())?;
+                args.want(id("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"));
+
+                let mut out = Vec::new();
+                let mut transport = transport(
+                    &mut out,
+                    "v1/clone.response",
+                    Protocol::V2,
+                    gix_transport::client::git::ConnectMode::Daemon,
+                );
+
+                args.send(&mut transport, true).await?;
+                assert_eq!(
+                    out.as_slice().as_bstr(),
+                    format!("0012command=fetch\n0001000ethin-pack\n000eofs-delta\n{expected_line}0032want aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\n0009done\n0000"),
+                    "{spec}"
+                );
+            }
+            Ok(())
+        }
     }
 }
@@ -1,6 +1,6 @@
 use bstr::ByteSlice;
 use gix_features::progress;
-use gix_protocol::{fetch, handshake, ls_refs, FetchConnection};
+use gix_protocol::{bundle_uri, fetch, handshake, ls_refs, FetchConnection};
 use gix_transport::Protocol;
 
 use crate::fetch::{helper_unused, oid, transport, CloneDelegate, CloneRefInWantDelegate, LsRemoteDelegate};
@@ -106,6 +106,81 @@ async fn ls_remote() -> crate::Result {
     Ok(())
 }
 
+#[maybe_async::test(feature = "blocking-client", async(feature = "async-client", async_std::test))]
+async fn bundle_uri_list() -> crate::Result {
+    let mut transport = transport(
+        Vec::new(),
+        "v2/bundle-uri.response",
+        Protocol::V2,
+        gix_transport::client::git::ConnectMode::Daemon,
+    );
+    let outcome = gix_protocol::handshake(
+        &mut transport,
+        gix_transport::Service::UploadPack,
+        helper_unused,
+        Vec::new(),
+        &mut progress::Discard,
+    )
+    .await?;
+    let list = gix_protocol::bundle_uri(
+        &mut transport,
+        &outcome.capabilities,
+        vec![("agent", Some("test".into()))],
+        &mut progress::Discard,
+    )
+    .await?;
+    assert_eq!(
+        list,
+        bundle_uri::List {
+            version: Some(1),
+            mode: bundle_uri::Mode::All,
+            bundles: vec![
+                bundle_uri::Bundle {
+                    id: "everything".into(),
+                    uri: "https://cdn.example.com/everything.bundle".into(),
+                    creation_token: Some(1),
+                },
+                bundle_uri::Bundle {
+                    id: "recent".into(),
+                    uri: "recent.bundle".into(),
+                    creation_token: Some(2),
+                }
+            ]
+        }
+    );
+    assert_eq!(
+        transport.into_inner().1.as_bstr(),
+        b"002fgit-upload-pack does/not/matter\0\0version=2\00017command=bundle-uri\n000fagent=test\n0000".as_bstr()
+    );
+    Ok(())
+}
+
+#[test]
+fn bundle_uri_list_from_key_value_pairs() {
+    let list = bundle_uri::List::from_key_value_pairs(
+        [
+            ("bundle.version", "1"),
+            ("Bundle.Mode", "any"),
+            ("bundle.with.dots.uri", "https://example.com/a.bundle"),
+            ("bundle.no-uri.creationToken", "5"),
+            ("bundle.unknown.future-key", "value"),
+            ("other.section.uri", "ignored"),
+        ]
+        .iter()
+        .map(|(k, v)| (k.as_bytes().as_bstr(), v.as_bytes().as_bstr())),
+    );
+    assert_eq!(list.mode, bundle_uri::Mode::Any, "keys are case-insensitive");
+    assert_eq!(
+        list.bundles,
+        [bundle_uri::Bundle {
+            id: "with.dots".into(),
+            uri: "https://example.com/a.bundle".into(),
+            creation_token: None,
+        }],
+        "ids may contain dots, and bundles without URI are dropped"
+    );
+}
+
 #[maybe_async::test(feature = "blocking-client", async(feature = "async-client", async_std::test))]
 async fn ls_remote_abort_in_prep_ls_refs() -> crate::Result {
     let out = Vec::new();
@@ -0,0 +1,128 @@
+use std::{borrow::Cow, io};
+
+use bstr::BStr;
+use gix_protocol::{
+    credentials::{helper, protocol::Context},
+    handshake,
+};
+use gix_transport::{
+    client::{self, MessageKind, RequestWriter, SetServiceResponse, Transport, TransportWithoutIO, WriteMode},
+    Protocol, Service,
+};
+
+use crate::fetch::transport;
+
+/// A transport which denies access until an identity with the expected `password` is set.
+struct RequiresPassword<T> {
+    inner: T,
+    password: &'static str,
+    authenticated: bool,
+}
+
+impl<T: TransportWithoutIO> TransportWithoutIO for RequiresPassword<T> {
+    fn set_identity(&mut self, identity: gix_sec::identity::Account) -> Result<(), client::Error> {
+        self.authenticated = identity.password == self.password;
+        Ok(())
+    }
+
+    fn request(
+        &mut self,
+        write_mode: WriteMode,
+        on_into_read: MessageKind,
+    ) -> Result<RequestWriter<'_>, client::Error> {
+        self.inner.request(write_mode, on_into_read)
+    }
+
+    fn to_url(&self) -> Cow<'_, BStr> {
+        self.inner.to_url()
+    }
+
+    fn connection_persists_across_multiple_requests(&self) -> bool {
+        self.inner.connection_persists_across_multiple_requests()
+    }
+
+    fn configure(
+        &mut self,
+        config: &dyn std::any::Any,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+        self.inner.configure(config)
+    }
+}
+
+impl<T: Transport> Transport for RequiresPassword<T> {
+    fn handshake<'a>(
+        &mut self,
+        service: Service,
+        extra_parameters: &'a [(&'a str, Option<&'a str>)],
+    ) -> Result<SetServiceResponse<'_>, client::Error> {
+        if !self.authenticated {
+            return Err(client::Error::Io(io::ErrorKind::PermissionDenied.into()));
+        }
+        self.inner.handshake(service, extra_parameters)
+    }
+}
+
+/// Perform a handshake with a server expecting `expected_password` while the credential helper provides `password`,
+/// and return the handshake result along with the helper actions that were invoked.
+fn handshake_with_password(
+    password: &'static str,
+    expected_password: &'static str,
+) -> (Result<handshake::Outcome, handshake::Error>, Vec<&'static str>) {
+    let mut out = Vec::new();
+    let transport = RequiresPassword {
+        inner: transport(
+            &mut out,
+            "v1/clone.response",
+            Protocol::V1,
+            gix_transport::client::git::ConnectMode::Daemon,
+        ),
+        password: expected_password,
+        authenticated: false,
+    };
+    let mut actions = Vec::new();
+    let res = gix_protocol::handshake(
+        transport,
+        Service::UploadPack,
+        |action| {
+            actions.push(match &action {
+                helper::Action::Get(_) => "get",
+                helper::Action::Store(_) => "store",
+                helper::Action::Erase(_) => "erase",
+                helper::Action::List(_) => "list",
+            });
+            if !matches!(action, helper::Action::Get(_)) {
+                return Ok(None);
+            }
+            let mut outcome = helper::Outcome {
+                username: Some("user".into()),
+                password: Some(password.into()),
+                quit: false,
+                next: Context::default().into(),
+            };
+            Ok(Some(gix_protocol::credentials::protocol::Outcome {
+                identity: outcome.consume_identity().expect("complete"),
+                next: outcome.next,
+            }))
+        },
+        Vec::new(),
+        &mut gix_features::progress::Discard,
+    );
+    (res, actions)
+}
+
+#[test]
+fn credentials_are_stored_after_successful_authentication() {
+    let (res, actions) = handshake_with_password("good", "good");
+    assert!(res.is_ok(), "the second handshake succeeds with valid credentials");
+    assert_eq!(actions, ["get", "store"]);
+}
+
+#[test]
+fn credentials_are_erased_if_authentication_fails() {
+    let (res, actions) = handshake_with_password("bad", "good");
+    assert!(
+        matches!(res, Err(handshake::Error::InvalidCredentials { .. })),
+        "still being denied means the credentials are invalid"
+    );
+    assert_eq!(actions, ["get", "erase"], "bad credentials are never stored");
+}
@@ -0,0 +1,108 @@
+mod from_lines {
+    use bstr::ByteSlice;
+    use gix_protocol::report_status::{Error, Ref, Report, Status, Unpack};
+
+    fn parse(lines: &[&str]) -> Result<Report, Error> {
+        Report::from_lines(lines.iter().map(|line| line.as_bytes().as_bstr()))
+    }
+
+    #[test]
+    fn one_accepted_and_one_rejected_ref() -> crate::Result {
+        let report = parse(&[
+            "unpack ok\n",
+            "ok refs/heads/main\n",
+            "ng refs/heads/feature hook declined\n",
+        ])?;
+        assert_eq!(
+            report,
+            Report {
+                unpack: Unpack::Ok,
+                refs: vec![
+                    Ref {
+                        name: "refs/heads/main".into(),
+                        status: Status::Ok,
+                        updated_name: None,
+                        previous_id: None,
+                        new_id: None,
+                        forced_update: false,
+                    },
+                    Ref {
+                        name: "refs/heads/feature".into(),
+                        status: Status::Rejected {
+                            reason: "hook declined".into()
+                        },
+                        updated_name: None,
+                        previous_id: None,
+                        new_id: None,
+                        forced_update: false,
+                    }
+                ]
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn unpack_failure() -> crate::Result {
+        let report = parse(&["unpack index-pack abnormal exit", "ng refs/heads/main unpacker error"])?;
+        assert_eq!(
+            report.unpack,
+            Unpack::Failed {
+                reason: "index-pack abnormal exit".into()
+            }
+        );
+        assert_eq!(
+            report.refs[0].status,
+            Status::Rejected {
+                reason: "unpacker error".into()
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn v2_options_apply_to_the_preceding_ok_line() -> crate::Result {
+        let report = parse(&[
+            "unpack ok",
+            "ok refs/for/main",
+            "option refname refs/pull/123/head",
+            "option old-oid 0000000000000000000000000000000000000000",
+            "option new-oid 4b825dc642cb6eb9a060e54bf8d69288fbee4904",
+            "option forced-update",
+            "ok refs/heads/next",
+        ])?;
+        assert_eq!(report.refs.len(), 2);
+        let r = &report.refs[0];
+        assert_eq!(r.name, "refs/for/main");
+        assert_eq!(r.updated_name.as_ref().expect("set"), "refs/pull/123/head");
+        assert_eq!(r.previous_id, Some(gix_hash::Kind::Sha1.null()));
+        assert_eq!(r.new_id, Some(gix_hash::ObjectId::empty_tree(gix_hash::Kind::Sha1)));
+        assert!(r.forced_update);
+        assert_eq!(report.refs[1].updated_name, None, "options don't carry over");
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert!(matches!(
+            parse(&[]).unwrap_err(),
+            Error::MissingUnpackStatus { line: None }
+        ));
+        assert!(matches!(
+            parse(&["ok refs/heads/main"]).unwrap_err(),
+            Error::MissingUnpackStatus { line: Some(_) }
+        ));
+        assert!(matches!(
+            parse(&["unpack ok", "ng refs/heads/main"]).unwrap_err(),
+            Error::MalformedLine { .. }
+        ));
+        assert!(matches!(
+            parse(&["unpack ok", "option forced-update"]).unwrap_err(),
+            Error::UnexpectedOption { .. }
+        ));
+        assert!(matches!(
+            parse(&["unpack ok", "ok refs/heads/main", "option new-oid foo"]).unwrap_err(),
+            Error::DecodeId { .. }
+        ));
+    }
+}
@@ -68,6 +68,32 @@ pub mod store {
         Disable,
     }
 
+    /// A function to decide how to handle the reflog of a reference by its full name, overriding
+    /// [`write_reflog`][crate::file::Store::write_reflog] of the store on a per-reference basis.
+    ///
+    /// It's called with the full, non-namespaced name of the reference whose reflog is about to be written, along with
+    /// the store-wide [`WriteReflog`] mode, and returns the mode to use for this reference.
+    #[derive(Clone)]
+    pub struct ReflogPolicy(std::sync::Arc<dyn Fn(&crate::FullNameRef, WriteReflog) -> WriteReflog + Send + Sync>);
+
+    impl ReflogPolicy {
+        /// Create a new instance from the given `policy` function.
+        pub fn new(policy: impl Fn(&crate::FullNameRef, WriteReflog) -> WriteReflog + Send + Sync + 'static) -> Self {
+            ReflogPolicy(std::sync::Arc::new(policy))
+        }
+
+        /// Return the mode to use for the reflog of the reference named `name` if the store would otherwise use `mode`.
+        pub fn mode_for(&self, name: &crate::FullNameRef, mode: WriteReflog) -> WriteReflog {
+            (self.0)(name, mode)
+        }
+    }
+
+    impl std::fmt::Debug for ReflogPolicy {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("ReflogPolicy(..)")
+        }
+    }
+
     /// A thread-local handle for interacting with a [`Store`][crate::Store] to find and iterate references.
     #[derive(Clone)]
     #[allow(dead_code)]
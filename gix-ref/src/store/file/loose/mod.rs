@@ -38,6 +38,7 @@ mod init {
                 git_dir: git_dir.into(),
                 common_dir: None,
                 write_reflog,
+                reflog_policy: None,
                 namespace: None,
                 packed: gix_fs::SharedFileSnapshotMut::new().into(),
                 object_hash,
@@ -56,6 +57,7 @@ mod init {
                 git_dir: git_dir.into(),
                 common_dir: Some(common_dir.into()),
                 write_reflog,
+                reflog_policy: None,
                 namespace: None,
                 packed: gix_fs::SharedFileSnapshotMut::new().into(),
                 object_hash,
@@ -108,9 +108,13 @@ pub mod create_or_update {
             mut force_create_reflog: bool,
         ) -> Result<(), Error> {
             let (reflog_base, full_name) = self.reflog_base_and_relative_path(name);
-            match self.write_reflog {
+            let write_reflog = self
+                .reflog_policy
+                .as_ref()
+                .map_or(self.write_reflog, |policy| policy.mode_for(name, self.write_reflog));
+            match write_reflog {
                 WriteReflog::Normal | WriteReflog::Always => {
-                    if self.write_reflog == WriteReflog::Always {
+                    if write_reflog == WriteReflog::Always {
                         force_create_reflog = true;
                     }
                     let mut options = std::fs::OpenOptions::new();
@@ -154,3 +154,47 @@ fn missing_reflog_creates_it_even_if_similarly_named_empty_dir_exists_and_append
     }
     Ok(())
 }
+
+#[test]
+fn reflog_policy_can_suppress_reflogs_by_prefix() -> Result {
+    let (_keep, mut store) = empty_store(WriteReflog::Normal)?;
+    store.reflog_policy = Some(crate::store::ReflogPolicy::new(|name, mode| {
+        if name.as_bstr().starts_with(b"refs/remotes/") {
+            WriteReflog::Disable
+        } else {
+            mode
+        }
+    }));
+    let new = hex_to_id("28ce6a8b26aa170e1de65536fe8abe1832bd3242");
+    let committer = Signature {
+        name: "committer".into(),
+        email: "committer@example.com".into(),
+        time: Time {
+            seconds: 1234,
+            offset: 1800,
+            sign: Sign::Plus,
+        },
+    };
+    for name in ["refs/heads/main", "refs/remotes/origin/main"] {
+        store.reflog_create_or_append(
+            name.try_into()?,
+            None,
+            &new,
+            committer.to_ref().into(),
+            b"the message".as_bstr(),
+            false,
+        )?;
+    }
+
+    let mut buf = Vec::new();
+    assert_eq!(
+        reflog_lines(&store, "refs/heads/main", &mut buf)?.len(),
+        1,
+        "references not affected by the policy use the store-wide mode"
+    );
+    assert!(
+        store.reflog_iter("refs/remotes/origin/main", &mut buf)?.is_none(),
+        "the policy suppressed the reflog for remote tracking branches"
+    );
+    Ok(())
+}
@@ -3,7 +3,11 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use crate::{bstr::BStr, store::WriteReflog, Namespace};
+use crate::{
+    bstr::BStr,
+    store::{ReflogPolicy, WriteReflog},
+    Namespace,
+};
 
 /// A store for reference which uses plain files.
 ///
@@ -23,6 +27,9 @@ pub struct Store {
 
     /// The way to handle reflog edits
     pub write_reflog: WriteReflog,
+    /// If set, a function to determine how to handle the reflog for each reference individually, based on its full name
+    /// and [`write_reflog`][Store::write_reflog]. If unset, `write_reflog` is used for all references.
+    pub reflog_policy: Option<ReflogPolicy>,
     /// The namespace to use for edits and reads
     pub namespace: Option<Namespace>,
     /// A packed buffer which can be mapped in one version and shared as such.
@@ -223,7 +223,7 @@ impl client::Transport for SpawnProcessOnDemand {
         for env_to_remove in ENV_VARS_TO_REMOVE {
             cmd.env_remove(env_to_remove);
         }
-        cmd.envs(std::mem::take(&mut self.envs));
+        cmd.envs(self.envs.iter().cloned());
 
         let mut child = cmd.spawn().map_err(|err| client::Error::InvokeProgram {
             source: err,
@@ -156,8 +156,12 @@ pub fn new() -> (
                     proxy_authenticate,
                     verbose,
                     ssl_ca_info,
+                    ssl_ca_path,
+                    ssl_verify,
                     ssl_version,
                     http_version,
+                    cookie_file,
+                    save_cookies,
                     backend,
                 },
         } in req_recv
@@ -176,6 +180,19 @@ pub fn new() -> (
             if let Some(ca_info) = ssl_ca_info {
                 handle.cainfo(ca_info)?;
             }
+            if let Some(ca_path) = ssl_ca_path {
+                handle.capath(ca_path)?;
+            }
+            handle.ssl_verify_peer(ssl_verify)?;
+            handle.ssl_verify_host(ssl_verify)?;
+
+            let save_cookies = save_cookies && cookie_file.is_some();
+            if let Some(cookie_file) = &cookie_file {
+                handle.cookie_file(cookie_file)?;
+                if save_cookies {
+                    handle.cookie_jar(cookie_file)?;
+                }
+            }
 
             if let Some(ref mut curl_options) = backend.as_ref().and_then(|backend| backend.lock().ok()) {
                 if let Some(opts) = curl_options.downcast_mut::<super::Options>() {
@@ -348,6 +365,10 @@ pub fn new() -> (
                 handler.receive_body.take();
                 handler.send_header.take();
                 handler.send_data.take();
+                if save_cookies {
+                    // Write the cookie jar now as it would otherwise only be written when the handle is dropped.
+                    handle.cookie_list("FLUSH")?;
+                }
                 let actual_url = handle
                     .effective_url()?
                     .expect("effective url is present and valid UTF-8");
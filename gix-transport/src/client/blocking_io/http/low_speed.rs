@@ -0,0 +1,142 @@
+//! Abort transfers that remain below a minimal transfer rate for too long, as configured by `http.lowSpeedLimit`
+//! and `http.lowSpeedTime`.
+//!
+//! The `curl` backend has native support for this, so this is used by the `reqwest` backend only.
+use std::{
+    io,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc, Arc,
+    },
+    time::{Duration, Instant},
+};
+
+/// The error returned as source of an [`io::Error`] of kind [`TimedOut`][io::ErrorKind::TimedOut] if a transfer
+/// was too slow for too long.
+#[derive(Debug, thiserror::Error)]
+#[error("Transfer rate was below {limit_bytes_per_second} bytes per second for {}s", time.as_secs_f32())]
+pub struct Error {
+    /// The minimal amount of bytes per second that were expected.
+    pub limit_bytes_per_second: u32,
+    /// The time for which the transfer rate was below the limit.
+    pub time: Duration,
+}
+
+/// A reader which fails with an [`Error`] if less than `limit_bytes_per_second` were read on average for `time`.
+///
+/// Note that the transfer rate is only checked when data is received, hence a read that blocks forever can't be
+/// aborted this way. Use [`abort_stalls_with()`][Reader::abort_stalls_with()] to detect these as well.
+pub struct Reader<R> {
+    inner: R,
+    limit_bytes_per_second: u32,
+    time: Duration,
+    window_start: Instant,
+    bytes_in_window: u64,
+    /// The total amount of bytes read, shared with the watchdog thread, if there is one.
+    bytes_total: Arc<AtomicU64>,
+    /// Dropping this stops the watchdog thread, if there is one.
+    _stop_watchdog: Option<mpsc::Sender<()>>,
+}
+
+impl<R> Reader<R> {
+    /// Create a new instance to read from `inner`, which fails if fewer than `limit_bytes_per_second` are read on average for `time`.
+    ///
+    /// If either `limit_bytes_per_second` or `time` are zero, the limit is disabled.
+    pub fn new(inner: R, limit_bytes_per_second: u32, time: Duration) -> Self {
+        Reader {
+            inner,
+            limit_bytes_per_second,
+            time,
+            window_start: Instant::now(),
+            bytes_in_window: 0,
+            bytes_total: Default::default(),
+            _stop_watchdog: None,
+        }
+    }
+
+    /// Create a new instance to read from `inner` with limits as configured in `options`.
+    pub fn from_options(inner: R, options: &super::Options) -> Self {
+        Self::new(
+            inner,
+            options.low_speed_limit_bytes_per_second,
+            Duration::from_secs(options.low_speed_time_seconds),
+        )
+    }
+
+    /// Start a thread which calls `on_stall` with the error that [`read()`][io::Read::read()] would return if
+    /// the transfer rate stays below the limit, even if no data is received at all and `read()` blocks.
+    ///
+    /// `on_stall` is called at most once, and the thread stops when this instance is dropped.
+    /// Nothing happens if the limit is disabled.
+    pub fn abort_stalls_with(&mut self, on_stall: impl FnOnce(io::Error) + Send + 'static) {
+        if !self.is_enabled() {
+            return;
+        }
+        let (stop_tx, stop_rx) = mpsc::channel::<()>();
+        let bytes_total = self.bytes_total.clone();
+        let (limit_bytes_per_second, time) = (self.limit_bytes_per_second, self.time);
+        std::thread::Builder::new()
+            .name("gix-transport low-speed watchdog".into())
+            .spawn(move || {
+                let mut window_start = (Instant::now(), bytes_total.load(Ordering::Relaxed));
+                loop {
+                    match stop_rx.recv_timeout(time) {
+                        Err(mpsc::RecvTimeoutError::Timeout) => {}
+                        Ok(()) | Err(mpsc::RecvTimeoutError::Disconnected) => return,
+                    }
+                    let elapsed = window_start.0.elapsed();
+                    let bytes = bytes_total.load(Ordering::Relaxed);
+                    if !is_fast_enough(bytes - window_start.1, elapsed, limit_bytes_per_second) {
+                        on_stall(timed_out(limit_bytes_per_second, time));
+                        return;
+                    }
+                    window_start = (Instant::now(), bytes);
+                }
+            })
+            .expect("named threads with default stack size can always be spawned");
+        self._stop_watchdog = Some(stop_tx);
+    }
+
+    /// Return the reader we wrap.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.limit_bytes_per_second != 0 && !self.time.is_zero()
+    }
+}
+
+impl<R: io::Read> io::Read for Reader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let bytes_read = self.inner.read(buf)?;
+        if bytes_read == 0 || !self.is_enabled() {
+            return Ok(bytes_read);
+        }
+        self.bytes_in_window += bytes_read as u64;
+        self.bytes_total.fetch_add(bytes_read as u64, Ordering::Relaxed);
+        let elapsed = self.window_start.elapsed();
+        if elapsed >= self.time {
+            if !is_fast_enough(self.bytes_in_window, elapsed, self.limit_bytes_per_second) {
+                return Err(timed_out(self.limit_bytes_per_second, self.time));
+            }
+            self.window_start = Instant::now();
+            self.bytes_in_window = 0;
+        }
+        Ok(bytes_read)
+    }
+}
+
+fn is_fast_enough(bytes: u64, elapsed: Duration, limit_bytes_per_second: u32) -> bool {
+    bytes as f64 / elapsed.as_secs_f64() >= f64::from(limit_bytes_per_second)
+}
+
+fn timed_out(limit_bytes_per_second: u32, time: Duration) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::TimedOut,
+        Error {
+            limit_bytes_per_second,
+            time,
+        },
+    )
+}
@@ -38,6 +38,8 @@ pub mod reqwest;
 
 mod traits;
 
+pub mod low_speed;
+
 ///
 pub mod options {
     /// A function to authenticate a URL.
@@ -120,7 +122,7 @@ pub mod options {
 
 /// Options to configure http requests.
 // TODO: testing most of these fields requires a lot of effort, unless special flags to introspect ongoing requests are added.
-#[derive(Default, Clone)]
+#[derive(Clone)]
 pub struct Options {
     /// Headers to be added to every request.
     /// They are applied unconditionally and are expected to be valid as they occur in an HTTP request, like `header: value`, without newlines.
@@ -177,14 +179,60 @@ pub struct Options {
     pub verbose: bool,
     /// If set, use this path to point to a file with CA certificates to verify peers.
     pub ssl_ca_info: Option<PathBuf>,
+    /// If set, use this path to point to a directory with CA certificates to verify peers.
+    ///
+    /// Note that this is only supported by the `curl` backend.
+    /// Refers to `http.sslCAPath`.
+    pub ssl_ca_path: Option<PathBuf>,
+    /// If `false`, neither the certificate of the peer nor its host name will be verified, which is `true` by default.
+    ///
+    /// Note that this is only supported by the `curl` backend.
+    /// Refers to `http.sslVerify`.
+    pub ssl_verify: bool,
     /// The SSL version or version range to use, or `None` to let the TLS backend determine which versions are acceptable.
     pub ssl_version: Option<SslVersionRangeInclusive>,
     /// The HTTP version to enforce. If unset, it is implementation defined.
     pub http_version: Option<HttpVersion>,
+    /// If set, read cookies to send to the server from this file, which is in the Netscape cookie file format.
+    ///
+    /// Note that this is only supported by the `curl` backend.
+    /// Refers to `http.cookieFile`.
+    pub cookie_file: Option<PathBuf>,
+    /// If `true`, write cookies received from the server back to [`cookie_file`][Self::cookie_file] after each request,
+    /// along with the ones that were read from it. It has no effect if `cookie_file` isn't set.
+    ///
+    /// Refers to `http.saveCookies`.
+    pub save_cookies: bool,
     /// Backend specific options, if available.
     pub backend: Option<Arc<Mutex<dyn Any + Send + Sync + 'static>>>,
 }
 
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            extra_headers: Default::default(),
+            follow_redirects: Default::default(),
+            low_speed_limit_bytes_per_second: Default::default(),
+            low_speed_time_seconds: Default::default(),
+            proxy: Default::default(),
+            no_proxy: Default::default(),
+            proxy_auth_method: Default::default(),
+            proxy_authenticate: Default::default(),
+            user_agent: Default::default(),
+            connect_timeout: Default::default(),
+            verbose: Default::default(),
+            ssl_ca_info: Default::default(),
+            ssl_ca_path: Default::default(),
+            ssl_verify: true,
+            ssl_version: Default::default(),
+            http_version: Default::default(),
+            cookie_file: Default::default(),
+            save_cookies: Default::default(),
+            backend: Default::default(),
+        }
+    }
+}
+
 /// The actual http client implementation, using curl
 #[cfg(feature = "http-client-curl")]
 pub type Impl = curl::Curl;
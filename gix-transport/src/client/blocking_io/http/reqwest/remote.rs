@@ -137,6 +137,13 @@ impl Default for Remote {
 
                 // reading the response body is streaming and may fail for many reasons. If so, we send the error over the response
                 // body channel and that's all we can do.
+                let mut res = http::low_speed::Reader::from_options(&mut res, &config);
+                res.abort_stalls_with({
+                    let body = response_body_tx.channel.clone();
+                    move |err| {
+                        body.send(Err(err)).ok();
+                    }
+                });
                 if let Err(err) = std::io::copy(&mut res, &mut response_body_tx) {
                     response_body_tx.channel.send(Err(err)).ok();
                 }
@@ -673,3 +673,169 @@ fn check_content_type_is_case_insensitive() -> crate::Result {
     assert!(result.is_ok());
     Ok(())
 }
+
+#[test]
+#[cfg(feature = "http-client-curl")]
+fn cookies_are_read_from_the_cookie_file_and_received_ones_are_saved_to_it() -> crate::Result {
+    let tmp = tempfile::tempdir()?;
+    let cookie_file = tmp.path().join("cookies");
+    std::fs::write(&cookie_file, "127.0.0.1\tFALSE\t/\tFALSE\t2147483647\tfrom-file\tyes\n")?;
+
+    let mut handshake = fixture_bytes("v1/http-handshake.response");
+    let end_of_status_line = handshake.find_byte(b'\n').expect("status line") + 1;
+    handshake.splice(
+        end_of_status_line..end_of_status_line,
+        b"Set-Cookie: from-server=yes; Path=/\n".iter().copied(),
+    );
+    let server = mock::Server::new(handshake);
+    let mut c = http::connect(
+        format!("http://127.0.0.1:{}/path", server.addr.port())
+            .as_str()
+            .try_into()?,
+        Protocol::V1,
+    );
+    c.configure(&http::Options {
+        cookie_file: Some(cookie_file.clone()),
+        save_cookies: true,
+        ..Default::default()
+    })?;
+
+    let SetServiceResponse { refs, .. } = c.handshake(Service::UploadPack, &[])?;
+    io::copy(&mut refs.expect("refs in protocol V1"), &mut io::sink())?;
+    let cookie_header = |request: String| {
+        request
+            .lines()
+            .map(str::to_lowercase)
+            .find(|l| l.starts_with("cookie:"))
+            .expect("cookie header")
+    };
+    assert_eq!(
+        cookie_header(server.received_as_string()),
+        "cookie: from-file=yes",
+        "cookies from the file are sent"
+    );
+
+    server.next_read_and_respond_with(fixture_bytes("v1/http-clone.response"));
+    let mut writer = c.request(
+        client::WriteMode::OneLfTerminatedLinePerWriteCall,
+        client::MessageKind::Text(b"done"),
+    )?;
+    writer.write_all(b"hello")?;
+    let reader = writer.into_read()?;
+    let cookie_header = cookie_header(server.received_as_string());
+    assert!(
+        cookie_header.contains("from-file=yes") && cookie_header.contains("from-server=yes"),
+        "the cookie set by the server is sent along with the one from the file: {cookie_header:?}"
+    );
+    drop(reader);
+
+    let saved_cookies = std::fs::read_to_string(&cookie_file)?;
+    assert!(
+        saved_cookies.contains("\tfrom-file\tyes") && saved_cookies.contains("\tfrom-server\tyes"),
+        "with `save_cookies`, received cookies are written to the cookie file after each request: {saved_cookies:?}"
+    );
+    Ok(())
+}
+
+mod low_speed {
+    use std::{io::Read, time::Duration};
+
+    use gix_transport::client::http::low_speed;
+
+    /// A reader which produces a single byte per `delay`.
+    struct Throttled {
+        remaining: usize,
+        delay: Duration,
+    }
+
+    impl Read for Throttled {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.remaining == 0 || buf.is_empty() {
+                return Ok(0);
+            }
+            std::thread::sleep(self.delay);
+            buf[0] = b'x';
+            self.remaining -= 1;
+            Ok(1)
+        }
+    }
+
+    #[test]
+    fn transfers_below_the_limit_for_the_configured_time_are_aborted() {
+        let throttled = Throttled {
+            remaining: 100,
+            delay: Duration::from_millis(5),
+        };
+        let mut reader = low_speed::Reader::new(throttled, 1_000_000, Duration::from_millis(20));
+        let err = reader
+            .read_to_end(&mut Vec::new())
+            .expect_err("far too slow for the limit");
+        assert_eq!(err.kind(), std::io::ErrorKind::TimedOut);
+        let err = err
+            .get_ref()
+            .and_then(|err| err.downcast_ref::<low_speed::Error>())
+            .expect("the source is the low-speed error");
+        assert_eq!(err.limit_bytes_per_second, 1_000_000);
+        assert_eq!(err.time, Duration::from_millis(20));
+    }
+
+    #[test]
+    fn stalled_transfers_are_aborted_even_if_no_data_arrives() {
+        let never_read = Throttled {
+            remaining: 1,
+            delay: Duration::from_secs(3600),
+        };
+        let mut reader = low_speed::Reader::new(never_read, 1, Duration::from_millis(20));
+        let (send, recv) = std::sync::mpsc::channel();
+        reader.abort_stalls_with(move |err| {
+            send.send(err).ok();
+        });
+        let err = recv
+            .recv_timeout(Duration::from_secs(10))
+            .expect("the watchdog notices that nothing was read at all");
+        assert_eq!(err.kind(), std::io::ErrorKind::TimedOut);
+        assert!(err
+            .get_ref()
+            .and_then(|err| err.downcast_ref::<low_speed::Error>())
+            .is_some());
+        assert!(
+            recv.recv_timeout(Duration::from_millis(100)).is_err(),
+            "stalls are reported only once"
+        );
+    }
+
+    #[test]
+    fn dropping_the_reader_stops_the_watchdog() {
+        let mut reader = low_speed::Reader::new(&b"data"[..], 1, Duration::from_millis(20));
+        let (send, recv) = std::sync::mpsc::channel::<std::io::Error>();
+        reader.abort_stalls_with(move |err| {
+            send.send(err).ok();
+        });
+        drop(reader);
+        assert!(
+            matches!(
+                recv.recv_timeout(Duration::from_secs(10)),
+                Err(std::sync::mpsc::RecvTimeoutError::Disconnected)
+            ),
+            "the watchdog thread ends without reporting a stall"
+        );
+    }
+
+    #[test]
+    fn transfers_above_the_limit_or_without_limit_complete() -> std::io::Result<()> {
+        let mut buf = Vec::new();
+        let mut reader = low_speed::Reader::new(&[42u8; 4096][..], 1, Duration::from_millis(1));
+        reader.read_to_end(&mut buf)?;
+        assert_eq!(buf.len(), 4096);
+
+        let throttled = Throttled {
+            remaining: 10,
+            delay: Duration::from_millis(5),
+        };
+        buf.clear();
+        let mut reader = low_speed::Reader::new(throttled, 0, Duration::from_millis(1));
+        reader.read_to_end(&mut buf)?;
+        assert_eq!(buf.len(), 10, "a limit of 0 disables the check");
+        Ok(())
+    }
+}
@@ -49,6 +49,14 @@ pub enum Error {
         url: gix_url::Url,
         source: gix_path::realpath::Error,
     },
+    #[error("The template directory configured in init.templateDir could not be interpolated")]
+    TemplateDirInterpolate(#[source] gix_config::path::interpolate::Error),
+    #[error("Failed to copy template content from \"{}\" to \"{}\"", from.display(), to.display())]
+    CopyTemplate {
+        from: std::path::PathBuf,
+        to: std::path::PathBuf,
+        source: std::io::Error,
+    },
 }
 
 /// Instantiation
@@ -59,10 +67,15 @@ impl PrepareFetch {
     /// Note that this is merely a handle to perform the actual connection to the remote, and if any of it fails the freshly initialized repository
     /// will be removed automatically as soon as this instance drops.
     ///
+    /// If `init.templateDir` is configured, the contents of the template directory are copied into the new `.git` directory,
+    /// overwriting the files written during initialization.
+    ///
     /// # Deviation
     ///
     /// Similar to `git`, a missing user name and email configuration is not terminal and we will fill it in with dummy values. However,
     /// instead of deriving values from the system, ours are hardcoded to indicate what happened.
+    ///
+    /// A `config` file in the template directory is ignored, whereas `git` would use it as basis for the repository configuration.
     #[allow(clippy::result_large_err)]
     pub fn new<Url, E>(
         url: Url,
@@ -82,6 +95,17 @@ impl PrepareFetch {
         })?;
         create_opts.destination_must_be_empty = true;
         let mut repo = crate::ThreadSafeRepository::init_opts(path, kind, create_opts, open_opts)?.to_thread_local();
+        if let Some(template_dir) = repo
+            .config
+            .apply_leniency(repo.config.template_dir())
+            .map_err(Error::TemplateDirInterpolate)?
+        {
+            if template_dir.is_dir() {
+                copy_template(&template_dir, repo.git_dir(), true)?;
+            } else {
+                log::warn!("templates not found in {}", template_dir.display());
+            }
+        }
         if repo.committer().is_none() {
             let mut config = gix_config::File::new(gix_config::file::Metadata::api());
             config
@@ -118,6 +142,30 @@ impl PrepareFetch {
     }
 }
 
+/// Recursively copy all files and directories in `from` into `to`, overwriting existing files.
+/// If `is_root`, the `config` file is skipped.
+fn copy_template(from: &std::path::Path, to: &std::path::Path, is_root: bool) -> Result<(), Error> {
+    let copy_err = |from: &std::path::Path, to: &std::path::Path| {
+        let (from, to) = (from.to_owned(), to.to_owned());
+        move |source: std::io::Error| Error::CopyTemplate { from, to, source }
+    };
+    for entry in std::fs::read_dir(from).map_err(copy_err(from, to))? {
+        let entry = entry.map_err(copy_err(from, to))?;
+        let file_name = entry.file_name();
+        if is_root && file_name == "config" {
+            continue;
+        }
+        let (src, dst) = (entry.path(), to.join(file_name));
+        if entry.file_type().map_err(copy_err(&src, &dst))?.is_dir() {
+            std::fs::create_dir_all(&dst).map_err(copy_err(&src, &dst))?;
+            copy_template(&src, &dst, false)?;
+        } else {
+            std::fs::copy(&src, &dst).map_err(copy_err(&src, &dst))?;
+        }
+    }
+    Ok(())
+}
+
 /// A utility to collect configuration on how to perform a checkout into a working tree, and when dropped without checking out successfully
 /// the fetched repository will be dropped.
 #[must_use]
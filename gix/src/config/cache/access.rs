@@ -1,5 +1,9 @@
 #![allow(clippy::result_large_err)]
-use std::{borrow::Cow, path::PathBuf, time::Duration};
+use std::{
+    borrow::Cow,
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
 use gix_attributes::Source;
 use gix_lock::acquire::Fail;
@@ -10,7 +14,7 @@ use crate::{
     config::{
         cache::util::{ApplyLeniency, ApplyLeniencyDefault, ApplyLeniencyDefaultValue},
         checkout_options,
-        tree::{gitoxide, Checkout, Core, Key},
+        tree::{gitoxide, Checkout, Core, Init, Key, Remote},
         Cache,
     },
     filter, remote,
@@ -59,6 +63,28 @@ impl Cache {
             .get_or_init(|| identity::Personas::from_config_and_env(&self.resolved))
     }
 
+    /// Return `true` if at least one remote has `remote.<name>.promisor=true`, treating invalid values as `false`.
+    pub(crate) fn is_promisor(&self) -> bool {
+        *self.is_promisor.get_or_init(|| {
+            let mut filter = self.filter_config_section;
+            let mut names: Vec<_> = self
+                .resolved
+                .sections_by_name_and_filter("remote", &mut filter)
+                .into_iter()
+                .flatten()
+                .filter_map(|section| section.header().subsection_name())
+                .collect();
+            names.sort();
+            names.dedup();
+            names.into_iter().any(|name| {
+                let mut filter = self.filter_config_section;
+                self.resolved
+                    .boolean_filter("remote", Some(name), Remote::PROMISOR.name, &mut filter)
+                    .map_or(false, |res| res.unwrap_or(false))
+            })
+        })
+    }
+
     pub(crate) fn url_rewrite(&self) -> &remote::url::Rewrite {
         self.url_rewrite
             .get_or_init(|| remote::url::Rewrite::from_config(&self.resolved, self.filter_config_section))
@@ -82,6 +108,70 @@ impl Cache {
             .unwrap_or(Ok(DEFAULT))
     }
 
+    /// Returns `true` if the advice controlled by `key`, one of the keys of the `advice` section, should be shown,
+    /// which is the default.
+    pub(crate) fn advice(&self, key: &config::tree::keys::Boolean) -> Result<bool, config::boolean::Error> {
+        const DEFAULT: bool = true;
+        self.resolved
+            .boolean_filter_by_key(key.logical_name().as_str(), &mut self.filter_config_section.clone())
+            .map(|res| {
+                key.enrich_error(res)
+                    .with_lenient_default_value(self.lenient_config, DEFAULT)
+            })
+            .unwrap_or(Ok(DEFAULT))
+    }
+
+    /// Returns how to treat the untracked cache extension of the index, as configured by `core.untrackedCache`.
+    pub(crate) fn untracked_cache(
+        &self,
+    ) -> Result<config::tree::core::UntrackedCacheMode, config::key::GenericErrorWithValue> {
+        self.resolved
+            .string_by_key(Core::UNTRACKED_CACHE.logical_name().as_str())
+            .map(|value| Core::UNTRACKED_CACHE.try_into_untracked_cache(value))
+            .transpose()
+            .with_leniency(self.lenient_config)
+            .map(Option::unwrap_or_default)
+    }
+
+    /// Returns true if `core.splitIndex` indicates that a split index should be written.
+    pub(crate) fn split_index(&self) -> Result<bool, config::boolean::Error> {
+        const DEFAULT: bool = false;
+        self.resolved
+            .boolean_by_key(Core::SPLIT_INDEX.logical_name().as_str())
+            .map(|res| {
+                Core::SPLIT_INDEX
+                    .enrich_error(res)
+                    .with_lenient_default_value(self.lenient_config, DEFAULT)
+            })
+            .unwrap_or(Ok(DEFAULT))
+    }
+
+    /// Returns `true` if `core.sparseCheckout` indicates that the sparse-checkout file should be consulted.
+    pub(crate) fn sparse_checkout(&self) -> Result<bool, config::boolean::Error> {
+        const DEFAULT: bool = false;
+        self.resolved
+            .boolean_by_key(Core::SPARSE_CHECKOUT.logical_name().as_str())
+            .map(|res| {
+                Core::SPARSE_CHECKOUT
+                    .enrich_error(res)
+                    .with_lenient_default_value(self.lenient_config, DEFAULT)
+            })
+            .unwrap_or(Ok(DEFAULT))
+    }
+
+    /// Returns `true` if `core.sparseCheckoutCone` indicates that the sparse-checkout file uses cone mode.
+    pub(crate) fn sparse_checkout_cone(&self) -> Result<bool, config::boolean::Error> {
+        const DEFAULT: bool = false;
+        self.resolved
+            .boolean_by_key(Core::SPARSE_CHECKOUT_CONE.logical_name().as_str())
+            .map(|res| {
+                Core::SPARSE_CHECKOUT_CONE
+                    .enrich_error(res)
+                    .with_lenient_default_value(self.lenient_config, DEFAULT)
+            })
+            .unwrap_or(Ok(DEFAULT))
+    }
+
     pub(crate) fn diff_renames(
         &self,
     ) -> Result<Option<crate::object::tree::diff::Rewrites>, crate::object::tree::diff::rewrites::Error> {
@@ -119,6 +209,33 @@ impl Cache {
             .into()
     }
 
+    /// The directory containing hooks as configured by `core.hooksPath`, or `<git_dir>/hooks` if unset.
+    ///
+    /// Like `git` which runs hooks from the root of the worktree, relative paths are resolved against `work_dir`, or
+    /// against `git_dir` if there is no worktree like in bare repositories.
+    pub(crate) fn hooks_path(
+        &self,
+        git_dir: &Path,
+        work_dir: Option<&Path>,
+    ) -> Result<PathBuf, gix_config::path::interpolate::Error> {
+        let path = match self.trusted_file_path("core", None, Core::HOOKS_PATH.name) {
+            Some(path) => path?,
+            None => return Ok(git_dir.join("hooks")),
+        };
+        Ok(if path.is_absolute() {
+            path.into_owned()
+        } else {
+            work_dir.unwrap_or(git_dir).join(path)
+        })
+    }
+
+    /// The path to the directory whose contents are copied into newly created repositories.
+    pub(crate) fn template_dir(&self) -> Option<Result<PathBuf, gix_config::path::interpolate::Error>> {
+        self.trusted_file_path("init", None, Init::TEMPLATE_DIR.name)?
+            .map(std::borrow::Cow::into_owned)
+            .into()
+    }
+
     /// A helper to obtain a file from trusted configuration at `section_name`, `subsection_name`, and `key`, which is interpolated
     /// if present.
     pub(crate) fn trusted_file_path(
@@ -152,22 +269,6 @@ impl Cache {
         repo: &Repository,
         attributes_source: gix_worktree::cache::state::attributes::Source,
     ) -> Result<gix_worktree::checkout::Options, checkout_options::Error> {
-        fn boolean(
-            me: &Cache,
-            full_key: &str,
-            key: &'static config::tree::keys::Boolean,
-            default: bool,
-        ) -> Result<bool, checkout_options::Error> {
-            debug_assert_eq!(
-                full_key,
-                key.logical_name(),
-                "BUG: key name and hardcoded name must match"
-            );
-            Ok(me
-                .apply_leniency(me.resolved.boolean_by_key(full_key).map(|v| key.enrich_error(v)))?
-                .unwrap_or(default))
-        }
-
         let git_dir = repo.git_dir();
         let thread_limit = self.apply_leniency(
             self.resolved
@@ -211,18 +312,25 @@ impl Cache {
             destination_is_initially_empty: false,
             overwrite_existing: false,
             keep_going: false,
-            stat_options: gix_index::entry::stat::Options {
-                trust_ctime: boolean(self, "core.trustCTime", &Core::TRUST_C_TIME, true)?,
-                use_nsec: boolean(self, "gitoxide.core.useNsec", &gitoxide::Core::USE_NSEC, false)?,
-                use_stdev: boolean(self, "gitoxide.core.useStdev", &gitoxide::Core::USE_STDEV, false)?,
-                check_stat: self
-                    .apply_leniency(
-                        self.resolved
-                            .string("core", None, "checkStat")
-                            .map(|v| Core::CHECK_STAT.try_into_checkstat(v)),
-                    )?
-                    .unwrap_or(true),
-            },
+            stat_options: self.stat_options()?,
+        })
+    }
+
+    /// Return the options to use when comparing the stat information of index entries with the one of files in the worktree.
+    ///
+    /// `core.checkStat=minimal` causes all stat fields but the size and modification times to be ignored.
+    pub(crate) fn stat_options(&self) -> Result<gix_index::entry::stat::Options, config::stat_options::Error> {
+        Ok(gix_index::entry::stat::Options {
+            trust_ctime: boolean(self, "core.trustCTime", &Core::TRUST_C_TIME, true)?,
+            use_nsec: boolean(self, "gitoxide.core.useNsec", &gitoxide::Core::USE_NSEC, false)?,
+            use_stdev: boolean(self, "gitoxide.core.useStdev", &gitoxide::Core::USE_STDEV, false)?,
+            check_stat: self
+                .apply_leniency(
+                    self.resolved
+                        .string("core", None, "checkStat")
+                        .map(|v| Core::CHECK_STAT.try_into_checkstat(v)),
+                )?
+                .unwrap_or(true),
         })
     }
 
@@ -313,7 +421,26 @@ impl Cache {
     ///
     /// We never fail for here even if the permission is set to deny as we `gix-config` will fail later
     /// if it actually wants to use the home directory - we don't want to fail prematurely.
+    /// Symlinks in the home directory are resolved, just like when the configuration was loaded.
     pub(crate) fn home_dir(&self) -> Option<PathBuf> {
-        gix_path::env::home_dir().and_then(|path| self.environment.home.check_opt(path))
+        gix_path::env::home_dir()
+            .and_then(|path| self.environment.home.check_opt(path))
+            .map(config::cache::util::canonicalize_home)
     }
 }
+
+fn boolean(
+    me: &Cache,
+    full_key: &str,
+    key: &'static config::tree::keys::Boolean,
+    default: bool,
+) -> Result<bool, config::boolean::Error> {
+    debug_assert_eq!(
+        full_key,
+        key.logical_name(),
+        "BUG: key name and hardcoded name must match"
+    );
+    Ok(me
+        .apply_leniency(me.resolved.boolean_by_key(full_key).map(|v| key.enrich_error(v)))?
+        .unwrap_or(default))
+}
@@ -11,7 +11,8 @@ pub(crate) struct StageOne {
     pub is_bare: bool,
     pub lossy: Option<bool>,
     pub object_hash: gix_hash::Kind,
-    pub reflog: Option<gix_ref::store::WriteReflog>,
+    /// The value of `core.logAllRefUpdates`, or git's default for bare or non-bare repositories if it's unset.
+    pub reflog: gix_ref::store::WriteReflog,
 }
 
 /// Initialization
@@ -41,15 +42,29 @@ impl StageOne {
             .map(|version| Core::REPOSITORY_FORMAT_VERSION.try_into_usize(version))
             .transpose()?
             .unwrap_or_default();
-        let object_hash = (repo_format_version != 1)
-            .then_some(Ok(gix_hash::Kind::Sha1))
-            .or_else(|| {
-                config
-                    .string("extensions", None, "objectFormat")
-                    .map(|format| Extensions::OBJECT_FORMAT.try_into_object_format(format))
-            })
-            .transpose()?
-            .unwrap_or(gix_hash::Kind::Sha1);
+        if repo_format_version > 1 {
+            return Err(Error::UnsupportedRepositoryFormatVersion {
+                version: repo_format_version,
+            });
+        }
+        let object_hash = match config.string("extensions", None, Extensions::OBJECT_FORMAT.name) {
+            // Like git, refuse to interpret a repository whose object format is declared without the extension
+            // being available in its format version, as objects would be read with the wrong hash otherwise.
+            Some(format) if repo_format_version != 1 => {
+                return Err(Error::ExtensionRequiresRepositoryFormatVersion1 {
+                    version: repo_format_version,
+                    extension: "extensions.objectFormat",
+                    value: format.into_owned(),
+                })
+            }
+            Some(format) if format.as_ref().eq_ignore_ascii_case(b"sha256") => {
+                return Err(Error::UnsupportedObjectFormat {
+                    name: format.into_owned(),
+                })
+            }
+            Some(format) => Extensions::OBJECT_FORMAT.try_into_object_format(format)?,
+            None => gix_hash::Kind::Sha1,
+        };
 
         let extension_worktree = util::config_bool(
             &config,
@@ -67,10 +82,13 @@ impl StageOne {
                 lossy,
                 lenient,
             )?;
+            // Note that `core.bare` was already determined by the shared configuration above, and `core.worktree`
+            // is ignored in `config.worktree` when resolving the worktree, so a single worktree can't override them.
+            // They are kept nonetheless so they are retained when writing the configuration back.
             config.append(worktree_config);
         };
 
-        let reflog = util::query_refupdates(&config, lenient)?;
+        let reflog = util::reflog_or_default(util::query_refupdates(&config, lenient)?, !is_bare);
         Ok(StageOne {
             git_dir_config: config,
             buf,
@@ -54,6 +54,8 @@ impl Cache {
         api_config_overrides: &[BString],
         cli_config_overrides: &[BString],
     ) -> Result<Self, Error> {
+        let home = home.map(|home| util::canonicalize_home(home.to_owned()));
+        let home = home.as_deref();
         let options = gix_config::file::init::Options {
             includes: if use_includes {
                 gix_config::file::includes::Options::follow(
@@ -153,6 +155,7 @@ impl Cache {
         let object_kind_hint = util::disambiguate_hint(&config, lenient_config)?;
         let (static_pack_cache_limit_bytes, pack_cache_bytes, object_cache_bytes) =
             util::parse_object_caches(&config, lenient_config, filter_config_section)?;
+        let ssh_command = util::ssh_command(&config, filter_config_section);
         // NOTE: When adding a new initial cache, consider adjusting `reread_values_and_clear_caches()` as well.
         Ok(Cache {
             resolved: config.into(),
@@ -162,6 +165,7 @@ impl Cache {
             static_pack_cache_limit_bytes,
             pack_cache_bytes,
             object_cache_bytes,
+            ssh_command,
             reflog,
             is_bare,
             ignore_case,
@@ -177,6 +181,7 @@ impl Cache {
             #[cfg(any(feature = "blocking-network-client", feature = "async-network-client"))]
             url_scheme: Default::default(),
             diff_algorithm: Default::default(),
+            is_promisor: Default::default(),
         })
     }
 
@@ -212,17 +217,20 @@ impl Cache {
         )?;
         let object_kind_hint = util::disambiguate_hint(config, self.lenient_config)?;
         let reflog = util::query_refupdates(config, self.lenient_config)?;
+        let ssh_command = util::ssh_command(config, self.filter_config_section);
 
         self.hex_len = hex_len;
         self.ignore_case = ignore_case;
         self.object_kind_hint = object_kind_hint;
         self.reflog = reflog;
+        self.ssh_command = ssh_command;
 
         self.user_agent = Default::default();
         self.personas = Default::default();
         self.url_rewrite = Default::default();
         self.diff_renames = Default::default();
         self.diff_algorithm = Default::default();
+        self.is_promisor = Default::default();
         (
             self.static_pack_cache_limit_bytes,
             self.pack_cache_bytes,
@@ -308,6 +316,10 @@ fn apply_environment_overrides(
                     let key = &Http::SSL_CA_INFO;
                     (env(key), key.name)
                 },
+                {
+                    let key = &Http::SSL_CA_PATH;
+                    (env(key), key.name)
+                },
                 {
                     let key = &Http::SSL_VERSION;
                     (env(key), key.name)
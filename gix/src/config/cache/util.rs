@@ -1,6 +1,9 @@
 #![allow(clippy::result_large_err)]
+use std::borrow::Cow;
+
 use super::Error;
 use crate::{
+    bstr::{BString, ByteSlice},
     config,
     config::tree::{gitoxide, Core},
     revision::spec::parse::ObjectKindHint,
@@ -17,6 +20,24 @@ pub(crate) fn interpolate_context<'a>(
     }
 }
 
+/// Resolve all symlinks in `home` to match git, which uses the real path of the home directory to expand `~`.
+/// Fall back to `home` as is if this isn't possible, for instance due to insufficient permissions.
+pub(crate) fn canonicalize_home(home: std::path::PathBuf) -> std::path::PathBuf {
+    gix_path::realpath(&home).unwrap_or(home)
+}
+
+/// Return the value of `core.sshCommand`, which includes `GIT_SSH_COMMAND` if environment overrides were applied,
+/// or `None` if it is unset or empty.
+pub(crate) fn ssh_command(
+    config: &gix_config::File<'static>,
+    mut filter_config_section: fn(&gix_config::file::Metadata) -> bool,
+) -> Option<BString> {
+    config
+        .string_filter_by_key("core.sshCommand", &mut filter_config_section)
+        .filter(|cmd| !cmd.trim().is_empty())
+        .map(Cow::into_owned)
+}
+
 pub(crate) fn base_options(lossy: Option<bool>, lenient: bool) -> gix_config::file::init::Options<'static> {
     gix_config::file::init::Options {
         lossy: lossy.unwrap_or(!cfg!(debug_assertions)),
@@ -39,7 +60,7 @@ pub(crate) fn config_bool(
         "BUG: key name and hardcoded name must match"
     );
     config
-        .boolean_by_key(key_str)
+        .boolean_single_by_key(key_str, true /* warn if multivar */)
         .map_or(Ok(default), |res| key.enrich_error(res))
         .map_err(Error::from)
         .with_lenient_default(lenient)
@@ -77,6 +77,14 @@ pub enum Error {
     ConfigTypedString(#[from] key::GenericErrorWithValue),
     #[error("Cannot handle objects formatted as {:?}", .name)]
     UnsupportedObjectFormat { name: BString },
+    #[error("Cannot handle repositories with format version {version}, only 0 and 1 are supported")]
+    UnsupportedRepositoryFormatVersion { version: usize },
+    #[error("Repository format version {version} doesn't support {extension}={value:?}, which requires version 1")]
+    ExtensionRequiresRepositoryFormatVersion1 {
+        version: usize,
+        extension: &'static str,
+        value: BString,
+    },
     #[error(transparent)]
     CoreAbbrev(#[from] abbrev::Error),
     #[error("Could not read configuration file at \"{}\"", path.display())]
@@ -134,6 +142,21 @@ pub mod checkout_options {
         Attributes(#[from] super::attribute_stack::Error),
         #[error(transparent)]
         FilterPipelineOptions(#[from] crate::filter::pipeline::options::Error),
+        #[error(transparent)]
+        StatOptions(#[from] super::stat_options::Error),
+    }
+}
+
+///
+pub mod stat_options {
+    /// The error produced when obtaining the options for comparing index entries with files in the worktree.
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        ConfigCheckStat(#[from] super::key::GenericErrorWithValue),
+        #[error(transparent)]
+        ConfigBoolean(#[from] super::boolean::Error),
     }
 }
 
@@ -501,6 +524,10 @@ pub(crate) struct Cache {
     pub(crate) url_scheme: OnceCell<crate::remote::url::SchemePermission>,
     /// The algorithm to use when diffing blobs
     pub(crate) diff_algorithm: OnceCell<gix_diff::blob::Algorithm>,
+    /// Whether any remote is configured as promisor remote, lazily computed.
+    pub(crate) is_promisor: OnceCell<bool>,
+    /// The command to use when connecting via `ssh`, from `core.sshCommand` or `GIT_SSH_COMMAND`, or `None` if unset or empty.
+    pub(crate) ssh_command: Option<BString>,
     /// The amount of bytes to use for a memory backed delta pack cache. If `Some(0)`, no cache is used, if `None`
     /// a standard cache is used which costs near to nothing and always pays for itself.
     pub(crate) pack_cache_bytes: Option<usize>,
@@ -16,6 +16,8 @@ pub(crate) mod root {
     pub struct Tree;
 
     impl Tree {
+        /// The `advice` section.
+        pub const ADVICE: sections::Advice = sections::Advice;
         /// The `author` section.
         pub const AUTHOR: sections::Author = sections::Author;
         /// The `branch` section.
@@ -54,6 +56,8 @@ pub(crate) mod root {
         pub const SAFE: sections::Safe = sections::Safe;
         /// The `ssh` section.
         pub const SSH: sections::Ssh = sections::Ssh;
+        /// The `transfer` section.
+        pub const TRANSFER: sections::Transfer = sections::Transfer;
         /// The `user` section.
         pub const USER: sections::User = sections::User;
         /// The `url` section.
@@ -62,6 +66,7 @@ pub(crate) mod root {
         /// List all available sections.
         pub fn sections(&self) -> &[&dyn Section] {
             &[
+                &Self::ADVICE,
                 &Self::AUTHOR,
                 &Self::BRANCH,
                 &Self::CHECKOUT,
@@ -81,6 +86,7 @@ pub(crate) mod root {
                 &Self::REMOTE,
                 &Self::SAFE,
                 &Self::SSH,
+                &Self::TRANSFER,
                 &Self::USER,
                 &Self::URL,
             ]
@@ -90,9 +96,9 @@ pub(crate) mod root {
 
 mod sections;
 pub use sections::{
-    branch, checkout, core, credential, diff, extensions, fetch, gitoxide, http, index, protocol, remote, ssh, Author,
-    Branch, Checkout, Clone, Committer, Core, Credential, Diff, Extensions, Fetch, Gitoxide, Http, Index, Init, Pack,
-    Protocol, Remote, Safe, Ssh, Url, User,
+    branch, checkout, core, credential, diff, extensions, fetch, gitoxide, http, index, protocol, remote, ssh, Advice,
+    Author, Branch, Checkout, Clone, Committer, Core, Credential, Diff, Extensions, Fetch, Gitoxide, Http, Index, Init,
+    Pack, Protocol, Remote, Safe, Ssh, Transfer, Url, User,
 };
 
 /// Generic value implementations for static instantiation.
@@ -0,0 +1,27 @@
+use crate::{
+    config,
+    config::tree::{keys, Advice, Key, Section},
+};
+
+impl Advice {
+    /// The `advice.fetchRemovedDestinations` key.
+    pub const FETCH_REMOVED_DESTINATIONS: keys::Boolean =
+        keys::Boolean::new_boolean("fetchRemovedDestinations", &config::Tree::ADVICE).with_note(
+            "gitoxide-specific, controls the warning about fetch destinations removed for not starting with `refs/`",
+        );
+    /// The `advice.fetchFilteredTips` key.
+    pub const FETCH_FILTERED_TIPS: keys::Boolean =
+        keys::Boolean::new_boolean("fetchFilteredTips", &config::Tree::ADVICE).with_note(
+            "gitoxide-specific, controls the message about remote refs that didn't match any fetch ref-spec",
+        );
+}
+
+impl Section for Advice {
+    fn name(&self) -> &str {
+        "advice"
+    }
+
+    fn keys(&self) -> &[&dyn Key] {
+        &[&Self::FETCH_REMOVED_DESTINATIONS, &Self::FETCH_FILTERED_TIPS]
+    }
+}
@@ -54,8 +54,17 @@ impl Core {
     pub const ASKPASS: keys::Executable = keys::Executable::new_executable("askPass", &config::Tree::CORE)
         .with_environment_override("GIT_ASKPASS")
         .with_note("fallback is 'SSH_ASKPASS'");
+    /// The `core.alternateRefsCommand` key.
+    pub const ALTERNATE_REFS_COMMAND: keys::Program =
+        keys::Program::new_program("alternateRefsCommand", &config::Tree::CORE);
+    /// The `core.alternateRefsPrefixes` key.
+    pub const ALTERNATE_REFS_PREFIXES: keys::String =
+        keys::String::new_string("alternateRefsPrefixes", &config::Tree::CORE)
+            .with_note("ignored if `core.alternateRefsCommand` is set");
     /// The `core.excludesFile` key.
     pub const EXCLUDES_FILE: keys::Executable = keys::Executable::new_executable("excludesFile", &config::Tree::CORE);
+    /// The `core.hooksPath` key.
+    pub const HOOKS_PATH: keys::Path = keys::Path::new_path("hooksPath", &config::Tree::CORE);
     /// The `core.attributesFile` key.
     pub const ATTRIBUTES_FILE: keys::Executable =
         keys::Executable::new_executable("attributesFile", &config::Tree::CORE)
@@ -80,6 +89,20 @@ impl Core {
         &config::Tree::CORE,
         validate::CheckRoundTripEncoding,
     );
+    /// The `core.untrackedCache` key.
+    pub const UNTRACKED_CACHE: UntrackedCache =
+        UntrackedCache::new_with_validate("untrackedCache", &config::Tree::CORE, validate::UntrackedCache)
+            .with_note("the untracked cache extension isn't used or written yet");
+    /// The `core.splitIndex` key.
+    pub const SPLIT_INDEX: keys::Boolean = keys::Boolean::new_boolean("splitIndex", &config::Tree::CORE)
+        .with_deviation("split indices are read, but never written");
+    /// The `core.sparseCheckout` key.
+    pub const SPARSE_CHECKOUT: keys::Boolean = keys::Boolean::new_boolean("sparseCheckout", &config::Tree::CORE)
+        .with_note("the sparse-checkout file isn't applied during checkout yet");
+    /// The `core.sparseCheckoutCone` key.
+    pub const SPARSE_CHECKOUT_CONE: keys::Boolean =
+        keys::Boolean::new_boolean("sparseCheckoutCone", &config::Tree::CORE)
+            .with_note("only relevant if `core.sparseCheckout` is enabled");
 }
 
 impl Section for Core {
@@ -106,7 +129,10 @@ impl Section for Core {
             &Self::TRUST_C_TIME,
             &Self::WORKTREE,
             &Self::ASKPASS,
+            &Self::ALTERNATE_REFS_COMMAND,
+            &Self::ALTERNATE_REFS_PREFIXES,
             &Self::EXCLUDES_FILE,
+            &Self::HOOKS_PATH,
             &Self::ATTRIBUTES_FILE,
             &Self::SSH_COMMAND,
             &Self::USE_REPLACE_REFS,
@@ -115,6 +141,10 @@ impl Section for Core {
             &Self::AUTO_CRLF,
             &Self::EOL,
             &Self::CHECK_ROUND_TRIP_ENCODING,
+            &Self::UNTRACKED_CACHE,
+            &Self::SPLIT_INDEX,
+            &Self::SPARSE_CHECKOUT,
+            &Self::SPARSE_CHECKOUT_CONE,
         ]
     }
 }
@@ -143,6 +173,52 @@ pub type Eol = keys::Any<validate::Eol>;
 /// The `core.checkRoundTripEncoding` key.
 pub type CheckRoundTripEncoding = keys::Any<validate::CheckRoundTripEncoding>;
 
+/// The `core.untrackedCache` key.
+pub type UntrackedCache = keys::Any<validate::UntrackedCache>;
+
+/// The value of the `core.untrackedCache` key.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum UntrackedCacheMode {
+    /// Keep the untracked cache extension of the index if it is present, but don't add it if it isn't.
+    #[default]
+    Keep,
+    /// Add the untracked cache extension to the index and keep it updated.
+    Enabled,
+    /// Remove the untracked cache extension from the index.
+    Disabled,
+}
+
+mod untracked_cache {
+    use std::borrow::Cow;
+
+    use crate::{
+        bstr::BStr,
+        config,
+        config::tree::core::{UntrackedCache, UntrackedCacheMode},
+    };
+
+    impl UntrackedCache {
+        /// Convert `value` into the mode with which to treat the untracked cache extension of the index, which is
+        /// either `keep` or a boolean.
+        pub fn try_into_untracked_cache(
+            &'static self,
+            value: Cow<'_, BStr>,
+        ) -> Result<UntrackedCacheMode, config::key::GenericErrorWithValue> {
+            if value.as_ref() == "keep" {
+                return Ok(UntrackedCacheMode::Keep);
+            }
+            let value = gix_config::Boolean::try_from(value.as_ref()).map_err(|err| {
+                config::key::GenericErrorWithValue::from_value(self, value.into_owned()).with_source(err)
+            })?;
+            Ok(if value.into() {
+                UntrackedCacheMode::Enabled
+            } else {
+                UntrackedCacheMode::Disabled
+            })
+        }
+    }
+}
+
 mod check_round_trip_encoding {
     use crate::bstr::{BStr, ByteSlice};
     use crate::config;
@@ -481,6 +557,14 @@ mod validate {
         }
     }
 
+    pub struct UntrackedCache;
+    impl keys::Validate for UntrackedCache {
+        fn validate(&self, value: &BStr) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+            super::Core::UNTRACKED_CACHE.try_into_untracked_cache(value.into())?;
+            Ok(())
+        }
+    }
+
     pub struct CheckRoundTripEncoding;
     impl keys::Validate for CheckRoundTripEncoding {
         fn validate(&self, value: &BStr) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
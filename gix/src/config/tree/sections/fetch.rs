@@ -10,6 +10,29 @@ impl Fetch {
         &config::Tree::FETCH,
         validate::NegotiationAlgorithm,
     );
+    /// The `fetch.writeCommitGraph` key.
+    pub const WRITE_COMMIT_GRAPH: keys::Boolean = keys::Boolean::new_boolean("writeCommitGraph", &config::Tree::FETCH)
+        .with_deviation(
+            "rewrites an existing single commit-graph file instead of adding to a split commit-graph chain",
+        );
+    /// The `fetch.unpackLimit` key.
+    pub const UNPACK_LIMIT: keys::UnsignedInteger =
+        keys::UnsignedInteger::new_unsigned_integer("unpackLimit", &config::Tree::FETCH)
+            .with_note("falls back to `transfer.unpackLimit`");
+    /// The `fetch.prune` key.
+    pub const PRUNE: keys::Boolean = keys::Boolean::new_boolean("prune", &config::Tree::FETCH);
+    /// The `fetch.pruneTags` key.
+    pub const PRUNE_TAGS: keys::Boolean = keys::Boolean::new_boolean("pruneTags", &config::Tree::FETCH)
+        .with_note("only has an effect if pruning is enabled as well");
+    /// The `fetch.writeFetchHEAD` key.
+    pub const WRITE_FETCH_HEAD: keys::Boolean = keys::Boolean::new_boolean("writeFetchHEAD", &config::Tree::FETCH);
+    /// The `fetch.bundleURI` key.
+    pub const BUNDLE_URI: keys::String = keys::String::new_string("bundleURI", &config::Tree::FETCH)
+        .with_note("may point to a single bundle or to a bundle list");
+    /// The `fetch.bundleCreationToken` key.
+    pub const BUNDLE_CREATION_TOKEN: keys::UnsignedInteger =
+        keys::UnsignedInteger::new_unsigned_integer("bundleCreationToken", &config::Tree::FETCH)
+            .with_note("written after applying bundles of the list in `fetch.bundleURI`, which aren't applied again");
 }
 
 impl Section for Fetch {
@@ -18,7 +41,16 @@ impl Section for Fetch {
     }
 
     fn keys(&self) -> &[&dyn Key] {
-        &[&Self::NEGOTIATION_ALGORITHM]
+        &[
+            &Self::NEGOTIATION_ALGORITHM,
+            &Self::WRITE_COMMIT_GRAPH,
+            &Self::UNPACK_LIMIT,
+            &Self::PRUNE,
+            &Self::PRUNE_TAGS,
+            &Self::WRITE_FETCH_HEAD,
+            &Self::BUNDLE_URI,
+            &Self::BUNDLE_CREATION_TOKEN,
+        ]
     }
 }
 
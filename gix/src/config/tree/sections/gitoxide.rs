@@ -14,6 +14,8 @@ impl Gitoxide {
     pub const COMMIT: Commit = Commit;
     /// The `gitoxide.committer` section.
     pub const COMMITTER: Committer = Committer;
+    /// The `gitoxide.fetch` section.
+    pub const FETCH: Fetch = Fetch;
     /// The `gitoxide.http` section.
     pub const HTTP: Http = Http;
     /// The `gitoxide.https` section.
@@ -47,6 +49,7 @@ impl Section for Gitoxide {
             &Self::CORE,
             &Self::COMMIT,
             &Self::COMMITTER,
+            &Self::FETCH,
             &Self::HTTP,
             &Self::HTTPS,
             &Self::OBJECTS,
@@ -114,6 +117,32 @@ mod subsections {
         }
     }
 
+    /// The `fetch` sub-section.
+    #[derive(Copy, Clone, Default)]
+    pub struct Fetch;
+
+    impl Fetch {
+        /// The `gitoxide.fetch.maxRetries` key.
+        pub const MAX_RETRIES: keys::UnsignedInteger =
+            keys::UnsignedInteger::new_unsigned_integer("maxRetries", &Gitoxide::FETCH).with_note(
+                "entirely new, to retry listing refs up to this many times after transient network errors, with increasing delays",
+            );
+    }
+
+    impl Section for Fetch {
+        fn name(&self) -> &str {
+            "fetch"
+        }
+
+        fn keys(&self) -> &[&dyn Key] {
+            &[&Self::MAX_RETRIES]
+        }
+
+        fn parent(&self) -> Option<&dyn Section> {
+            Some(&Tree::GITOXIDE)
+        }
+    }
+
     /// The `Http` sub-section.
     #[derive(Copy, Clone, Default)]
     pub struct Http;
@@ -398,7 +427,7 @@ mod subsections {
         }
     }
 }
-pub use subsections::{Allow, Author, Commit, Committer, Core, Http, Https, Objects, Ssh, User};
+pub use subsections::{Allow, Author, Commit, Committer, Core, Fetch, Http, Https, Objects, Ssh, User};
 
 pub mod validate {
     use std::error::Error;
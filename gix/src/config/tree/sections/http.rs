@@ -45,9 +45,22 @@ impl Http {
     /// The `http.sslCAInfo` key.
     pub const SSL_CA_INFO: keys::Path =
         keys::Path::new_path("sslCAInfo", &config::Tree::HTTP).with_environment_override("GIT_SSL_CAINFO");
+    /// The `http.sslCAPath` key.
+    pub const SSL_CA_PATH: keys::Path = keys::Path::new_path("sslCAPath", &config::Tree::HTTP)
+        .with_environment_override("GIT_SSL_CAPATH")
+        .with_deviation("only supported by the curl backend");
+    /// The `http.sslVerify` key.
+    pub const SSL_VERIFY: keys::Boolean = keys::Boolean::new_boolean("sslVerify", &config::Tree::HTTP).with_deviation(
+        "only supported by the curl backend, and the GIT_SSL_NO_VERIFY environment variable isn't supported yet",
+    );
     /// The `http.schannelCheckRevoke` key.
     pub const SCHANNEL_CHECK_REVOKE: keys::Boolean =
         keys::Boolean::new_boolean("schannelCheckRevoke", &config::Tree::HTTP);
+    /// The `http.cookieFile` key.
+    pub const COOKIE_FILE: keys::Path =
+        keys::Path::new_path("cookieFile", &config::Tree::HTTP).with_deviation("only supported by the curl backend");
+    /// The `http.saveCookies` key.
+    pub const SAVE_COOKIES: keys::Boolean = keys::Boolean::new_boolean("saveCookies", &config::Tree::HTTP);
 }
 
 impl Section for Http {
@@ -68,7 +81,11 @@ impl Section for Http {
             &Self::LOW_SPEED_LIMIT,
             &Self::SCHANNEL_USE_SSL_CA_INFO,
             &Self::SSL_CA_INFO,
+            &Self::SSL_CA_PATH,
+            &Self::SSL_VERIFY,
             &Self::SCHANNEL_CHECK_REVOKE,
+            &Self::COOKIE_FILE,
+            &Self::SAVE_COOKIES,
         ]
     }
 }
@@ -1,5 +1,10 @@
 #![allow(missing_docs)]
 
+/// The `advice` top-level section.
+#[derive(Copy, Clone, Default)]
+pub struct Advice;
+mod advice;
+
 /// The `author` top-level section.
 #[derive(Copy, Clone, Default)]
 pub struct Author;
@@ -95,6 +100,11 @@ mod safe;
 pub struct Ssh;
 pub mod ssh;
 
+/// The `transfer` top-level section.
+#[derive(Copy, Clone, Default)]
+pub struct Transfer;
+mod transfer;
+
 /// The `user` top-level section.
 #[derive(Copy, Clone, Default)]
 pub struct User;
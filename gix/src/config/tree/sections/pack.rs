@@ -12,6 +12,16 @@ impl Pack {
     /// The `pack.indexVersion` key.
     pub const INDEX_VERSION: IndexVersion =
         IndexVersion::new_with_validate("indexVersion", &config::Tree::PACK, validate::IndexVersion);
+
+    /// The `pack.deltaCacheSize` key, the maximum amount of bytes to use for caching deltas when creating packs.
+    pub const DELTA_CACHE_SIZE: keys::UnsignedInteger =
+        keys::UnsignedInteger::new_unsigned_integer("deltaCacheSize", &config::Tree::PACK)
+            .with_note("packs received when fetching are streamed to disk and resolved without a delta cache, so it has no effect there");
+
+    /// The `pack.packSizeLimit` key, the maximum size in bytes of packs created when repacking.
+    pub const PACK_SIZE_LIMIT: keys::UnsignedInteger =
+        keys::UnsignedInteger::new_unsigned_integer("packSizeLimit", &config::Tree::PACK)
+            .with_note("like in git, packs received when fetching are written as they are and never split");
 }
 
 /// The `pack.indexVersion` key.
@@ -42,7 +52,12 @@ impl Section for Pack {
     }
 
     fn keys(&self) -> &[&dyn Key] {
-        &[&Self::THREADS, &Self::INDEX_VERSION]
+        &[
+            &Self::THREADS,
+            &Self::INDEX_VERSION,
+            &Self::DELTA_CACHE_SIZE,
+            &Self::PACK_SIZE_LIMIT,
+        ]
     }
 }
 
@@ -0,0 +1,25 @@
+use crate::{
+    config,
+    config::tree::{keys, Key, Section, Transfer},
+};
+
+impl Transfer {
+    /// The `transfer.unpackLimit` key.
+    pub const UNPACK_LIMIT: keys::UnsignedInteger =
+        keys::UnsignedInteger::new_unsigned_integer("unpackLimit", &config::Tree::TRANSFER).with_deviation(
+            "if unset, received packs are never unpacked into loose objects instead of defaulting to 100",
+        );
+    /// The `transfer.bundleURI` key.
+    pub const BUNDLE_URI: keys::Boolean = keys::Boolean::new_boolean("bundleURI", &config::Tree::TRANSFER)
+        .with_note("bundles advertised by the server are only downloaded if this is enabled");
+}
+
+impl Section for Transfer {
+    fn name(&self) -> &str {
+        "transfer"
+    }
+
+    fn keys(&self) -> &[&dyn Key] {
+        &[&Self::UNPACK_LIMIT, &Self::BUNDLE_URI]
+    }
+}
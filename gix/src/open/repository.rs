@@ -188,7 +188,7 @@ impl ThreadSafeRepository {
             lenient_config,
         )?;
         let mut refs = {
-            let reflog = repo_config.reflog.unwrap_or(gix_ref::store::WriteReflog::Disable);
+            let reflog = repo_config.reflog;
             let object_hash = repo_config.object_hash;
             match &common_dir {
                 Some(common_dir) => crate::RefStore::for_linked_worktree(&git_dir, common_dir, reflog, object_hash),
@@ -199,7 +199,7 @@ impl ThreadSafeRepository {
         let git_install_dir = crate::path::install_dir().ok();
         let home = gix_path::env::home_dir().and_then(|home| env.home.check_opt(home));
 
-        let mut filter_config_section = filter_config_section.unwrap_or(config::section::is_trusted);
+        let filter_config_section = filter_config_section.unwrap_or(config::section::is_trusted);
         let config = config::Cache::from_stage_one(
             repo_config,
             common_dir_ref,
@@ -219,11 +219,14 @@ impl ThreadSafeRepository {
             check_safe_directories(&git_dir, git_install_dir.as_deref(), home.as_deref(), &config)?;
         }
 
-        // core.worktree might be used to overwrite the worktree directory
+        // core.worktree might be used to overwrite the worktree directory, but not from `config.worktree` which only
+        // applies to a single worktree.
         if !config.is_bare {
             if let Some(wt) = config
                 .resolved
-                .path_filter("core", None, Core::WORKTREE.name, &mut filter_config_section)
+                .path_filter("core", None, Core::WORKTREE.name, &mut |meta| {
+                    meta.source != gix_config::Source::Worktree && filter_config_section(meta)
+                })
             {
                 let wt_path = wt
                     .interpolate(interpolate_context(git_install_dir.as_deref(), home.as_deref()))
@@ -0,0 +1,370 @@
+use std::{
+    convert::TryInto,
+    io::{self, BufRead, Read},
+    sync::atomic::AtomicBool,
+};
+
+use gix_odb::{Find, FindExt};
+use gix_protocol::bundle_uri::{List, Mode};
+use gix_ref::transaction::PreviousValue;
+
+use crate::{
+    bstr::{BStr, BString, ByteSlice},
+    remote::fetch::outcome,
+    Progress, Repository,
+};
+
+type BoxedError = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+/// Download the bundles of `list` and add their objects to the object database of `repo`, resolving relative bundle URIs
+/// against `base`.
+///
+/// Bundles are applied in order of their creation token if there is one, and with [`Mode::Any`] we stop after the first
+/// bundle could be applied. Bundles with a creation token not greater than `min_creation_token` were applied in a previous
+/// fetch and are skipped. Failing to download or apply a bundle isn't an error as the subsequent fetch will obtain the
+/// missing objects either way. Refs of bundles are only written if `update_refs` is `true`.
+pub(crate) fn apply_list(
+    repo: &Repository,
+    list: &List,
+    base: Option<&BStr>,
+    min_creation_token: Option<u64>,
+    update_refs: bool,
+    options: gix_pack::bundle::write::Options,
+    progress: &mut impl Progress,
+    should_interrupt: &AtomicBool,
+) -> Vec<outcome::Bundle> {
+    let mut bundles: Vec<_> = list
+        .bundles
+        .iter()
+        .filter(|bundle| match (bundle.creation_token, min_creation_token) {
+            (Some(token), Some(min)) => token > min,
+            _ => true,
+        })
+        .collect();
+    bundles.sort_by_key(|bundle| bundle.creation_token.unwrap_or(0));
+
+    let mut out = Vec::new();
+    for bundle in bundles {
+        let uri = resolve(bundle.uri.as_ref(), base);
+        match download(repo, uri.as_ref()).and_then(|mut download| {
+            apply(
+                repo,
+                uri.as_ref(),
+                &mut download.body,
+                update_refs,
+                options.clone(),
+                &mut *progress,
+                should_interrupt,
+            )
+        }) {
+            Ok(Some(mut applied)) => {
+                applied.creation_token = bundle.creation_token;
+                out.push(applied);
+                if list.mode == Mode::Any {
+                    break;
+                }
+            }
+            Ok(None) => {}
+            Err(err) => log::warn!("failed to apply bundle at {uri}, continuing without it: {err}"),
+        }
+    }
+    out
+}
+
+/// Apply the bundle or the bundle list found at `uri`, as configured in `fetch.bundleURI`.
+///
+/// Bundles of a list whose creation token isn't greater than `min_creation_token` are skipped, and the greatest creation token
+/// of all applied bundles is written to `fetch.bundleCreationToken` in the local configuration file to skip them in future.
+/// Neither the refs of bundles nor the creation token are written unless `update_refs` is `true`.
+pub(crate) fn apply_uri(
+    repo: &Repository,
+    uri: &BStr,
+    min_creation_token: Option<u64>,
+    update_refs: bool,
+    options: gix_pack::bundle::write::Options,
+    progress: &mut impl Progress,
+    should_interrupt: &AtomicBool,
+) -> Vec<outcome::Bundle> {
+    let mut download = match download(repo, uri) {
+        Ok(download) => download,
+        Err(err) => {
+            log::warn!("failed to download bundle list or bundle at {uri}, continuing without it: {err}");
+            return Vec::new();
+        }
+    };
+    let is_bundle = match download.body.fill_buf() {
+        Ok(buf) => buf.starts_with(b"# v2 git bundle") || buf.starts_with(b"# v3 git bundle"),
+        Err(err) => {
+            log::warn!("failed to read bundle list or bundle at {uri}, continuing without it: {err}");
+            return Vec::new();
+        }
+    };
+    if is_bundle {
+        return match apply(
+            repo,
+            uri,
+            &mut download.body,
+            update_refs,
+            options,
+            progress,
+            should_interrupt,
+        ) {
+            Ok(bundle) => bundle.into_iter().collect(),
+            Err(err) => {
+                log::warn!("failed to apply bundle at {uri}, continuing without it: {err}");
+                Vec::new()
+            }
+        };
+    }
+
+    let list = match read_list(&mut download.body) {
+        Ok(list) => list,
+        Err(err) => {
+            log::warn!("failed to read bundle list at {uri}, continuing without it: {err}");
+            return Vec::new();
+        }
+    };
+    drop(download);
+    let base = uri.rfind_byte(b'/').map(|pos| uri[..pos].as_bstr());
+    let bundles = apply_list(
+        repo,
+        &list,
+        base,
+        min_creation_token,
+        update_refs,
+        options,
+        progress,
+        should_interrupt,
+    );
+    if let Some(token) = bundles
+        .iter()
+        .filter_map(|bundle| bundle.creation_token)
+        .max()
+        .filter(|_| update_refs)
+    {
+        if let Err(err) = store_creation_token(repo, token) {
+            log::warn!("failed to store the creation token of bundles applied from {uri}: {err}");
+        }
+    }
+    bundles
+}
+
+/// Write `token` to `fetch.bundleCreationToken` in the local configuration file of `repo`.
+///
+/// Note that the configuration of `repo` isn't updated in memory.
+fn store_creation_token(repo: &Repository, token: u64) -> Result<(), BoxedError> {
+    let config_path = repo.common_dir().join("config");
+    let mut config = gix_config::File::from_path_no_includes(config_path.clone(), gix_config::Source::Local)?;
+    config
+        .section_mut_or_create_new("fetch", None)
+        .expect("section header name is always valid")
+        .set(
+            "bundleCreationToken".try_into().expect("valid at compile time"),
+            token.to_string().as_str(),
+        );
+
+    let mut lock =
+        gix_lock::File::acquire_to_update_resource(&config_path, gix_lock::acquire::Fail::Immediately, None)?;
+    config.write_to(&mut lock)?;
+    lock.commit().map_err(|err| err.error)?;
+    Ok(())
+}
+
+/// Parse a bundle list in `git-config` format, as obtained from `fetch.bundleURI`.
+fn read_list(read: &mut dyn BufRead) -> Result<List, BoxedError> {
+    let mut buf = Vec::new();
+    read.read_to_end(&mut buf)?;
+    let config = gix_config::File::from_bytes_no_includes(&buf, gix_config::file::Metadata::api(), Default::default())?;
+    let mut pairs = Vec::<(BString, BString)>::new();
+    for section in config.sections_by_name("bundle").into_iter().flatten() {
+        let prefix = match section.header().subsection_name() {
+            Some(id) => format!("bundle.{id}."),
+            None => "bundle.".into(),
+        };
+        for key in section.body().keys() {
+            if let Some(value) = section.body().value(key) {
+                pairs.push((format!("{prefix}{key}").into(), value.into_owned()));
+            }
+        }
+    }
+    Ok(List::from_key_value_pairs(
+        pairs.iter().map(|(key, value)| (key.as_bstr(), value.as_bstr())),
+    ))
+}
+
+/// Return `uri` if it's absolute, or `uri` relative to `base` otherwise.
+fn resolve(uri: &BStr, base: Option<&BStr>) -> BString {
+    match base {
+        Some(base) if !uri.contains_str("://") && !uri.starts_with(b"/") => {
+            let mut out: BString = base.trim_end_with(|c| c == '/').into();
+            out.push(b'/');
+            out.extend_from_slice(uri);
+            out
+        }
+        _ => uri.to_owned(),
+    }
+}
+
+/// The body of a download, keeping alive whatever is needed to read it.
+struct Download {
+    body: Box<dyn BufRead>,
+    /// The client that performs the download, which has to outlive the `body`.
+    _client: Option<Box<dyn std::any::Any>>,
+}
+
+fn download(repo: &Repository, uri: &BStr) -> Result<Download, BoxedError> {
+    let url = gix_url::parse(uri)?;
+    match url.scheme {
+        gix_url::Scheme::File => Ok(Download {
+            body: Box::new(io::BufReader::new(std::fs::File::open(gix_path::from_bstr(
+                url.path.as_bstr(),
+            ))?)),
+            _client: None,
+        }),
+        #[cfg(any(
+            feature = "blocking-http-transport-curl",
+            feature = "blocking-http-transport-reqwest"
+        ))]
+        gix_url::Scheme::Http | gix_url::Scheme::Https => {
+            use gix_protocol::transport::client::http::{self, Http};
+
+            let mut client = http::Impl::default();
+            if let Some(options) = repo.transport_options(uri, None)? {
+                client.configure(options.as_ref())?;
+            }
+            let url = uri.to_str()?;
+            let user_agent = repo.config.user_agent_tuple().1.unwrap_or_default();
+            let response = client.get(url, url, Some(format!("User-Agent: {user_agent}")))?;
+            Ok(Download {
+                body: Box::new(response.body),
+                _client: Some(Box::new(client)),
+            })
+        }
+        scheme => {
+            let _ = repo;
+            Err(format!("Bundles can't be obtained via the {scheme} protocol").into())
+        }
+    }
+}
+
+/// A parsed bundle header, which is followed by the pack.
+struct Header {
+    prerequisites: Vec<gix_hash::ObjectId>,
+    refs: Vec<(BString, gix_hash::ObjectId)>,
+}
+
+fn read_header(read: &mut dyn BufRead, object_hash: gix_hash::Kind) -> Result<Header, BoxedError> {
+    let mut line = Vec::new();
+    read.read_until(b'\n', &mut line)?;
+    let is_v3 = match line.trim_end() {
+        b"# v2 git bundle" => false,
+        b"# v3 git bundle" => true,
+        _ => return Err("Not a bundle in a supported format".into()),
+    };
+    let mut header = Header {
+        prerequisites: Vec::new(),
+        refs: Vec::new(),
+    };
+    loop {
+        line.clear();
+        if read.read_until(b'\n', &mut line)? == 0 {
+            return Err("Unexpected end of bundle header".into());
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if is_v3 && line.starts_with(b"@") {
+            if let Some(format) = line.strip_prefix(b"@object-format=") {
+                if !format.eq_ignore_ascii_case(object_hash.to_string().as_bytes()) {
+                    return Err(format!("Bundle uses object format {:?}", format.as_bstr()).into());
+                }
+            } else if line.starts_with(b"@filter") {
+                return Err("Filtered bundles are unsupported".into());
+            }
+            continue;
+        }
+        match line.strip_prefix(b"-") {
+            Some(prerequisite) => {
+                let hex = prerequisite.split(|b| *b == b' ').next().unwrap_or_default();
+                header.prerequisites.push(gix_hash::ObjectId::from_hex(hex)?);
+            }
+            None => {
+                let (hex, name) = line
+                    .split_once_str(" ")
+                    .ok_or_else(|| format!("Invalid reference line in bundle header: {:?}", line.as_bstr()))?;
+                header.refs.push((name.into(), gix_hash::ObjectId::from_hex(hex)?));
+            }
+        }
+    }
+    Ok(header)
+}
+
+/// Write the pack of the bundle in `read` obtained from `uri` into the object database and store its refs below
+/// `refs/bundles/` if `update_refs` is `true`, or return `None` if its prerequisites aren't present or if the objects
+/// of its refs are present already.
+///
+/// If refs aren't updated, the `.keep` file of the pack is retained as nothing refers to its objects.
+fn apply(
+    repo: &Repository,
+    uri: &BStr,
+    read: &mut dyn BufRead,
+    update_refs: bool,
+    options: gix_pack::bundle::write::Options,
+    progress: &mut impl Progress,
+    should_interrupt: &AtomicBool,
+) -> Result<Option<outcome::Bundle>, BoxedError> {
+    let header = read_header(read, repo.object_hash())?;
+    if let Some(missing) = header.prerequisites.iter().find(|id| !repo.objects.contains(id)) {
+        log::info!("skipping bundle at {uri} as its prerequisite {missing} is missing");
+        return Ok(None);
+    }
+    if !header.refs.is_empty() && header.refs.iter().all(|(_, id)| repo.objects.contains(id)) {
+        log::info!("skipping bundle at {uri} as the objects of all of its refs are present already");
+        return Ok(None);
+    }
+
+    progress.step();
+    progress.set_name(format!("apply bundle {uri}"));
+    let mut write_pack_bundle = gix_pack::Bundle::write_to_directory(
+        read,
+        Some(repo.objects.store_ref().path().join("pack")),
+        &mut *progress,
+        should_interrupt,
+        Some(Box::new({
+            let repo = repo.clone();
+            move |oid, buf| repo.objects.find(oid, buf).ok()
+        })),
+        options,
+    )?;
+    repo.objects
+        .store_ref()
+        .refresh_with_progress(&mut gix_features::progress::Discard)?;
+
+    let mut refs = Vec::with_capacity(header.refs.len());
+    for (name, id) in header.refs {
+        let name = match name.strip_prefix(b"refs/") {
+            Some(name) => format!("refs/bundles/{}", name.as_bstr()),
+            None => continue,
+        };
+        if !repo.objects.contains(id) {
+            continue;
+        }
+        let name: gix_ref::FullName = name.try_into()?;
+        if update_refs {
+            repo.reference(name.clone(), id, PreviousValue::Any, format!("bundle-uri: {uri}"))?;
+        }
+        refs.push((name, id));
+    }
+    if update_refs {
+        if let Some(path) = write_pack_bundle.keep_path.take() {
+            std::fs::remove_file(path)?;
+        }
+    }
+    Ok(Some(outcome::Bundle {
+        uri: uri.to_owned(),
+        write_pack_bundle,
+        refs,
+        creation_token: None,
+    }))
+}
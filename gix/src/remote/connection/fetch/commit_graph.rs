@@ -0,0 +1,189 @@
+use std::io::Write;
+
+use gix_commitgraph::file::write::Commit;
+use gix_odb::FindExt;
+
+use crate::Repository;
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum Error {
+    #[error(transparent)]
+    OpenReferences(#[from] crate::reference::iter::Error),
+    #[error(transparent)]
+    IterReferences(#[from] crate::reference::iter::init::Error),
+    #[error(transparent)]
+    PeelReference(#[from] Box<dyn std::error::Error + Send + Sync + 'static>),
+    #[error(transparent)]
+    FindCommit(#[from] gix_odb::find::existing_object::Error<gix_odb::store::find::Error>),
+    #[error(transparent)]
+    FindObject(#[from] crate::object::find::existing::Error),
+    #[error("Could not open an existing commit-graph file")]
+    OpenFile(#[from] gix_commitgraph::file::Error),
+    #[error("Could not open the commit-graph files to build upon")]
+    OpenGraph(#[from] gix_commitgraph::init::Error),
+    #[error("Could not read the parents of a commit in an existing commit-graph file")]
+    ReadParents(#[from] gix_commitgraph::file::commit::Error),
+    #[error("A commit-graph file refers to a parent at position {0} that doesn't exist")]
+    ParentOutOfRange(u32),
+    #[error(transparent)]
+    Write(#[from] gix_commitgraph::file::write::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Lock(#[from] gix_lock::acquire::Error),
+}
+
+/// Add all commits reachable from all references of `repo` that aren't yet part of its commit-graph to it.
+///
+/// If a single `objects/info/commit-graph` file exists, it's rewritten to also contain the new commits, with all known
+/// commits taken from it instead of from the object database. Otherwise a new layer with only the new commits is added
+/// to the split commit-graph chain in `objects/info/commit-graphs`, which is created if needed. Like `git`, layers at the
+/// top of the chain are merged into the new one unless they are at least twice as large, to keep the chain short.
+/// Nothing is written if there are no new commits.
+///
+/// Shallow repositories are skipped as they can't provide all parents.
+pub(crate) fn write(repo: &Repository) -> Result<(), Error> {
+    if repo.is_shallow() {
+        return Ok(());
+    }
+    let info_dir = repo.objects.store_ref().path().join("info");
+    let single_path = info_dir.join("commit-graph");
+    let graphs_dir = info_dir.join("commit-graphs");
+    let chain_path = graphs_dir.join("commit-graph-chain");
+
+    let is_single = single_path.is_file();
+    let mut layers = Vec::new();
+    if is_single {
+        layers.push(gix_commitgraph::File::at(&single_path)?);
+    } else if chain_path.is_file() {
+        for hash in std::fs::read_to_string(&chain_path)?.lines() {
+            layers.push(gix_commitgraph::File::at(
+                graphs_dir.join(format!("graph-{hash}.graph")),
+            )?);
+        }
+    }
+
+    let mut commits = new_commits(repo, &layers)?;
+    if commits.is_empty() {
+        return Ok(());
+    }
+
+    let mut num_layers_to_keep = layers.len();
+    if is_single {
+        num_layers_to_keep = 0;
+    } else {
+        let mut num_commits_to_write = commits.len();
+        while let Some(top) = num_layers_to_keep.checked_sub(1).map(|idx| &layers[idx]) {
+            let top_num_commits = top.num_commits() as usize;
+            if top_num_commits >= 2 * num_commits_to_write && num_layers_to_keep <= usize::from(u8::MAX) {
+                break;
+            }
+            num_commits_to_write += top_num_commits;
+            num_layers_to_keep -= 1;
+        }
+    }
+    for layer in &layers[num_layers_to_keep..] {
+        for commit in layer.iter_commits() {
+            let mut parents = Vec::new();
+            for pos in commit.iter_parents() {
+                let pos = pos?;
+                parents.push(id_at(&layers, pos).ok_or(Error::ParentOutOfRange(pos.0))?);
+            }
+            commits.push(Commit {
+                id: commit.id().to_owned(),
+                tree: commit.root_tree_id().to_owned(),
+                parents,
+                committer_timestamp: commit.committer_timestamp(),
+            });
+        }
+    }
+    let merged_layer_paths: Vec<_> = layers
+        .drain(num_layers_to_keep..)
+        .map(|layer| layer.path().to_owned())
+        .collect();
+
+    if is_single {
+        let mut lock =
+            gix_lock::File::acquire_to_update_resource(&single_path, gix_lock::acquire::Fail::Immediately, None)?;
+        gix_commitgraph::File::write_to(commits, &mut lock, repo.object_hash())?;
+        // All layers were dropped already, so the previous file isn't memory-mapped anymore while replacing it.
+        lock.commit().map_err(|err| err.error)?;
+        return Ok(());
+    }
+
+    let kept_hashes: Vec<_> = layers.iter().map(|layer| layer.checksum().to_owned()).collect();
+    let mut buf = Vec::new();
+    let outcome = if layers.is_empty() {
+        gix_commitgraph::File::write_to(commits, &mut buf, repo.object_hash())?
+    } else {
+        let base = gix_commitgraph::Graph::new(layers)?;
+        gix_commitgraph::File::write_to_with_base(commits, &base, &mut buf, repo.object_hash())?
+    };
+
+    std::fs::create_dir_all(&graphs_dir)?;
+    let new_layer_path = graphs_dir.join(format!("graph-{}.graph", outcome.checksum));
+    let mut lock =
+        gix_lock::File::acquire_to_update_resource(&new_layer_path, gix_lock::acquire::Fail::Immediately, None)?;
+    lock.write_all(&buf)?;
+    lock.commit().map_err(|err| err.error)?;
+
+    let mut lock = gix_lock::File::acquire_to_update_resource(&chain_path, gix_lock::acquire::Fail::Immediately, None)?;
+    for hash in kept_hashes.iter().chain(Some(&outcome.checksum)) {
+        writeln!(lock, "{hash}")?;
+    }
+    lock.commit().map_err(|err| err.error)?;
+
+    for path in merged_layer_paths {
+        if path != new_layer_path {
+            std::fs::remove_file(path).ok();
+        }
+    }
+    Ok(())
+}
+
+/// Collect all commits reachable from the references of `repo` that aren't contained in any of the commit-graph `layers`.
+fn new_commits(repo: &Repository, layers: &[gix_commitgraph::File]) -> Result<Vec<Commit>, Error> {
+    let is_known = |id: &gix_hash::ObjectId| layers.iter().any(|layer| layer.lookup(id).is_some());
+    let mut buf = Vec::new();
+    let mut tips = Vec::new();
+    for reference in repo.references()?.all()?.peeled() {
+        let reference = reference?;
+        let id = match reference.try_id() {
+            Some(id) => id.detach(),
+            None => continue,
+        };
+        if !is_known(&id) && repo.objects.find(id, &mut buf)?.kind == gix_object::Kind::Commit {
+            tips.push(id);
+        }
+    }
+
+    let mut seen = gix_hashtable::HashSet::default();
+    let mut commits = Vec::new();
+    while let Some(id) = tips.pop() {
+        if !seen.insert(id) || is_known(&id) {
+            continue;
+        }
+        let commit = repo.objects.find_commit(id, &mut buf)?;
+        let parents: Vec<_> = commit.parents().collect();
+        tips.extend(parents.iter().copied());
+        commits.push(Commit {
+            id,
+            tree: commit.tree(),
+            parents,
+            committer_timestamp: commit.committer.time.seconds.max(0) as u64,
+        });
+    }
+    Ok(commits)
+}
+
+/// Return the id at the graph-wide `pos` within the chain of `layers`.
+fn id_at(layers: &[gix_commitgraph::File], pos: gix_commitgraph::Position) -> Option<gix_hash::ObjectId> {
+    let mut pos = pos.0;
+    for layer in layers {
+        if pos < layer.num_commits() {
+            return Some(layer.id_at(gix_commitgraph::file::Position(pos)).to_owned());
+        }
+        pos -= layer.num_commits();
+    }
+    None
+}
@@ -1,6 +1,10 @@
 use super::Error;
 use crate::{
-    config::{cache::util::ApplyLeniency, tree::Pack},
+    bstr::BStr,
+    config::{
+        cache::util::ApplyLeniency,
+        tree::{keys, Fetch, Key, Pack, Remote, Transfer},
+    },
     Repository,
 };
 
@@ -24,3 +28,162 @@ pub fn pack_index_version(repo: &Repository) -> Result<gix_pack::index::Version,
         .with_leniency(repo.options.lenient_config)?
         .unwrap_or(gix_pack::index::Version::V2))
 }
+
+pub fn write_commit_graph(repo: &Repository) -> Result<bool, Error> {
+    repo.config
+        .resolved
+        .boolean_filter_by_key(
+            Fetch::WRITE_COMMIT_GRAPH.logical_name().as_str(),
+            &mut repo.filter_config_section(),
+        )
+        .map(|res| Fetch::WRITE_COMMIT_GRAPH.enrich_error(res))
+        .transpose()
+        .with_leniency(repo.options.lenient_config)
+        .map(|value| value.unwrap_or(false))
+        .map_err(Error::WriteCommitGraphConfig)
+}
+
+/// Return `true` if `FETCH_HEAD` should be written after fetching, which is the default.
+pub fn write_fetch_head(repo: &Repository) -> Result<bool, Error> {
+    repo.config
+        .resolved
+        .boolean_filter_by_key(
+            Fetch::WRITE_FETCH_HEAD.logical_name().as_str(),
+            &mut repo.filter_config_section(),
+        )
+        .map(|res| Fetch::WRITE_FETCH_HEAD.enrich_error(res))
+        .transpose()
+        .with_leniency(repo.options.lenient_config)
+        .map(|value| value.unwrap_or(true))
+        .map_err(Error::WriteFetchHeadConfig)
+}
+
+/// Return the amount of objects below which a received pack is unpacked into loose objects, if configured.
+pub fn unpack_limit(repo: &Repository) -> Result<Option<u32>, Error> {
+    for key in [&Fetch::UNPACK_LIMIT, &Transfer::UNPACK_LIMIT] {
+        let limit = repo
+            .config
+            .resolved
+            .integer_filter_by_key(key.logical_name().as_str(), &mut repo.filter_config_section())
+            .map(|value| key.try_into_u32(value))
+            .transpose()
+            .with_leniency(repo.options.lenient_config)
+            .map_err(Error::UnpackLimitConfig)?;
+        if limit.is_some() {
+            return Ok(limit);
+        }
+    }
+    Ok(None)
+}
+
+/// Return `true` if bundles advertised by the server should be downloaded before fetching.
+#[cfg(feature = "blocking-network-client")]
+pub fn transfer_bundle_uri(repo: &Repository) -> Result<bool, Error> {
+    repo.config
+        .resolved
+        .boolean_filter_by_key(
+            Transfer::BUNDLE_URI.logical_name().as_str(),
+            &mut repo.filter_config_section(),
+        )
+        .map(|res| Transfer::BUNDLE_URI.enrich_error(res))
+        .transpose()
+        .with_leniency(repo.options.lenient_config)
+        .map(|value| value.unwrap_or(false))
+        .map_err(Error::BundleUriConfig)
+}
+
+/// Return the location of a bundle or bundle list stored in `fetch.bundleURI`, if set.
+#[cfg(feature = "blocking-network-client")]
+pub fn fetch_bundle_uri(repo: &Repository) -> Option<crate::bstr::BString> {
+    use crate::bstr::ByteSlice;
+    repo.config
+        .resolved
+        .string_filter_by_key(
+            Fetch::BUNDLE_URI.logical_name().as_str(),
+            &mut repo.filter_config_section(),
+        )
+        .filter(|uri| !uri.trim().is_empty())
+        .map(|uri| uri.into_owned())
+}
+
+/// Return the creation token of the most recent bundle applied from the list in `fetch.bundleURI`, as stored in
+/// `fetch.bundleCreationToken`.
+#[cfg(feature = "blocking-network-client")]
+pub fn bundle_creation_token(repo: &Repository) -> Result<Option<u64>, Error> {
+    repo.config
+        .resolved
+        .integer_filter_by_key(
+            Fetch::BUNDLE_CREATION_TOKEN.logical_name().as_str(),
+            &mut repo.filter_config_section(),
+        )
+        .map(|value| Fetch::BUNDLE_CREATION_TOKEN.try_into_u64(value))
+        .transpose()
+        .with_leniency(repo.options.lenient_config)
+        .map_err(Error::BundleCreationTokenConfig)
+}
+
+/// Return the filter stored in `remote.<remote_name>.partialCloneFilter` if `remote.<remote_name>.promisor` is `true`,
+/// to reuse it when fetching from a promisor remote.
+pub fn partial_clone_filter(repo: &Repository, remote_name: Option<&BStr>) -> Result<Option<String>, Error> {
+    let name = match remote_name {
+        Some(name) => name,
+        None => return Ok(None),
+    };
+    let mut filter = repo.filter_config_section();
+    let is_promisor = repo
+        .config
+        .resolved
+        .boolean_filter("remote", Some(name), Remote::PROMISOR.name, &mut filter)
+        .map(|res| Remote::PROMISOR.enrich_error(res))
+        .transpose()
+        .with_leniency(repo.options.lenient_config)
+        .map_err(Error::PromisorConfig)?
+        .unwrap_or(false);
+    if !is_promisor {
+        return Ok(None);
+    }
+    Ok(repo
+        .config
+        .resolved
+        .string_filter("remote", Some(name), Remote::PARTIAL_CLONE_FILTER.name, &mut filter)
+        .filter(|spec| !spec.is_empty())
+        .map(|spec| spec.to_str_lossy().into_owned()))
+}
+
+/// Return `true` if stale remote-tracking refs should be pruned, with `remote.<remote_name>.prune` overriding `fetch.prune`.
+pub fn prune(repo: &Repository, remote_name: Option<&BStr>) -> Result<bool, Error> {
+    boolean_with_remote_override(repo, remote_name, &Remote::PRUNE, &Fetch::PRUNE)
+}
+
+/// Return `true` if local tags that don't exist on the remote should be pruned, with `remote.<remote_name>.pruneTags`
+/// overriding `fetch.pruneTags`.
+pub fn prune_tags(repo: &Repository, remote_name: Option<&BStr>) -> Result<bool, Error> {
+    boolean_with_remote_override(repo, remote_name, &Remote::PRUNE_TAGS, &Fetch::PRUNE_TAGS)
+}
+
+fn boolean_with_remote_override(
+    repo: &Repository,
+    remote_name: Option<&BStr>,
+    remote_key: &keys::Boolean,
+    fetch_key: &keys::Boolean,
+) -> Result<bool, Error> {
+    let mut filter = repo.filter_config_section();
+    let value = remote_name
+        .and_then(|name| {
+            repo.config
+                .resolved
+                .boolean_filter("remote", Some(name), remote_key.name, &mut filter)
+                .map(|res| remote_key.enrich_error(res))
+        })
+        .or_else(|| {
+            repo.config
+                .resolved
+                .boolean_filter_by_key(fetch_key.logical_name().as_str(), &mut filter)
+                .map(|res| fetch_key.enrich_error(res))
+        });
+    value
+        .transpose()
+        .with_leniency(repo.options.lenient_config)
+        .map(|value| value.unwrap_or(false))
+        .map_err(Error::PruneConfig)
+}
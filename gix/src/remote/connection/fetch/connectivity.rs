@@ -0,0 +1,55 @@
+use gix_odb::Find;
+
+use super::Error;
+use crate::{remote::fetch, Repository};
+
+/// Assure that the objects of all `mappings` are present along with all of their ancestors, and the root trees of
+/// all commits, failing with the first missing object.
+///
+/// Parents of shallow commits are not expected to be present. If `is_partial` is `true` or the repository is a
+/// [promisor repository][Repository::is_promisor()], missing trees and blobs are expected as they may have been filtered,
+/// and are skipped. Commits and objects the tips point to must always be present.
+/// Note that this traverses the entire history of each tip.
+pub(crate) fn check(repo: &Repository, mappings: &[fetch::Mapping], is_partial: bool) -> Result<(), Error> {
+    let shallow_commits = repo.shallow_commits()?;
+    let is_promisor = is_partial || repo.is_promisor();
+    let may_be_filtered = |kind: Option<gix_object::Kind>| {
+        is_promisor && matches!(kind, Some(gix_object::Kind::Tree | gix_object::Kind::Blob))
+    };
+    let mut buf = Vec::new();
+    let mut seen = gix_hashtable::HashSet::default();
+    // Each object is paired with its expected kind, if it is known without looking at the object itself.
+    let mut queue: Vec<_> = mappings
+        .iter()
+        .filter_map(|m| m.remote.as_id().map(|id| (id.to_owned(), None)))
+        .collect();
+    while let Some((id, kind)) = queue.pop() {
+        if !seen.insert(id) {
+            continue;
+        }
+        let object = match repo
+            .objects
+            .try_find(id, &mut buf)
+            .map_err(Error::ConnectivityFindObject)?
+        {
+            Some(object) => object,
+            None if may_be_filtered(kind) => continue,
+            None => return Err(Error::ConnectivityMissingObject { id }),
+        };
+        match object.decode().map_err(Error::ConnectivityDecodeObject)? {
+            gix_object::ObjectRef::Tag(tag) => queue.push((tag.target(), Some(tag.target_kind))),
+            gix_object::ObjectRef::Commit(commit) => {
+                let tree = commit.tree();
+                if !may_be_filtered(Some(gix_object::Kind::Tree)) && !repo.objects.contains(tree) {
+                    return Err(Error::ConnectivityMissingObject { id: tree });
+                }
+                if shallow_commits.as_ref().map_or(false, |shallow| shallow.contains(&id)) {
+                    continue;
+                }
+                queue.extend(commit.parents().map(|id| (id, Some(gix_object::Kind::Commit))));
+            }
+            gix_object::ObjectRef::Tree(_) | gix_object::ObjectRef::Blob(_) => {}
+        }
+    }
+    Ok(())
+}
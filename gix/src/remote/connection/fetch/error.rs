@@ -21,6 +21,8 @@ pub enum Error {
     Client(#[from] gix_protocol::transport::client::Error),
     #[error(transparent)]
     WritePack(#[from] gix_pack::bundle::write::Error),
+    #[error("Could not load the pack indices of the local object database")]
+    LoadIndices(#[from] gix_odb::store::load_index::Error),
     #[error(transparent)]
     UpdateRefs(#[from] super::refs::update::Error),
     #[error("Failed to remove .keep file at \"{}\"", path.display())]
@@ -45,6 +47,72 @@ pub enum Error {
     RejectShallowRemote,
     #[error(transparent)]
     NegotiationAlgorithmConfig(#[from] config::key::GenericErrorWithValue),
+    #[error("Could not obtain configuration to learn if a commit-graph should be written")]
+    WriteCommitGraphConfig(#[source] config::boolean::Error),
+    #[error("Object {id} isn't advertised by the remote, which doesn't allow fetching unadvertised objects as it lacks the 'allow-reachable-sha1-in-want' capability")]
+    UnadvertisedWantUnsupported { id: gix_hash::ObjectId },
+    #[error("Object {id} is missing after receiving the pack, refusing to update refs")]
+    ConnectivityMissingObject { id: gix_hash::ObjectId },
+    #[error("Could not find an object while checking connectivity")]
+    ConnectivityFindObject(#[source] gix_odb::store::find::Error),
+    #[error("Could not decode an object while checking connectivity")]
+    ConnectivityDecodeObject(#[source] gix_object::decode::Error),
+    #[error("Could not find local branch to set the upstream for")]
+    SetUpstreamFindBranch(#[source] crate::reference::find::Error),
+    #[error("The local branch {name:?} to set the upstream for doesn't exist")]
+    SetUpstreamBranchMissing { name: String },
+    #[error("Could not set the upstream as there were {num_candidates} fetched remote branches to choose from")]
+    SetUpstreamNoUniqueRemoteBranch { num_candidates: usize },
+    #[error("Could not read the local configuration file to set the upstream branch")]
+    SetUpstreamReadConfig(#[source] gix_config::file::init::from_paths::Error),
+    #[error("Could not lock the local configuration file to set the upstream branch")]
+    SetUpstreamLockConfig(#[source] gix_lock::acquire::Error),
+    #[error("Could not write the local configuration file to set the upstream branch")]
+    SetUpstreamWriteConfig(#[source] std::io::Error),
+    #[error("Could not obtain configuration to learn if small packs should be unpacked")]
+    UnpackLimitConfig(#[source] config::unsigned_integer::Error),
+    #[error("Could not open the received pack to unpack it into loose objects")]
+    UnpackOpenPack(#[from] gix_pack::bundle::init::Error),
+    #[error("Could not decode an object of the received pack")]
+    UnpackDecodeObject(#[from] gix_pack::data::decode::Error),
+    #[error("Could not write an object of the received pack as loose object")]
+    UnpackWriteObject(#[from] gix_odb::store::write::Error),
+    #[error("Failed to remove the unpacked pack file at \"{}\"", path.display())]
+    RemoveUnpackedPackFile {
+        path: std::path::PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Could not obtain configuration to learn if stale refs should be pruned")]
+    PruneConfig(#[source] config::boolean::Error),
+    #[error("Could not iterate local references to find stale ones to prune")]
+    PruneIterReferences(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+    #[error("Could not delete stale references")]
+    PruneDeleteReferences(#[source] crate::reference::edit::Error),
+    #[error("The server does not support partial clone")]
+    PartialCloneUnsupported(#[source] gix_protocol::fetch::arguments::filter::Error),
+    #[error("Could not obtain configuration to learn if the remote is a promisor remote")]
+    PromisorConfig(#[source] config::boolean::Error),
+    #[error("Could not read the local configuration file to register the remote as promisor remote")]
+    PromisorReadConfig(#[source] gix_config::file::init::from_paths::Error),
+    #[error("Could not lock the local configuration file to register the remote as promisor remote")]
+    PromisorLockConfig(#[source] gix_lock::acquire::Error),
+    #[error("Could not write the local configuration file to register the remote as promisor remote")]
+    PromisorWriteConfig(#[source] std::io::Error),
+    #[error("Could not obtain configuration to learn if FETCH_HEAD should be written")]
+    WriteFetchHeadConfig(#[source] config::boolean::Error),
+    #[error("Could not find the current branch to learn which fetched refs are to be merged")]
+    FetchHeadFindHead(#[source] crate::reference::find::existing::Error),
+    #[error("Could not lock FETCH_HEAD for writing")]
+    FetchHeadLock(#[source] gix_lock::acquire::Error),
+    #[error("Could not obtain configuration to learn if bundles advertised by the server should be used")]
+    BundleUriConfig(#[source] config::boolean::Error),
+    #[error("Could not obtain the creation token of the most recently applied bundle")]
+    BundleCreationTokenConfig(#[source] config::unsigned_integer::Error),
+    #[error("Could not write FETCH_HEAD at \"{}\"", path.display())]
+    FetchHeadWrite {
+        path: std::path::PathBuf,
+        source: std::io::Error,
+    },
 }
 
 impl gix_protocol::transport::IsSpuriousError for Error {
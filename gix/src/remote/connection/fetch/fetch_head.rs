@@ -0,0 +1,134 @@
+use std::io::Write;
+
+use super::Error;
+use crate::{
+    bstr::{BStr, BString, ByteSlice, ByteVec},
+    remote,
+    remote::fetch,
+    Repository,
+};
+
+/// Write `FETCH_HEAD` into the git directory of `repo` to list all remote refs in `mappings` along with the objects they
+/// point to, in the format `git fetch` uses. If `append` is `true`, add to the existing entries instead of replacing them.
+///
+/// An entry is marked for merging if `branch.<current>.remote` refers to `remote` and `branch.<current>.merge` names the
+/// remote ref, and all other entries are marked `not-for-merge`. Entries marked for merging are written first.
+pub(crate) fn write(
+    repo: &Repository,
+    remote: &crate::Remote<'_>,
+    mappings: &[fetch::Mapping],
+    append: bool,
+) -> Result<(), Error> {
+    let merge_ref = remote_ref_to_merge(repo, remote)?;
+    let url = remote
+        .url(remote::Direction::Fetch)
+        .map(display_url)
+        .unwrap_or_default();
+
+    let mut for_merge = Vec::new();
+    let mut not_for_merge = Vec::new();
+    for mapping in mappings {
+        let id = match mapping.remote.as_id() {
+            Some(id) => id,
+            None => continue,
+        };
+        let name = match &mapping.remote {
+            fetch::Source::Ref(r) => Some(r.unpack().0),
+            fetch::Source::ObjectId(_) => None,
+        };
+        let is_for_merge = name.is_some() && name == merge_ref.as_ref().map(|name| name.as_bstr());
+        let lines = if is_for_merge {
+            &mut for_merge
+        } else {
+            &mut not_for_merge
+        };
+        let mut line = BString::from(id.to_string());
+        line.push_str(if is_for_merge { "\t\t" } else { "\tnot-for-merge\t" });
+        line.push_str(note(name, url.as_bstr()));
+        line.push(b'\n');
+        if !lines.contains(&line) {
+            lines.push(line);
+        }
+    }
+
+    let path = repo.git_dir().join("FETCH_HEAD");
+    let mut lock = gix_lock::File::acquire_to_update_resource(&path, gix_lock::acquire::Fail::Immediately, None)
+        .map_err(Error::FetchHeadLock)?;
+    let io_err = |source| Error::FetchHeadWrite {
+        path: path.clone(),
+        source,
+    };
+    if append {
+        match std::fs::read(&path) {
+            Ok(previous) => lock.write_all(&previous).map_err(io_err)?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => return Err(io_err(err)),
+        }
+    }
+    for line in for_merge.iter().chain(not_for_merge.iter()) {
+        lock.write_all(line).map_err(io_err)?;
+    }
+    lock.commit().map_err(|err| io_err(err.error))?;
+    Ok(())
+}
+
+/// Return the full name of the remote ref the current branch merges if it is configured to merge from `remote`.
+fn remote_ref_to_merge(repo: &Repository, remote: &crate::Remote<'_>) -> Result<Option<BString>, Error> {
+    let head_name = match repo.head_name().map_err(Error::FetchHeadFindHead)? {
+        Some(name) => name,
+        None => return Ok(None),
+    };
+    let short_name = head_name.shorten();
+    let remote_of_branch = match repo.branch_remote_name(short_name) {
+        Some(name) => name,
+        None => return Ok(None),
+    };
+    let is_this_remote = match remote.name() {
+        Some(name) => name.as_bstr() == remote_of_branch.as_bstr(),
+        None => remote
+            .url(remote::Direction::Fetch)
+            .map_or(false, |url| url.to_bstring() == remote_of_branch.as_bstr()),
+    };
+    if !is_this_remote {
+        return Ok(None);
+    }
+    Ok(repo
+        .branch_remote_ref(short_name)
+        .and_then(Result::ok)
+        .map(|name| name.as_bstr().to_owned()))
+}
+
+/// Describe the remote ref `name` fetched from `url` like `git` does, e.g. `branch 'main' of https://host/repo`.
+fn note(name: Option<&BStr>, url: &BStr) -> BString {
+    let mut out = BString::default();
+    if let Some(name) = name.filter(|name| *name != "HEAD") {
+        let (kind, what) = [
+            ("branch ", "refs/heads/"),
+            ("tag ", "refs/tags/"),
+            ("remote-tracking branch ", "refs/remotes/"),
+        ]
+        .iter()
+        .find_map(|(kind, prefix)| name.strip_prefix(prefix.as_bytes()).map(|what| (*kind, what)))
+        .unwrap_or(("", name.as_bytes()));
+        out.push_str(kind);
+        out.push(b'\'');
+        out.push_str(what);
+        out.push_str("' of ");
+    }
+    out.push_str(url);
+    out
+}
+
+/// Return `url` without credentials, trailing slashes and `.git` suffix.
+fn display_url(url: &gix_url::Url) -> BString {
+    let mut url = url.clone();
+    url.set_user(None);
+    let mut url = url.to_bstring();
+    while url.last() == Some(&b'/') {
+        url.pop();
+    }
+    if url.ends_with(b".git") {
+        url.truncate(url.len() - ".git".len());
+    }
+    url
+}
@@ -52,6 +52,8 @@ pub enum Status {
         ///
         /// It's possible that negotiation didn't have to happen as no reference of interest changed on the server.
         negotiate: Option<outcome::Negotiate>,
+        /// In dry-run mode, an estimate of how many objects would have been received, or `None` otherwise.
+        estimate: Option<outcome::Estimate>,
         /// However, depending on the refspecs, references might have been updated nonetheless to point to objects as
         /// reported by the remote.
         update_refs: refs::update::Outcome,
@@ -74,6 +76,17 @@ pub struct Outcome {
     pub ref_map: RefMap,
     /// The status of the operation to indicate what happened.
     pub status: Status,
+    /// The names of all local refs that were deleted as they don't exist on the remote anymore, or that would have been
+    /// deleted in dry-run mode.
+    ///
+    /// It's only non-empty if pruning was enabled, see [`Prepare::with_prune()`].
+    pub pruned_refs: Vec<gix_ref::FullName>,
+    /// All bundles that were downloaded and applied before negotiating, in the order in which they were applied.
+    ///
+    /// It's only non-empty if `fetch.bundleURI` is set, or if the server advertised bundles and their use was enabled
+    /// with [`Prepare::with_bundle_uri()`] or `transfer.bundleURI`. Note that bundles are only applied with the
+    /// `blocking-network-client` feature.
+    pub bundles: Vec<outcome::Bundle>,
 }
 
 /// Additional types related to the outcome of a fetch operation.
@@ -89,6 +102,32 @@ pub mod outcome {
         pub rounds: Vec<negotiate::Round>,
     }
 
+    /// A bundle that was downloaded and whose objects were added to the object database before negotiating.
+    #[derive(Debug, Clone)]
+    pub struct Bundle {
+        /// The location the bundle was obtained from.
+        pub uri: crate::bstr::BString,
+        /// Information collected while writing the pack contained in the bundle and its index.
+        pub write_pack_bundle: gix_pack::bundle::write::Outcome,
+        /// The references stored in the bundle as written to the local repository, each below `refs/bundles/`,
+        /// along with the object they point to. They are only written if [refs are updated][super::Prepare::with_update_refs()].
+        pub refs: Vec<(gix_ref::FullName, gix_hash::ObjectId)>,
+        /// The creation token of the bundle if it was part of a bundle list that provides them.
+        pub creation_token: Option<u64>,
+    }
+
+    /// An estimate of the amount of objects a fetch would receive, as computed in dry-run mode.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Estimate {
+        /// The server announced a pack with exactly this amount of objects after negotiation, which is what it intends to send.
+        Objects(u32),
+        /// The server didn't announce a pack, so this is the amount of distinct objects that remote refs point to which
+        /// don't exist locally.
+        ///
+        /// It's a lower bound as the history leading up to these objects is likely to be missing as well.
+        RefDelta(usize),
+    }
+
     ///
     pub mod negotiate {
         /// Key information about each round in the pack-negotiation.
@@ -193,6 +232,18 @@ where
             reflog_message: None,
             write_packed_refs: WritePackedRefs::Never,
             shallow: Default::default(),
+            write_commit_graph: None,
+            check_connectivity: false,
+            set_upstream: None,
+            prune: None,
+            prune_tags: None,
+            write_fetch_head: None,
+            append_fetch_head: false,
+            filter: None,
+            thread_limit: None,
+            max_negotiation_rounds: None,
+            bundle_uri: None,
+            update_refs: true,
         })
     }
 }
@@ -207,11 +258,19 @@ where
     }
 }
 
+#[cfg(feature = "blocking-network-client")]
+mod bundle_uri;
+mod commit_graph;
 mod config;
+mod connectivity;
+mod fetch_head;
+mod promisor;
+mod prune;
 mod receive_pack;
 ///
 #[path = "update_refs/mod.rs"]
 pub mod refs;
+mod upstream;
 
 /// A structure to hold the result of the handshake with the remote and configure the upcoming fetch operation.
 pub struct Prepare<'remote, 'repo, T>
@@ -224,6 +283,18 @@ where
     reflog_message: Option<RefLogMessage>,
     write_packed_refs: WritePackedRefs,
     shallow: remote::fetch::Shallow,
+    write_commit_graph: Option<bool>,
+    check_connectivity: bool,
+    set_upstream: Option<String>,
+    prune: Option<bool>,
+    prune_tags: Option<bool>,
+    write_fetch_head: Option<bool>,
+    append_fetch_head: bool,
+    filter: Option<String>,
+    thread_limit: Option<usize>,
+    max_negotiation_rounds: Option<usize>,
+    bundle_uri: Option<bool>,
+    update_refs: bool,
 }
 
 /// Builder
@@ -265,6 +336,126 @@ where
         self.shallow = shallow;
         self
     }
+
+    /// If `Some(true)`, write a commit-graph covering all commits reachable from references after a pack was received,
+    /// or never do so with `Some(false)`.
+    ///
+    /// If `None`, the default, `fetch.writeCommitGraph` decides.
+    pub fn with_write_commit_graph(mut self, enabled: impl Into<Option<bool>>) -> Self {
+        self.write_commit_graph = enabled.into();
+        self
+    }
+
+    /// If enabled, before updating refs, assure that all objects they point to are present along with their entire
+    /// ancestry, failing the fetch if the received pack turns out to be incomplete.
+    ///
+    /// This is disabled by default as it traverses the entire history of each fetched ref.
+    pub fn with_connectivity_check(mut self, enabled: bool) -> Self {
+        self.check_connectivity = enabled;
+        self
+    }
+
+    /// If `Some(local_branch)`, after a successful fetch configure `branch.<local_branch>.remote` and
+    /// `branch.<local_branch>.merge` so that the local branch tracks the fetched remote branch, similar to `git fetch --set-upstream`.
+    ///
+    /// The remote branch is the only branch that was fetched, or the one with the same name as `local_branch` if there are
+    /// multiple. The local branch must exist, and the configuration is only written to the local configuration file, without
+    /// updating the in-memory configuration of the repository.
+    pub fn with_set_upstream(mut self, local_branch: impl Into<Option<String>>) -> Self {
+        self.set_upstream = local_branch.into();
+        self
+    }
+
+    /// If `Some(true)`, delete local refs matching the destination of a fetch ref-spec if the corresponding ref doesn't exist
+    /// on the remote anymore, similar to `git fetch --prune`, or never do so with `Some(false)`.
+    ///
+    /// If `None`, the default, `remote.<name>.prune` or `fetch.prune` decide.
+    pub fn with_prune(mut self, enabled: impl Into<Option<bool>>) -> Self {
+        self.prune = enabled.into();
+        self
+    }
+
+    /// If `Some(true)` and pruning is enabled, also delete local tags that don't exist on the remote anymore, similar to
+    /// `git fetch --prune --prune-tags`, or never do so with `Some(false)`.
+    ///
+    /// If `None`, the default, `remote.<name>.pruneTags` or `fetch.pruneTags` decide. Note that tags fetched by
+    /// an explicit ref-spec are pruned either way, and that tags can't be pruned if they aren't fetched at all, i.e.
+    /// if `remote.<name>.tagOpt` is `--no-tags`.
+    pub fn with_prune_tags(mut self, enabled: impl Into<Option<bool>>) -> Self {
+        self.prune_tags = enabled.into();
+        self
+    }
+
+    /// If `Some(true)`, write `FETCH_HEAD` to list all fetched refs after a successful fetch, or never do so with `Some(false)`.
+    ///
+    /// If `None`, the default, `fetch.writeFetchHEAD` decides, which is enabled unless configured otherwise.
+    pub fn with_write_fetch_head(mut self, enabled: impl Into<Option<bool>>) -> Self {
+        self.write_fetch_head = enabled.into();
+        self
+    }
+
+    /// If enabled, add the fetched refs to the existing entries of `FETCH_HEAD` instead of replacing them,
+    /// similar to `git fetch --append`.
+    pub fn with_append_fetch_head(mut self, enabled: bool) -> Self {
+        self.append_fetch_head = enabled;
+        self
+    }
+
+    /// Ask the server to omit objects matching the filter `spec`, like `blob:none`, to perform a partial fetch similar to
+    /// `git fetch --filter=<spec>`.
+    ///
+    /// If `None`, the default, the filter in `remote.<name>.partialCloneFilter` is used if the remote is a promisor remote
+    /// as per `remote.<name>.promisor`.
+    pub fn with_filter(mut self, spec: impl Into<Option<String>>) -> Self {
+        self.filter = spec.into();
+        self
+    }
+
+    /// Set the maximum amount of threads to use for loading the pack indices of the local object database before
+    /// negotiation, and for resolving the deltas of the received pack, with `Some(0)` meaning as many as there are logical cores.
+    ///
+    /// If `None`, the default, `pack.threads` is used, falling back to as many threads as there are logical cores.
+    pub fn with_thread_limit(mut self, limit: impl Into<Option<usize>>) -> Self {
+        self.thread_limit = limit.into();
+        self
+    }
+
+    /// Stop negotiating after `Some(rounds)` rounds at most and tell the server that we are done, so that it sends a pack
+    /// based on what it knows to be in common so far. This bounds the work spent on negotiation in pathological cases,
+    /// for instance if the server never acknowledges to be ready, at the cost of possibly receiving more objects than needed.
+    ///
+    /// If `None`, the default, the negotiation continues until the negotiator has no more commits to offer.
+    pub fn with_max_negotiation_rounds(mut self, rounds: impl Into<Option<usize>>) -> Self {
+        self.max_negotiation_rounds = rounds.into();
+        self
+    }
+
+    /// If `Some(true)`, download and apply the bundles advertised by the server via the `bundle-uri` capability before
+    /// negotiating, so that only the objects that aren't contained in any bundle have to be fetched, or never do so with `Some(false)`.
+    ///
+    /// If `None`, the default, `transfer.bundleURI` decides. Note that as bundles are downloaded with blocking I/O, they are
+    /// only used with the `blocking-network-client` feature.
+    pub fn with_bundle_uri(mut self, enabled: impl Into<Option<bool>>) -> Self {
+        self.bundle_uri = enabled.into();
+        self
+    }
+
+    /// If disabled, negotiate and receive the pack as usual, but don't change any references, similar to a [dry run][Self::with_dry_run()]
+    /// that still downloads objects. This allows to inspect the received objects before deciding to update refs.
+    ///
+    /// The ref-edits that would have been performed are still computed and returned as part of the outcome, as are the refs
+    /// that would have been pruned. `FETCH_HEAD` isn't written and no upstream is configured either. As no ref points to the new
+    /// objects, the `.keep` file of the received pack is retained and must be removed by the caller once the objects are referenced.
+    ///
+    /// Bundles are still applied, but their refs aren't written below `refs/bundles/` and their packs retain their `.keep` file
+    /// as well. Neither is `fetch.bundleCreationToken` updated nor is the remote registered as promisor remote when using a
+    /// [filter][Self::with_filter()].
+    ///
+    /// It's enabled by default.
+    pub fn with_update_refs(mut self, enabled: bool) -> Self {
+        self.update_refs = enabled;
+        self
+    }
 }
 
 impl<'remote, 'repo, T> Drop for Prepare<'remote, 'repo, T>
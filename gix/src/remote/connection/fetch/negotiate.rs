@@ -5,7 +5,11 @@ use gix_negotiate::Flags;
 use gix_odb::HeaderExt;
 use gix_pack::Find;
 
-use crate::remote::{fetch, fetch::Shallow};
+use crate::{
+    bstr::{BStr, ByteSlice},
+    config::tree::{Core, Key},
+    remote::{fetch, fetch::Shallow},
+};
 
 type Queue = gix_revision::PriorityQueue<SecondsSinceUnixEpoch, gix_hash::ObjectId>;
 
@@ -25,6 +29,8 @@ pub enum Error {
     ObtainRefDuringIteration(#[from] Box<dyn std::error::Error + Send + Sync + 'static>),
     #[error(transparent)]
     LoadIndex(#[from] gix_odb::store::load_index::Error),
+    #[error("Could not run core.alternateRefsCommand to obtain the tips of an alternate repository")]
+    AlternateRefsCommand(#[source] std::io::Error),
 }
 
 #[must_use]
@@ -252,6 +258,26 @@ pub(crate) fn add_wants(
     }
 }
 
+/// Return the first object id we have to `want` that isn't the target of any ref advertised by the remote, which is only
+/// allowed in protocol V1 if the server advertises the `allow-reachable-sha1-in-want` capability.
+pub(crate) fn first_unadvertised_want(ref_map: &fetch::RefMap, mapping_known: &[bool]) -> Option<gix_hash::ObjectId> {
+    ref_map
+        .mappings
+        .iter()
+        .zip(mapping_known)
+        .filter(|(_, known)| !**known)
+        .find_map(|(m, _)| match &m.remote {
+            fetch::Source::ObjectId(id) => {
+                let is_advertised = ref_map.remote_refs.iter().any(|r| {
+                    let (_, target, peeled) = r.unpack();
+                    target == Some(id.as_ref()) || peeled == Some(id.as_ref())
+                });
+                (!is_advertised).then_some(*id)
+            }
+            fetch::Source::Ref(_) => None,
+        })
+}
+
 /// Remove all commits that are more recent than the cut-off, which is the commit time of the oldest common commit we have with the server.
 fn mark_recent_complete_commits(
     queue: &mut Queue,
@@ -290,21 +316,33 @@ fn mark_all_refs_in_repo(
     let _span = gix_trace::detail!("mark_all_refs");
     for local_ref in repo.references()?.all()?.peeled() {
         let local_ref = local_ref?;
-        let id = local_ref.id().detach();
-        let mut is_complete = false;
-        if let Some(commit) = graph
-            .try_lookup_or_insert_commit(id, |md| {
-                is_complete = md.flags.contains(Flags::COMPLETE);
-                md.flags |= mark
-            })?
-            .filter(|_| !is_complete)
-        {
-            queue.insert(commit.commit_time, id);
-        };
+        mark_tip(local_ref.id().detach(), graph, queue, mark)?;
     }
     Ok(())
 }
 
+fn mark_tip(
+    id: gix_hash::ObjectId,
+    graph: &mut gix_negotiate::Graph<'_>,
+    queue: &mut Queue,
+    mark: Flags,
+) -> Result<(), Error> {
+    let mut is_complete = false;
+    if let Some(commit) = graph
+        .try_lookup_or_insert_commit(id, |md| {
+            is_complete = md.flags.contains(Flags::COMPLETE);
+            md.flags |= mark
+        })?
+        .filter(|_| !is_complete)
+    {
+        queue.insert(commit.commit_time, id);
+    };
+    Ok(())
+}
+
+/// Mark the tips of all alternate repositories as complete, which are obtained by running `core.alternateRefsCommand`
+/// with the path to the alternate repository if set, or from all references of the alternate repository otherwise,
+/// limited to those matching `core.alternateRefsPrefixes` if set.
 fn mark_alternate_complete(
     repo: &crate::Repository,
     graph: &mut gix_negotiate::Graph<'_>,
@@ -312,17 +350,85 @@ fn mark_alternate_complete(
 ) -> Result<(), Error> {
     let alternates = repo.objects.store_ref().alternate_db_paths()?;
     let _span = gix_trace::detail!("mark_alternate_refs", num_odb = alternates.len());
+    if alternates.is_empty() {
+        return Ok(());
+    }
+
+    let mut filter = repo.filter_config_section();
+    let command = repo
+        .config
+        .resolved
+        .string_filter_by_key(Core::ALTERNATE_REFS_COMMAND.logical_name().as_str(), &mut filter)
+        .filter(|cmd| !cmd.trim().is_empty());
+    let prefixes: Vec<_> = repo
+        .config
+        .resolved
+        .string_filter_by_key(Core::ALTERNATE_REFS_PREFIXES.logical_name().as_str(), &mut filter)
+        .map(|prefixes| {
+            prefixes
+                .fields()
+                .map(|prefix| gix_path::from_byte_slice(prefix).to_owned())
+                .collect()
+        })
+        .unwrap_or_default();
 
-    for alternate_repo in alternates.into_iter().filter_map(|path| {
-        path.ancestors()
-            .nth(1)
-            .and_then(|git_dir| crate::open_opts(git_dir, repo.options.clone()).ok())
-    }) {
-        mark_all_refs_in_repo(&alternate_repo, graph, queue, Flags::ALTERNATE | Flags::COMPLETE)?;
+    let mark = Flags::ALTERNATE | Flags::COMPLETE;
+    for git_dir in alternates
+        .into_iter()
+        .filter_map(|path| path.ancestors().nth(1).map(ToOwned::to_owned))
+    {
+        if let Some(command) = command.as_deref() {
+            for id in alternate_tips_from_command(command, &git_dir)? {
+                mark_tip(id, graph, queue, mark)?;
+            }
+            continue;
+        }
+        let alternate_repo = match crate::open_opts(&git_dir, repo.options.clone()) {
+            Ok(repo) => repo,
+            Err(_) => continue,
+        };
+        if prefixes.is_empty() {
+            mark_all_refs_in_repo(&alternate_repo, graph, queue, mark)?;
+        } else {
+            let platform = alternate_repo.references()?;
+            for prefix in &prefixes {
+                for alternate_ref in platform.prefixed(prefix)?.peeled() {
+                    mark_tip(alternate_ref?.id().detach(), graph, queue, mark)?;
+                }
+            }
+        }
     }
     Ok(())
 }
 
+/// Run `command` with the `git_dir` of an alternate repository as argument and parse one object id per line of its output,
+/// ignoring lines that aren't valid object ids.
+fn alternate_tips_from_command(command: &BStr, git_dir: &std::path::Path) -> Result<Vec<gix_hash::ObjectId>, Error> {
+    use std::io::BufRead;
+
+    let mut child = gix_command::prepare(gix_path::from_bstr(command).into_owned())
+        .with_shell()
+        .arg(git_dir)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .map_err(Error::AlternateRefsCommand)?;
+    let mut ids = Vec::new();
+    for line in std::io::BufReader::new(child.stdout.take().expect("configured")).split(b'\n') {
+        let line = line.map_err(Error::AlternateRefsCommand)?;
+        if let Ok(id) = gix_hash::ObjectId::from_hex(line.trim_end()) {
+            ids.push(id);
+        }
+    }
+    let status = child.wait().map_err(Error::AlternateRefsCommand)?;
+    if !status.success() {
+        log::warn!(
+            "core.alternateRefsCommand {command:?} failed with {status}, using the tips it provided nonetheless"
+        );
+    }
+    Ok(ids)
+}
+
 /// Negotiate the nth `round` with `negotiator` sending `haves_to_send` after possibly making the known common commits
 /// as sent by the remote known to `negotiator` using `previous_response` if this isn't the first round.
 /// All `haves` are added to `arguments` accordingly.
@@ -0,0 +1,26 @@
+use std::convert::TryInto;
+
+use super::Error;
+use crate::{bstr::BStr, Repository};
+
+/// Write `remote.<remote_name>.promisor=true` and `remote.<remote_name>.partialCloneFilter=<filter>` to the local configuration
+/// file of `repo`, marking it as partial clone whose missing objects are promised by the remote, and to reuse `filter`
+/// in future fetches from it.
+///
+/// Note that the configuration of `repo` isn't updated in memory.
+pub(crate) fn register(repo: &Repository, remote_name: &BStr, filter: &str) -> Result<(), Error> {
+    let config_path = repo.common_dir().join("config");
+    let mut config = gix_config::File::from_path_no_includes(config_path.clone(), gix_config::Source::Local)
+        .map_err(Error::PromisorReadConfig)?;
+    let mut section = config
+        .section_mut_or_create_new("remote", Some(remote_name))
+        .expect("section header name is always valid per naming rules, and so is the name of a configured remote");
+    section.set("promisor".try_into().expect("valid at compile time"), "true");
+    section.set("partialCloneFilter".try_into().expect("valid at compile time"), filter);
+
+    let mut lock = gix_lock::File::acquire_to_update_resource(&config_path, gix_lock::acquire::Fail::Immediately, None)
+        .map_err(Error::PromisorLockConfig)?;
+    config.write_to(&mut lock).map_err(Error::PromisorWriteConfig)?;
+    lock.commit().map_err(|err| Error::PromisorWriteConfig(err.error))?;
+    Ok(())
+}
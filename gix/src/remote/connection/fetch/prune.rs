@@ -0,0 +1,90 @@
+use std::collections::BTreeSet;
+
+use gix_ref::transaction::{Change, PreviousValue, RefEdit, RefLog};
+
+use super::Error;
+use crate::{
+    bstr::{BStr, ByteSlice},
+    remote,
+    remote::fetch::{DryRun, RefMap},
+    Repository,
+};
+
+/// Delete all local refs matching the destination of a fetch refspec of `remote` that don't correspond to a ref on the remote
+/// anymore, as per `ref_map`, and return their names. Nothing is deleted if `dry_run` is enabled.
+///
+/// Tags that are only fetched due to `remote.<name>.tagOpt` are pruned only if `prune_tags` is `true`, whereas tags
+/// fetched by an explicit refspec are always subject to pruning. Symbolic refs are never pruned.
+pub(crate) fn remove_stale_refs(
+    repo: &Repository,
+    remote: &crate::Remote<'_>,
+    ref_map: &RefMap,
+    prune_tags: bool,
+    dry_run: DryRun,
+) -> Result<Vec<gix_ref::FullName>, Error> {
+    let implicit_tag_spec = remote.fetch_tags.to_refspec();
+    let destinations: Vec<&BStr> = remote
+        .refspecs(remote::Direction::Fetch)
+        .iter()
+        .map(|spec| (spec, false))
+        .chain(ref_map.extra_refspecs.iter().map(|spec| {
+            let is_implicit_tag_spec = implicit_tag_spec.map_or(false, |tag_spec| spec.to_ref() == tag_spec);
+            (spec, is_implicit_tag_spec)
+        }))
+        .filter(|(_, is_implicit_tag_spec)| prune_tags || !is_implicit_tag_spec)
+        .filter_map(|(spec, _)| match spec.to_ref().instruction() {
+            gix_refspec::Instruction::Fetch(gix_refspec::instruction::Fetch::AndUpdate { dst, .. }) => Some(dst),
+            _ => None,
+        })
+        .collect();
+    if destinations.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let tracked: BTreeSet<&BStr> = ref_map
+        .mappings
+        .iter()
+        .filter_map(|m| m.local.as_ref().map(|name| name.as_bstr()))
+        .collect();
+    let mut edits = Vec::new();
+    for reference in repo
+        .references()
+        .map_err(|err| Error::PruneIterReferences(err.into()))?
+        .all()
+        .map_err(|err| Error::PruneIterReferences(err.into()))?
+    {
+        let reference = reference.map_err(Error::PruneIterReferences)?.detach();
+        let name = reference.name.as_bstr();
+        if reference.target.try_id().is_none()
+            || tracked.contains(name)
+            || !destinations.iter().any(|dst| matches_destination(dst, name))
+        {
+            continue;
+        }
+        edits.push(RefEdit {
+            change: Change::Delete {
+                expected: PreviousValue::MustExistAndMatch(reference.target),
+                log: RefLog::AndReference,
+            },
+            name: reference.name,
+            deref: false,
+        });
+    }
+
+    if matches!(dry_run, DryRun::No) && !edits.is_empty() {
+        repo.edit_references(edits.iter().cloned())
+            .map_err(Error::PruneDeleteReferences)?;
+    }
+    Ok(edits.into_iter().map(|edit| edit.name).collect())
+}
+
+/// Return `true` if the full ref `name` matches the refspec destination `dst`, which may contain a single `*`.
+fn matches_destination(dst: &BStr, name: &BStr) -> bool {
+    match dst.find_byte(b'*') {
+        Some(pos) => {
+            let (prefix, suffix) = (&dst[..pos], &dst[pos + 1..]);
+            name.len() > prefix.len() + suffix.len() && name.starts_with(prefix) && name.ends_with(suffix)
+        }
+        None => dst == name,
+    }
+}
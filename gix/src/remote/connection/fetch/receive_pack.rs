@@ -3,12 +3,14 @@ use std::{
     sync::atomic::{AtomicBool, Ordering},
 };
 
-use gix_odb::{store::RefreshMode, FindExt};
+use gix_odb::{store::RefreshMode, Find, FindExt};
 use gix_protocol::{
     fetch::Arguments,
     transport::{client::Transport, packetline::read::ProgressAction},
 };
 
+#[cfg(feature = "blocking-network-client")]
+use crate::remote::connection::fetch::bundle_uri;
 use crate::{
     config::{
         cache::util::ApplyLeniency,
@@ -16,7 +18,7 @@ use crate::{
     },
     remote,
     remote::{
-        connection::fetch::config,
+        connection::fetch::{commit_graph, config, connectivity, fetch_head, promisor, prune, upstream},
         fetch,
         fetch::{
             negotiate, negotiate::Algorithm, outcome, refs, Error, Outcome, Prepare, ProgressId, RefLogMessage,
@@ -39,6 +41,9 @@ where
     /// "fetch.negotiationAlgorithm" describes algorithms `git` uses currently, with the default being `consecutive` and `skipping` being
     /// experimented with. We currently implement something we could call 'naive' which works for now.
     ///
+    /// The amount of rounds can be bounded with [`with_max_negotiation_rounds()`][Self::with_max_negotiation_rounds()], after which
+    /// the server is told that we are done to let it send a pack with what it knows to be in common.
+    ///
     /// ### Pack `.keep` files
     ///
     /// That packs that are freshly written to the object database are vulnerable to garbage collection for the brief time that it takes between
@@ -71,6 +76,59 @@ where
     /// ### Configuration
     ///
     /// - `gitoxide.userAgent` is read to obtain the application user agent for git servers and for HTTP servers as well.
+    /// - `pack.threads` is read to limit the amount of threads used to load local pack indices and to resolve the received pack,
+    ///    unless overridden with [`with_thread_limit()`][Self::with_thread_limit()]. Indices are only loaded ahead of time
+    ///    if a pack is going to be received.
+    /// - `fetch.writeCommitGraph` is read to write a commit-graph after a pack was received, unless overridden
+    ///    with [`with_write_commit_graph()`][Self::with_write_commit_graph()]. Failing to write it is logged, but not an error.
+    /// - `fetch.unpackLimit` or `transfer.unpackLimit` is read to unpack received packs with fewer objects than the limit into
+    ///    loose objects, removing the pack, its index and `.keep` file. Note that unlike `git`, packs are never unpacked
+    ///    if neither key is set, nor if [refs aren't updated][Self::with_update_refs()] as nothing would refer to the objects.
+    /// - `fetch.writeFetchHEAD` is read to write `FETCH_HEAD` after fetching, unless overridden with
+    ///    [`with_write_fetch_head()`][Self::with_write_fetch_head()].
+    /// - `core.alternateRefsCommand` is run with the path of each alternate repository to obtain its tips for use during
+    ///    negotiation, or if unset, `core.alternateRefsPrefixes` limits the references of alternate repositories to use as tips.
+    /// - `transfer.bundleURI` is read to use bundles advertised by the server, unless overridden with
+    ///    [`with_bundle_uri()`][Self::with_bundle_uri()], and `fetch.bundleURI` is read to use the bundle or bundle list
+    ///    at the given location.
+    ///
+    /// ### Connectivity
+    ///
+    /// If enabled with [`with_connectivity_check()`][Self::with_connectivity_check()], all objects that refs are about to point to
+    /// are checked to be present along with their ancestry before any ref is updated.
+    ///
+    /// ### Partial Fetches
+    ///
+    /// If a filter is set with [`with_filter()`][Self::with_filter()] or the remote is a promisor remote as per
+    /// `remote.<name>.promisor` with a filter in `remote.<name>.partialCloneFilter`, the server is asked to omit matching objects.
+    /// After a successful fetch, the remote is registered as promisor remote along with the filter in the local configuration file,
+    /// unless it's an anonymous remote. The connectivity check tolerates missing trees and blobs in this case, as well as in repositories
+    /// that are [promisor repositories][crate::Repository::is_promisor()], as they are expected to be missing.
+    /// If the server doesn't advertise the `filter` capability, which `git` servers do with `uploadpack.allowFilter`,
+    /// the fetch fails before negotiation starts.
+    ///
+    /// ### Pruning
+    ///
+    /// If enabled with [`with_prune()`][Self::with_prune()] or via `remote.<name>.prune` and `fetch.prune`, local refs that
+    /// don't exist on the remote anymore are deleted before refs are updated. Tags are only pruned along with them if
+    /// [`with_prune_tags()`][Self::with_prune_tags()], `remote.<name>.pruneTags` or `fetch.pruneTags` enable it.
+    ///
+    /// ### Bundles
+    ///
+    /// Before negotiating, bundles configured via `fetch.bundleURI` or advertised by a V2 server with the `bundle-uri` capability
+    /// are downloaded and their packs are added to the object database, with their refs written below `refs/bundles/`.
+    /// This way, the server only has to send the objects that aren't contained in any bundle. Bundles that can't be downloaded
+    /// or applied, for instance as their prerequisites are missing, are skipped, as are bundles whose refs are present already.
+    /// Bundles of a list in `fetch.bundleURI` are skipped if their creation token isn't greater than `fetch.bundleCreationToken`,
+    /// which is updated after applying them. Failing to obtain the bundle list from the server isn't an error either.
+    /// Nothing is downloaded in dry-run mode, and bundles are only used with the `blocking-network-client` feature.
+    ///
+    /// ### `FETCH_HEAD`
+    ///
+    /// Unless disabled, `FETCH_HEAD` is written to list all fetched refs with the objects they point to. An entry is marked
+    /// for merging if the current branch is configured to merge it from this remote via `branch.<name>.remote` and
+    /// `branch.<name>.merge`, all others are marked as `not-for-merge`. Previous entries are replaced unless
+    /// [`with_append_fetch_head()`][Self::with_append_fetch_head()] is enabled. Nothing is written in dry-run mode.
     ///
     #[gix_protocol::maybe_async::maybe_async]
     pub async fn receive<P>(mut self, mut progress: P, should_interrupt: &AtomicBool) -> Result<Outcome, Error>
@@ -87,6 +145,9 @@ where
         let fetch = gix_protocol::Command::Fetch;
         let progress = &mut progress;
         let repo = con.remote.repo;
+        if let Some(local_branch) = &self.set_upstream {
+            upstream::assure_branch_exists(repo, local_branch)?;
+        }
         let fetch_features = {
             let mut f = fetch.default_features(protocol_version, &handshake.capabilities);
             f.push(repo.config.user_agent_tuple());
@@ -94,6 +155,12 @@ where
         };
 
         gix_protocol::fetch::Response::check_required_features(protocol_version, &fetch_features)?;
+        let remote = con.remote;
+        let remote_name = remote.name().map(|name| name.as_bstr());
+        let filter = match self.filter.take() {
+            Some(filter) => Some(filter),
+            None => config::partial_clone_filter(repo, remote_name)?,
+        };
         let sideband_all = fetch_features.iter().any(|(n, _)| *n == "sideband-all");
         let mut arguments = gix_protocol::fetch::Arguments::new(protocol_version, fetch_features);
         if matches!(con.remote.fetch_tags, crate::remote::fetch::Tags::Included) {
@@ -108,6 +175,11 @@ where
             arguments.use_include_tag();
         }
         let (shallow_commits, mut shallow_lock) = add_shallow_args(&mut arguments, &self.shallow, repo)?;
+        if let Some(filter) = &filter {
+            arguments
+                .filter(filter.as_str().into())
+                .map_err(Error::PartialCloneUnsupported)?;
+        }
 
         if self.ref_map.object_hash != repo.object_hash() {
             return Err(Error::IncompatibleObjectHash {
@@ -116,6 +188,67 @@ where
             });
         }
 
+        let thread_limit = match self.thread_limit {
+            Some(limit) => Some(limit),
+            None => config::index_threads(repo)?,
+        };
+        #[cfg(feature = "blocking-network-client")]
+        let bundles = {
+            let mut bundles = Vec::new();
+            if matches!(self.dry_run, fetch::DryRun::No) {
+                let options = gix_pack::bundle::write::Options {
+                    thread_limit,
+                    index_version: config::pack_index_version(repo)?,
+                    iteration_mode: gix_pack::data::input::Mode::Verify,
+                    object_hash: repo.object_hash(),
+                };
+                if let Some(uri) = config::fetch_bundle_uri(repo) {
+                    bundles.extend(bundle_uri::apply_uri(
+                        repo,
+                        uri.as_ref(),
+                        config::bundle_creation_token(repo)?,
+                        self.update_refs,
+                        options.clone(),
+                        progress,
+                        should_interrupt,
+                    ));
+                }
+                if protocol_version == gix_protocol::transport::Protocol::V2
+                    && handshake
+                        .capabilities
+                        .contains(gix_protocol::Command::BundleUri.as_str())
+                    && self.bundle_uri.map_or_else(|| config::transfer_bundle_uri(repo), Ok)?
+                {
+                    match gix_protocol::bundle_uri(
+                        &mut con.transport,
+                        &handshake.capabilities,
+                        vec![repo.config.user_agent_tuple()],
+                        progress,
+                    ) {
+                        Ok(list) => {
+                            let remote_url = con.transport.to_url();
+                            bundles.extend(bundle_uri::apply_list(
+                                repo,
+                                &list,
+                                Some(remote_url.as_ref()),
+                                None,
+                                self.update_refs,
+                                options,
+                                progress,
+                                should_interrupt,
+                            ));
+                        }
+                        Err(err) => {
+                            log::warn!("failed to obtain the bundle list from the server, continuing without it: {err}")
+                        }
+                    }
+                }
+            }
+            bundles
+        };
+        #[cfg(not(feature = "blocking-network-client"))]
+        let bundles = Vec::new();
+
         let negotiate_span = gix_trace::detail!("negotiate");
         let mut negotiator = repo
             .config
@@ -144,6 +277,7 @@ where
             negotiate::make_refmapping_ignore_predicate(con.remote.fetch_tags, &self.ref_map),
         )?;
         let mut previous_response = None::<gix_protocol::fetch::Response>;
+        let mut num_objects_announced = None;
         let (mut write_pack_bundle, negotiate) = match &action {
             negotiate::Action::NoChange | negotiate::Action::SkipToRefUpdate => {
                 gix_protocol::indicate_end_of_interaction(&mut con.transport).await.ok();
@@ -152,6 +286,18 @@ where
             negotiate::Action::MustNegotiate {
                 remote_ref_target_known,
             } => {
+                if protocol_version == gix_protocol::transport::Protocol::V1
+                    && !handshake.capabilities.contains("allow-reachable-sha1-in-want")
+                {
+                    if let Some(id) = negotiate::first_unadvertised_want(&self.ref_map, remote_ref_target_known) {
+                        return Err(Error::UnadvertisedWantUnsupported { id });
+                    }
+                }
+                if matches!(self.dry_run, fetch::DryRun::No) {
+                    // Negotiation and resolving the received pack will look up most local objects, so load all indices
+                    // at once while we know that a pack is going to be received.
+                    repo.objects.store_ref().load_all_indices_in_parallel(thread_limit)?;
+                }
                 negotiate::add_wants(
                     repo,
                     &mut arguments,
@@ -192,7 +338,9 @@ where
                                 haves_to_send,
                                 previous_response_had_at_least_one_in_common: ack_seen,
                             });
-                            let is_done = haves_sent != haves_to_send || (seen_ack && in_vain >= 256);
+                            let is_done = haves_sent != haves_to_send
+                                || (seen_ack && in_vain >= 256)
+                                || self.max_negotiation_rounds.map_or(false, |max| rounds.len() >= max);
                             haves_to_send = gix_negotiate::window_size(is_stateless, haves_to_send);
                             is_done
                         }
@@ -238,7 +386,7 @@ where
                 }
 
                 let options = gix_pack::bundle::write::Options {
-                    thread_limit: config::index_threads(repo)?,
+                    thread_limit,
                     index_version: config::pack_index_version(repo)?,
                     iteration_mode: gix_pack::data::input::Mode::Verify,
                     object_hash: con.remote.repo.object_hash(),
@@ -264,6 +412,18 @@ where
                         options,
                     )?)
                 } else {
+                    let mut reader = reader;
+                    // The pack header tells us how many objects the server intends to send, which is all we need.
+                    let mut header = [0u8; 12];
+                    #[cfg(feature = "async-network-client")]
+                    let header_read =
+                        gix_protocol::futures_lite::io::AsyncReadExt::read_exact(&mut reader, &mut header).await;
+                    #[cfg(not(feature = "async-network-client"))]
+                    let header_read = std::io::Read::read_exact(&mut reader, &mut header);
+                    num_objects_announced = header_read
+                        .ok()
+                        .and_then(|_| gix_pack::data::header::decode(&header).ok())
+                        .map(|(_version, num_objects)| num_objects);
                     drop(reader);
                     None
                 };
@@ -281,6 +441,33 @@ where
             }
         };
 
+        if let Some(bundle) = write_pack_bundle.as_mut() {
+            if self.update_refs
+                && bundle.index.num_objects != 0
+                && config::unpack_limit(repo)?.map_or(false, |limit| bundle.index.num_objects < limit)
+            {
+                unpack_into_loose_objects(repo, bundle)?;
+            }
+        }
+
+        if self.check_connectivity && write_pack_bundle.is_some() {
+            connectivity::check(repo, &self.ref_map.mappings, filter.is_some())?;
+        }
+
+        let ref_edits_dry_run = if self.update_refs {
+            self.dry_run
+        } else {
+            fetch::DryRun::Yes
+        };
+        let pruned_refs = if self.prune.map_or_else(|| config::prune(repo, remote_name), Ok)? {
+            let prune_tags = self
+                .prune_tags
+                .map_or_else(|| config::prune_tags(repo, remote_name), Ok)?;
+            prune::remove_stale_refs(repo, con.remote, &self.ref_map, prune_tags, ref_edits_dry_run)?
+        } else {
+            Vec::new()
+        };
+
         let update_refs = refs::update(
             repo,
             self.reflog_message
@@ -290,18 +477,49 @@ where
             con.remote.refspecs(remote::Direction::Fetch),
             &self.ref_map.extra_refspecs,
             con.remote.fetch_tags,
-            self.dry_run,
+            ref_edits_dry_run,
             self.write_packed_refs,
         )?;
 
         if let Some(bundle) = write_pack_bundle.as_mut() {
-            if !update_refs.edits.is_empty() || bundle.index.num_objects == 0 {
+            if (self.update_refs && !update_refs.edits.is_empty()) || bundle.index.num_objects == 0 {
                 if let Some(path) = bundle.keep_path.take() {
                     std::fs::remove_file(&path).map_err(|err| Error::RemovePackKeepFile { path, source: err })?;
                 }
             }
+            if bundle.index.num_objects != 0
+                && self
+                    .write_commit_graph
+                    .map_or_else(|| config::write_commit_graph(repo), Ok)?
+            {
+                if let Err(err) = commit_graph::write(repo) {
+                    log::warn!("failed to write commit-graph after fetch, continuing without: {err}");
+                }
+            }
         }
 
+        if let (Some(local_branch), fetch::DryRun::No) = (&self.set_upstream, &ref_edits_dry_run) {
+            upstream::set(repo, con.remote, local_branch, &self.ref_map.mappings)?;
+        }
+
+        if let (Some(filter), Some(remote_name), fetch::DryRun::No) = (&filter, remote_name, &ref_edits_dry_run) {
+            promisor::register(repo, remote_name, filter)?;
+        }
+
+        if matches!(ref_edits_dry_run, fetch::DryRun::No)
+            && self
+                .write_fetch_head
+                .map_or_else(|| config::write_fetch_head(repo), Ok)?
+        {
+            fetch_head::write(repo, con.remote, &self.ref_map.mappings, self.append_fetch_head)?;
+        }
+
+        let estimate = matches!(self.dry_run, fetch::DryRun::Yes).then(|| {
+            num_objects_announced.map_or_else(
+                || outcome::Estimate::RefDelta(num_missing_ref_targets(repo, &self.ref_map)),
+                outcome::Estimate::Objects,
+            )
+        });
         let out = Outcome {
             ref_map: std::mem::take(&mut self.ref_map),
             status: match write_pack_bundle {
@@ -313,14 +531,58 @@ where
                 None => Status::NoPackReceived {
                     dry_run: matches!(self.dry_run, fetch::DryRun::Yes),
                     negotiate,
+                    estimate,
                     update_refs,
                 },
             },
+            pruned_refs,
+            bundles,
         };
         Ok(out)
     }
 }
 
+/// Return the amount of distinct objects the remote refs in `ref_map` point to which don't exist in `repo`.
+fn num_missing_ref_targets(repo: &Repository, ref_map: &fetch::RefMap) -> usize {
+    let mut ids: Vec<_> = ref_map
+        .mappings
+        .iter()
+        .filter_map(|m| m.remote.as_id())
+        .filter(|id| !repo.objects.contains(id))
+        .map(ToOwned::to_owned)
+        .collect();
+    ids.sort();
+    ids.dedup();
+    ids.len()
+}
+
+/// Write all objects of the freshly received pack in `bundle` as loose objects and remove all of its files afterwards.
+fn unpack_into_loose_objects(repo: &Repository, bundle: &mut gix_pack::bundle::write::Outcome) -> Result<(), Error> {
+    use gix_odb::Write;
+    let pack = match bundle.to_bundle() {
+        Some(pack) => pack?,
+        None => return Ok(()),
+    };
+    let mut buf = Vec::new();
+    for idx in 0..pack.index.num_objects() {
+        let (object, _location) = pack.get_object_by_index(idx, &mut buf, &mut gix_pack::cache::Never)?;
+        repo.objects.write_buf(object.kind, object.data)?;
+    }
+    drop(pack);
+
+    for path in [
+        bundle.data_path.take(),
+        bundle.index_path.take(),
+        bundle.keep_path.take(),
+    ]
+    .into_iter()
+    .flatten()
+    {
+        std::fs::remove_file(&path).map_err(|err| Error::RemoveUnpackedPackFile { path, source: err })?;
+    }
+    Ok(())
+}
+
 fn acquire_shallow_lock(repo: &Repository) -> Result<gix_lock::File, Error> {
     gix_lock::File::acquire_to_update_resource(repo.shallow_file(), gix_lock::acquire::Fail::Immediately, None)
         .map_err(Into::into)
@@ -515,6 +515,37 @@ mod update {
         }
     }
 
+    #[test]
+    fn rejections_and_forced_updates_come_with_a_reason() {
+        use fetch::refs::update::Mode;
+        let out = fetch::refs::update::Outcome {
+            edits: Vec::new(),
+            updates: vec![
+                Mode::RejectedNonFastForward.into(),
+                Mode::RejectedTagUpdate.into(),
+                Mode::FastForward.into(),
+            ],
+        };
+
+        let reasons: Vec<_> = out.updates.iter().map(|update| update.mode.reason()).collect();
+        assert_eq!(
+            reasons,
+            [
+                Some("the local ref isn't an ancestor of the remote one, prefix the refspec with '+' to force the update"),
+                Some("existing tags are never changed unless the refspec is prefixed with '+'"),
+                None
+            ]
+        );
+        assert_ne!(
+            reasons[0], reasons[1],
+            "non-fast-forwards and tag updates are rejected for different reasons"
+        );
+        assert_eq!(
+            Mode::Forced.reason(),
+            Some("the previous value was overwritten as the refspec allows non-fast-forward updates")
+        );
+    }
+
     #[test]
     fn fast_forwards_are_called_out_even_if_force_is_given() {
         let (repo, _tmp) = repo_rw("two-origins");
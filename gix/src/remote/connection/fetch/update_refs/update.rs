@@ -97,6 +97,29 @@ impl std::fmt::Display for Mode {
     }
 }
 
+impl Mode {
+    /// Return a human-readable explanation of why a ref was rejected or forcefully updated, or `None` if the update
+    /// needs no further explanation beyond its [`Display`][std::fmt::Display] representation.
+    pub fn reason(&self) -> Option<&'static str> {
+        Some(match self {
+            Mode::Forced => "the previous value was overwritten as the refspec allows non-fast-forward updates",
+            Mode::RejectedNonFastForward => {
+                "the local ref isn't an ancestor of the remote one, prefix the refspec with '+' to force the update"
+            }
+            Mode::RejectedTagUpdate => "existing tags are never changed unless the refspec is prefixed with '+'",
+            Mode::RejectedSymbolic => "fetching never changes local symbolic refs",
+            Mode::RejectedCurrentlyCheckedOut { .. } => {
+                "the branch would change underneath its worktree, check out a different branch there first"
+            }
+            Mode::NoChangeNeeded
+            | Mode::FastForward
+            | Mode::New
+            | Mode::ImplicitTagNotSentByRemote
+            | Mode::RejectedSourceObjectNotFound { .. } => return None,
+        })
+    }
+}
+
 impl Outcome {
     /// Produce an iterator over all information used to produce the this outcome, ref-update by ref-update, using the `mappings`
     /// used when producing the ref update.
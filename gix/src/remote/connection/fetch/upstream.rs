@@ -0,0 +1,83 @@
+use std::convert::TryInto;
+
+use super::Error;
+use crate::{
+    bstr::{BStr, BString, ByteSlice},
+    remote,
+    remote::fetch,
+    Repository,
+};
+
+/// Fail if `local_branch` doesn't exist in `repo`.
+pub(crate) fn assure_branch_exists(repo: &Repository, local_branch: &str) -> Result<(), Error> {
+    let name = format!("refs/heads/{local_branch}");
+    match repo
+        .try_find_reference(name.as_str())
+        .map_err(Error::SetUpstreamFindBranch)?
+    {
+        Some(_) => Ok(()),
+        None => Err(Error::SetUpstreamBranchMissing {
+            name: local_branch.into(),
+        }),
+    }
+}
+
+/// Write `branch.<local_branch>.remote` and `branch.<local_branch>.merge` to the local configuration file of `repo` so that
+/// `local_branch` tracks the only remote branch in `mappings`, or the one with the same name if there are multiple.
+///
+/// Note that the configuration of `repo` isn't updated in memory.
+pub(crate) fn set(
+    repo: &Repository,
+    remote: &crate::Remote<'_>,
+    local_branch: &str,
+    mappings: &[fetch::Mapping],
+) -> Result<(), Error> {
+    let mut remote_branches: Vec<&BStr> = mappings
+        .iter()
+        .filter_map(|m| match &m.remote {
+            fetch::Source::Ref(r) => Some(r.unpack().0),
+            fetch::Source::ObjectId(_) => None,
+        })
+        .filter(|name| name.starts_with_str("refs/heads/"))
+        .collect();
+    remote_branches.sort();
+    remote_branches.dedup();
+    let remote_branch = match remote_branches.as_slice() {
+        [single] => *single,
+        candidates => {
+            let same_name = format!("refs/heads/{local_branch}");
+            candidates
+                .iter()
+                .copied()
+                .find(|name| *name == same_name.as_str())
+                .ok_or(Error::SetUpstreamNoUniqueRemoteBranch {
+                    num_candidates: candidates.len(),
+                })?
+        }
+    };
+    let remote_name: BString = match remote.name() {
+        Some(name) => name.as_bstr().into(),
+        None => remote
+            .url(remote::Direction::Fetch)
+            .map(gix_url::Url::to_bstring)
+            .unwrap_or_default(),
+    };
+
+    let config_path = repo.common_dir().join("config");
+    let mut config = gix_config::File::from_path_no_includes(config_path.clone(), gix_config::Source::Local)
+        .map_err(Error::SetUpstreamReadConfig)?;
+    let mut section = config
+        .section_mut_or_create_new("branch", Some(local_branch.into()))
+        .expect("section header name is always valid per naming rules, our input branch name is valid");
+    section.set(
+        "remote".try_into().expect("valid at compile time"),
+        remote_name.as_bstr(),
+    );
+    section.set("merge".try_into().expect("valid at compile time"), remote_branch);
+
+    let mut lock = gix_lock::File::acquire_to_update_resource(&config_path, gix_lock::acquire::Fail::Immediately, None)
+        .map_err(Error::SetUpstreamLockConfig)?;
+    config.write_to(&mut lock).map_err(Error::SetUpstreamWriteConfig)?;
+    lock.commit().map_err(|err| Error::SetUpstreamWriteConfig(err.error))?;
+    Ok(())
+}
@@ -1,7 +1,7 @@
 use std::collections::HashSet;
 
 use gix_features::progress::Progress;
-use gix_protocol::transport::client::Transport;
+use gix_protocol::transport::{client::Transport, IsSpuriousError};
 
 use crate::{
     bstr,
@@ -32,6 +32,8 @@ pub enum Error {
     ConfigureCredentials(#[from] crate::config::credential_helpers::Error),
     #[error(transparent)]
     MappingValidation(#[from] gix_refspec::match_group::validate::Error),
+    #[error("Could not obtain configuration to learn how often to retry after transient network errors")]
+    MaxRetriesConfig(#[source] crate::config::unsigned_integer::Error),
 }
 
 impl gix_protocol::transport::IsSpuriousError for Error {
@@ -59,6 +61,17 @@ pub struct Options {
     ///
     /// This is useful for handling `remote.<name>.tagOpt` for example.
     pub extra_refspecs: Vec<gix_refspec::RefSpec>,
+    /// Object ids to fetch in addition to what the ref-specs select, like commits that no ref on the remote points to.
+    ///
+    /// Each of them is sent as `want <id>` and is equivalent to an additional ref-spec consisting of just the object id.
+    /// Note that servers only send objects that aren't advertised with protocol V1 if they are configured to do so with
+    /// `uploadpack.allowReachableSHA1InWant` or `uploadpack.allowAnySHA1InWant`, and the fetch fails otherwise.
+    pub extra_wants: Vec<gix_hash::ObjectId>,
+    /// The amount of times to retry listing refs if it failed due to a [spurious][gix_protocol::transport::IsSpuriousError]
+    /// network error, waiting increasingly long in between attempts.
+    ///
+    /// If `None`, `gitoxide.fetch.maxRetries` is used, and no retries are performed if it's unset.
+    pub max_retries: Option<u32>,
 }
 
 impl Default for Options {
@@ -67,6 +80,8 @@ impl Default for Options {
             prefix_from_spec_as_filter_on_remote: true,
             handshake_parameters: Vec::new(),
             extra_refspecs: Vec::new(),
+            extra_wants: Vec::new(),
+            max_retries: None,
         }
     }
 }
@@ -91,6 +106,11 @@ where
     /// ### Configuration
     ///
     /// - `gitoxide.userAgent` is read to obtain the application user agent for git servers and for HTTP servers as well.
+    /// - `gitoxide.fetch.maxRetries` is read to learn how often to retry after transient network errors, unless
+    ///   [`Options::max_retries`] is set.
+    ///
+    /// The wait between retries doubles with each attempt, starting at 100ms. The async implementation only retries
+    /// with the `async-network-client-async-std` feature as there is no other way to wait.
     #[allow(clippy::result_large_err)]
     #[gix_protocol::maybe_async::maybe_async]
     pub async fn ref_map(mut self, progress: impl Progress, options: Options) -> Result<fetch::RefMap, Error> {
@@ -105,11 +125,13 @@ where
     #[gix_protocol::maybe_async::maybe_async]
     pub(crate) async fn ref_map_inner(
         &mut self,
-        progress: impl Progress,
+        mut progress: impl Progress,
         Options {
             prefix_from_spec_as_filter_on_remote,
             handshake_parameters,
             mut extra_refspecs,
+            extra_wants,
+            max_retries,
         }: Options,
     ) -> Result<fetch::RefMap, Error> {
         let null = gix_hash::ObjectId::null(gix_hash::Kind::Sha1); // OK to hardcode Sha1, it's not supposed to match, ever.
@@ -119,19 +141,50 @@ where
                 extra_refspecs.push(tag_spec);
             }
         };
+        for id in extra_wants {
+            let hex = id.to_string();
+            let want_spec = gix_refspec::parse(hex.as_str().into(), gix_refspec::parse::Operation::Fetch)
+                .expect("a full hex object id is a valid ref-spec")
+                .to_owned();
+            if !extra_refspecs.contains(&want_spec) {
+                extra_refspecs.push(want_spec);
+            }
+        }
         let specs = {
             let mut s = self.remote.fetch_specs.clone();
             s.extend(extra_refspecs.clone());
             s
         };
-        let remote = self
-            .fetch_refs(
-                prefix_from_spec_as_filter_on_remote,
-                handshake_parameters,
-                &specs,
-                progress,
-            )
-            .await?;
+        let max_retries = match max_retries {
+            Some(retries) => retries,
+            None => configured_max_retries(self.remote.repo)?,
+        };
+        let mut retries = 0;
+        let remote = loop {
+            match self
+                .fetch_refs(
+                    prefix_from_spec_as_filter_on_remote,
+                    handshake_parameters.clone(),
+                    &specs,
+                    &mut progress,
+                )
+                .await
+            {
+                Err(err) if retries < max_retries && err.is_spurious() && CAN_WAIT_BEFORE_RETRY => {
+                    retries += 1;
+                    let wait = std::time::Duration::from_millis(100 << (retries - 1).min(7));
+                    progress.info(format!(
+                        "Retrying in {:.1}s ({retries}/{max_retries}) after transient error: {err}",
+                        wait.as_secs_f32()
+                    ));
+                    #[cfg(feature = "blocking-network-client")]
+                    std::thread::sleep(wait);
+                    #[cfg(all(not(feature = "blocking-network-client"), feature = "async-network-client-async-std"))]
+                    async_std::task::sleep(wait).await;
+                }
+                res => break res?,
+            }
+        };
         let num_explicit_specs = self.remote.fetch_specs.len();
         let group = gix_refspec::MatchGroup::from_fetch_specs(specs.iter().map(gix_refspec::RefSpec::to_ref));
         let (res, fixes) = group
@@ -249,6 +302,31 @@ where
     }
 }
 
+/// Retrying without waiting in between is pointless, so without a way to wait we don't retry at all.
+const CAN_WAIT_BEFORE_RETRY: bool = cfg!(any(
+    feature = "blocking-network-client",
+    feature = "async-network-client-async-std"
+));
+
+/// Return the amount of times listing refs should be retried after spurious errors, as configured in `repo`.
+#[allow(clippy::result_large_err)]
+fn configured_max_retries(repo: &crate::Repository) -> Result<u32, Error> {
+    use crate::config::{
+        cache::util::ApplyLeniency,
+        tree::{gitoxide, Key},
+    };
+    let key = &gitoxide::Fetch::MAX_RETRIES;
+    Ok(repo
+        .config
+        .resolved
+        .integer_filter_by_key(key.logical_name().as_str(), &mut repo.filter_config_section())
+        .map(|value| key.try_into_u32(value))
+        .transpose()
+        .with_leniency(repo.options.lenient_config)
+        .map_err(Error::MaxRetriesConfig)?
+        .unwrap_or_default())
+}
+
 /// Assume sha1 if server says nothing, otherwise configure anything beyond sha1 in the local repo configuration
 #[allow(clippy::result_large_err)]
 fn extract_object_format(
@@ -1,6 +1,9 @@
 use std::collections::BTreeSet;
 
-use crate::{bstr::ByteSlice, config};
+use crate::{
+    bstr::{BStr, ByteSlice},
+    config,
+};
 
 /// General Configuration
 impl crate::Repository {
@@ -22,6 +25,51 @@ impl crate::Repository {
         }
     }
 
+    /// Return `true` if the advice controlled by `key` should be shown to the user, with `key` being one of
+    /// the keys of the [`Advice`][config::tree::Advice] section.
+    ///
+    /// Advice is shown unless it was disabled in the configuration.
+    pub fn advice(&self, key: &config::tree::keys::Boolean) -> Result<bool, config::boolean::Error> {
+        self.config.advice(key)
+    }
+
+    /// Return the options to use when comparing the stat information of index entries with files in the worktree,
+    /// as configured by `core.checkStat`, `core.trustCTime` and their `gitoxide.core.*` counterparts.
+    ///
+    /// With `core.checkStat=minimal`, [`check_stat`][gix_index::entry::stat::Options::check_stat] is `false`
+    /// to only compare file sizes and modification times, which helps on filesystems with unreliable metadata.
+    pub fn stat_options(&self) -> Result<gix_index::entry::stat::Options, config::stat_options::Error> {
+        self.config.stat_options()
+    }
+
+    /// Return how the untracked cache extension of the index should be treated when writing it, as configured
+    /// by `core.untrackedCache`, which is to [keep it][config::tree::core::UntrackedCacheMode::Keep] by default.
+    pub fn untracked_cache(
+        &self,
+    ) -> Result<config::tree::core::UntrackedCacheMode, config::key::GenericErrorWithValue> {
+        self.config.untracked_cache()
+    }
+
+    /// Return `true` if `core.splitIndex` is enabled, which means that the index should be written as split index,
+    /// with a shared index file containing most of the entries.
+    pub fn split_index(&self) -> Result<bool, config::boolean::Error> {
+        self.config.split_index()
+    }
+
+    /// Return `true` if `core.sparseCheckout` is enabled, which means that the `info/sparse-checkout` file should be
+    /// used to decide which paths are present in the worktree.
+    pub fn sparse_checkout(&self) -> Result<bool, config::boolean::Error> {
+        self.config.sparse_checkout()
+    }
+
+    /// Return `true` if `core.sparseCheckoutCone` is enabled, which means that the `info/sparse-checkout` file contains
+    /// directories in _cone mode_ instead of patterns like those found in `.gitignore` files.
+    ///
+    /// This is only relevant if [`sparse_checkout()`](Self::sparse_checkout()) is `true`.
+    pub fn sparse_checkout_cone(&self) -> Result<bool, config::boolean::Error> {
+        self.config.sparse_checkout_cone()
+    }
+
     /// The options used to open the repository.
     pub fn open_options(&self) -> &crate::open::Options {
         &self.options
@@ -34,14 +82,17 @@ impl crate::Repository {
     ) -> Result<gix_protocol::transport::client::ssh::connect::Options, config::ssh_connect_options::Error> {
         use crate::config::{
             cache::util::ApplyLeniency,
-            tree::{gitoxide, Core, Ssh},
+            tree::{gitoxide, Ssh},
         };
 
         let config = &self.config.resolved;
         let mut trusted = self.filter_config_section();
         let mut fallback_active = false;
-        let ssh_command = config
-            .string_filter("core", None, Core::SSH_COMMAND.name, &mut trusted)
+        let ssh_command = self
+            .config
+            .ssh_command
+            .as_ref()
+            .map(|cmd| std::borrow::Cow::Borrowed(cmd.as_bstr()))
             .or_else(|| {
                 fallback_active = true;
                 config.string_filter(
@@ -64,6 +115,23 @@ impl crate::Repository {
         Ok(opts)
     }
 
+    /// Return the command to use when connecting via `ssh` as configured by `core.sshCommand`, or by `GIT_SSH_COMMAND`
+    /// which takes precedence if the environment may be used.
+    ///
+    /// It's `None` if it's unset or empty.
+    pub fn ssh_command(&self) -> Option<&BStr> {
+        self.config.ssh_command.as_ref().map(|cmd| cmd.as_bstr())
+    }
+
+    /// Return the directory containing hooks as configured by `core.hooksPath`, which defaults to the `hooks` directory
+    /// in the [`git_dir()`][crate::Repository::git_dir()].
+    ///
+    /// A relative path is resolved against the [`work_dir()`][crate::Repository::work_dir()], or the `git_dir()` if the
+    /// repository is bare, as this is where `git` runs hooks from.
+    pub fn hooks_path(&self) -> Result<std::path::PathBuf, gix_config::path::interpolate::Error> {
+        self.config.hooks_path(self.git_dir(), self.work_dir())
+    }
+
     /// The kind of object hash the repository is configured to use.
     pub fn object_hash(&self) -> gix_hash::Kind {
         self.config.object_hash
@@ -121,6 +121,57 @@ impl crate::Repository {
                             .map_err(Into::into)
                     }
 
+                    /// Obtain the boolean at `key` from the `http.<url>` section whose url matches `url` most specifically,
+                    /// falling back to the one in the `http` section.
+                    ///
+                    /// Like in `git`, the user of `url` only has to match if the section url mentions one, and passwords are ignored.
+                    fn url_scoped_boolean(
+                        config: &gix_config::File<'static>,
+                        key: &'static config::tree::keys::Boolean,
+                        url: &gix_url::Url,
+                        mut filter: fn(&gix_config::file::Metadata) -> bool,
+                    ) -> Option<Result<bool, config::boolean::Error>> {
+                        use crate::bstr::{ByteSlice, ByteVec};
+
+                        let mut url = url.clone();
+                        let user = url.set_user(None);
+                        let url_without_user = url.to_bstring();
+                        let url_with_user = user.map(|user| {
+                            let mut url = url_without_user.clone();
+                            let host_start = url.find("://").map_or(0, |pos| pos + 3);
+                            url.insert_str(host_start, format!("{user}@"));
+                            url
+                        });
+                        let is_prefix_of = |url: &[u8], scope: &[u8]| {
+                            let url = url.trim_end_with(|c| c == '/');
+                            url.starts_with(scope) && matches!(url.get(scope.len()), None | Some(b'/'))
+                        };
+
+                        let mut best_match = None;
+                        for section in config.sections_by_name_and_filter("http", &mut filter)? {
+                            let scope = match section.header().subsection_name() {
+                                Some(scope) => scope.trim_end_with(|c| c == '/'),
+                                None => continue,
+                            };
+                            let is_match = is_prefix_of(&url_without_user, scope)
+                                || url_with_user.as_ref().map_or(false, |url| is_prefix_of(url, scope));
+                            if !is_match || best_match.as_ref().map_or(false, |(len, _)| *len > scope.len()) {
+                                continue;
+                            }
+                            if let Some(value) = section.value_implicit(key.name) {
+                                best_match = Some((scope.len(), value));
+                            }
+                        }
+                        match best_match {
+                            Some((_, value)) => Some(key.enrich_error(
+                                value.map_or(Ok(true), |v| gix_config::Boolean::try_from(v).map(Into::into)),
+                            )),
+                            None => config
+                                .boolean_filter("http", None, key.name, &mut filter)
+                                .map(|res| key.enrich_error(res)),
+                        }
+                    }
+
                     fn proxy(
                         value: Option<(Cow<'_, BStr>, Cow<'static, BStr>, &'static config::tree::keys::String)>,
                         lenient: bool,
@@ -366,6 +417,63 @@ impl crate::Repository {
                             .map_err(|err| config::transport::Error::InterpolatePath { source: err, key })?;
                     }
 
+                    {
+                        let key = "http.sslCAPath";
+                        debug_assert_eq!(key, config::tree::Http::SSL_CA_PATH.logical_name());
+                        opts.ssl_ca_path = config
+                            .path_filter_by_key(key, &mut trusted_only)
+                            .map(|p| {
+                                use crate::config::cache::interpolate_context;
+                                p.interpolate(interpolate_context(
+                                    self.install_dir().ok().as_deref(),
+                                    self.config.home_dir().as_deref(),
+                                ))
+                                .map(std::borrow::Cow::into_owned)
+                            })
+                            .transpose()
+                            .with_leniency(lenient)
+                            .map_err(|err| config::transport::Error::InterpolatePath { source: err, key })?;
+                    }
+
+                    {
+                        let key = "http.cookieFile";
+                        debug_assert_eq!(key, config::tree::Http::COOKIE_FILE.logical_name());
+                        opts.cookie_file = config
+                            .path_filter_by_key(key, &mut trusted_only)
+                            .map(|p| {
+                                use crate::config::cache::interpolate_context;
+                                p.interpolate(interpolate_context(
+                                    self.install_dir().ok().as_deref(),
+                                    self.config.home_dir().as_deref(),
+                                ))
+                                .map(std::borrow::Cow::into_owned)
+                            })
+                            .transpose()
+                            .with_leniency(lenient)
+                            .map_err(|err| config::transport::Error::InterpolatePath { source: err, key })?
+                            .filter(|path| !path.as_os_str().is_empty());
+                    }
+                    opts.save_cookies = config
+                        .boolean_filter_by_key("http.saveCookies", &mut trusted_only)
+                        .map(|value| config::tree::Http::SAVE_COOKIES.enrich_error(value))
+                        .transpose()
+                        .with_leniency(lenient)
+                        .map_err(config::transport::http::Error::from)?
+                        .unwrap_or_default();
+
+                    opts.ssl_verify = url_scoped_boolean(config, &config::tree::Http::SSL_VERIFY, &url, trusted_only)
+                        .transpose()
+                        .with_leniency(lenient)
+                        .map_err(config::transport::http::Error::from)?
+                        .unwrap_or(true);
+                    if !opts.ssl_verify {
+                        if cfg!(feature = "blocking-http-transport-curl") {
+                            log::warn!("http.sslVerify is false, TLS certificates and host names won't be verified");
+                        } else {
+                            log::warn!("http.sslVerify is false, but this is ignored as the HTTP backend always verifies TLS certificates and host names");
+                        }
+                    }
+
                     {
                         opts.ssl_version = ssl_version(
                             config,
@@ -47,6 +47,7 @@ mod init;
 mod kind;
 mod location;
 mod object;
+mod promisor;
 mod reference;
 mod remote;
 mod revision;
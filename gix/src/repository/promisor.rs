@@ -0,0 +1,23 @@
+use gix_odb::Find;
+
+use crate::Repository;
+
+impl Repository {
+    /// Return `true` if this repository is a partial clone, i.e. if at least one remote is configured as promisor remote
+    /// with `remote.<name>.promisor=true`, which promises to provide the objects that were filtered when fetching from it.
+    ///
+    /// Invalid values are treated as `false`.
+    pub fn is_promisor(&self) -> bool {
+        self.config.is_promisor()
+    }
+
+    /// Return `true` if the object with `id` is absent from the object database, and this is expected as the repository is
+    /// a [partial clone][Self::is_promisor()] whose promisor remotes can provide it.
+    ///
+    /// This allows to tell objects that are missing due to filtering apart from those that are missing due to corruption.
+    /// Note that unlike `git`, we don't check if the object is referenced by an object received from a promisor remote, so any
+    /// missing object is considered promised in a partial clone.
+    pub fn is_promisor_missing(&self, id: impl AsRef<gix_hash::oid>) -> bool {
+        self.is_promisor() && !self.objects.contains(id)
+    }
+}
@@ -624,3 +624,32 @@ fn clone_into_empty_directory_and_early_drop() -> crate::Result {
     assert!(!head.is_file(), "we cleanup if the clone isn't followed through");
     Ok(())
 }
+
+#[test]
+fn clone_copies_the_configured_template_dir_into_the_new_repository() -> crate::Result {
+    let template = gix_testtools::tempfile::TempDir::new()?;
+    std::fs::create_dir(template.path().join("hooks"))?;
+    std::fs::write(template.path().join("hooks").join("pre-commit"), b"#!/bin/sh\nexit 0\n")?;
+    std::fs::write(template.path().join("config"), b"[core]\n\tbare = false\n")?;
+
+    let tmp = gix_testtools::tempfile::TempDir::new()?;
+    let repo = gix::clone::PrepareFetch::new(
+        remote::repo("base").path(),
+        tmp.path(),
+        gix::create::Kind::Bare,
+        Default::default(),
+        gix::open::Options::isolated().config_overrides([format!("init.templateDir={}", template.path().display())]),
+    )?
+    .persist();
+    assert_eq!(
+        std::fs::read(repo.git_dir().join("hooks").join("pre-commit"))?,
+        b"#!/bin/sh\nexit 0\n",
+        "the hook of the template was copied"
+    );
+    assert!(
+        repo.git_dir().join("hooks").join("update.sample").is_file(),
+        "existing files are kept"
+    );
+    assert!(repo.is_bare(), "the template configuration is ignored");
+    Ok(())
+}
@@ -465,6 +465,39 @@ mod core {
         Ok(())
     }
 
+    #[test]
+    fn untracked_cache() -> crate::Result {
+        use gix::config::tree::core::UntrackedCacheMode;
+        for (value, expected) in [
+            ("keep", UntrackedCacheMode::Keep),
+            ("true", UntrackedCacheMode::Enabled),
+            ("yes", UntrackedCacheMode::Enabled),
+            ("false", UntrackedCacheMode::Disabled),
+            ("off", UntrackedCacheMode::Disabled),
+        ] {
+            assert_eq!(
+                Core::UNTRACKED_CACHE.try_into_untracked_cache(bcow(value)).unwrap(),
+                expected
+            );
+            assert!(Core::UNTRACKED_CACHE.validate(value.into()).is_ok());
+        }
+        assert_eq!(
+            Core::UNTRACKED_CACHE
+                .try_into_untracked_cache(bcow("Keep"))
+                .unwrap_err()
+                .to_string(),
+            "The key \"core.untrackedCache=Keep\" was invalid"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn split_index() {
+        assert!(Core::SPLIT_INDEX.validate("true".into()).is_ok());
+        assert!(Core::SPLIT_INDEX.validate("false".into()).is_ok());
+        assert!(Core::SPLIT_INDEX.validate("keep".into()).is_err());
+    }
+
     #[test]
     fn check_round_trip_encoding() -> crate::Result {
         for (value, expected) in [
@@ -603,6 +636,23 @@ mod pack {
         assert!(Pack::INDEX_VERSION.validate("-1".into()).is_err());
         Ok(())
     }
+
+    #[test]
+    fn sizes_with_unit_suffix() -> crate::Result {
+        for key in [&Pack::DELTA_CACHE_SIZE, &Pack::PACK_SIZE_LIMIT] {
+            for (value, expected) in [("0", 0), ("1024", 1024), ("1k", 1024), ("256m", 256 * 1024 * 1024)] {
+                assert!(key.validate(value.into()).is_ok());
+                assert_eq!(key.try_into_usize(integer(value))?, expected);
+            }
+            assert!(key.validate("-1".into()).is_err());
+            assert!(key.validate("256x".into()).is_err());
+        }
+        Ok(())
+    }
+
+    fn integer(value: &str) -> Result<i64, gix_config::value::Error> {
+        gix_config::Integer::try_from(gix::bstr::BStr::new(value)).map(|int| int.to_decimal().expect("in range"))
+    }
 }
 
 mod protocol {
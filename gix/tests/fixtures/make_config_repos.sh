@@ -16,8 +16,11 @@ git init http-config
   git config gitoxide.http.connectTimeout 60k
   git config http.schannelCheckRevoke true
   git config http.sslCAInfo ./CA.pem
+  git config http.sslCAPath ./certs
   git config http.sslVersion sslv2
   git config http.version HTTP/1.1
+  git config http.cookieFile ./cookies
+  git config http.saveCookies true
 )
 
 git clone --shared http-config http-remote-override
@@ -134,6 +137,20 @@ git init http-proxy-authenticated
 EOF
 )
 
+git init http-ssl-verify
+(cd http-ssl-verify
+  git config http.sslVerify false
+  git config http.https://example.com/scoped.sslVerify true
+  git config http.https://example.com/scoped/deeper.sslVerify false
+  git config http.https://bob@example.com/user.sslVerify true
+)
+
+git init http-proxy-remote-authenticated
+(cd http-proxy-remote-authenticated
+  git config http.proxy localhost:9090
+  git config remote.origin.proxy user:pass@remote-proxy:8080
+)
+
 git init object-caches
 (cd object-caches
   git config core.deltaBaseCacheLimit 128m
@@ -163,3 +180,6 @@ mkdir not-a-repo-with-files;
 (cd not-a-repo-with-files
   touch this that
 )
+
+git init unsupported-repository-format-version
+git config --file unsupported-repository-format-version/.git/config core.repositoryFormatVersion 2
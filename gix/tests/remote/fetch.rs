@@ -108,6 +108,1093 @@ mod blocking_and_async_io {
         Ok(())
     }
 
+    #[test]
+    #[cfg(feature = "blocking-network-client")]
+    fn fetch_writes_commit_graph_if_configured() -> crate::Result {
+        for (enabled, override_config) in [(true, None), (false, None), (true, Some(false))] {
+            let (mut repo, _tmp) = repo_rw("two-origins");
+            let info_dir = repo.objects.store_ref().path().join("info");
+            remove_commit_graphs(&info_dir)?;
+            repo.config_snapshot_mut().set_value(
+                &gix::config::tree::Fetch::WRITE_COMMIT_GRAPH,
+                enabled.to_string().as_str(),
+            )?;
+
+            let remote = repo
+                .find_remote("changes-on-top-of-origin")?
+                .with_fetch_tags(fetch::Tags::None);
+            let outcome = remote
+                .connect(Fetch)?
+                .prepare_fetch(gix::progress::Discard, Default::default())?
+                .with_write_commit_graph(override_config)
+                .receive(gix::progress::Discard, &AtomicBool::default())?;
+            assert!(matches!(outcome.status, Status::Change { .. }), "a pack was received");
+
+            let expect_graph = enabled && override_config.unwrap_or(true);
+            let chain_path = info_dir.join("commit-graphs").join("commit-graph-chain");
+            assert_eq!(
+                chain_path.is_file(),
+                expect_graph,
+                "like git, a split commit-graph is written"
+            );
+            assert!(!info_dir.join("commit-graph").exists());
+            if expect_graph {
+                assert_eq!(std::fs::read_to_string(&chain_path)?.lines().count(), 1);
+                let graph = repo.commit_graph()?;
+                let fetched_tip = repo.find_reference("refs/remotes/changes-on-top-of-origin/main")?.id();
+                assert!(
+                    graph.lookup(fetched_tip).is_some(),
+                    "the newly fetched commit is part of the commit-graph"
+                );
+                assert!(
+                    graph.lookup(repo.head_id()?).is_some(),
+                    "previously existing commits are contained as well"
+                );
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "blocking-network-client")]
+    fn fetch_adds_only_new_commits_to_an_existing_commit_graph() -> crate::Result {
+        for split in [true, false] {
+            let (mut repo, _tmp) = repo_rw("two-origins");
+            let info_dir = repo.objects.store_ref().path().join("info");
+            remove_commit_graphs(&info_dir)?;
+            let known_commits = commits_reachable_from_refs(&repo)?;
+            let num_known_commits = known_commits.len() as u32;
+
+            let mut buf = Vec::new();
+            let base = gix::commitgraph::File::write_to(known_commits, &mut buf, repo.object_hash())?;
+            let graphs_dir = info_dir.join("commit-graphs");
+            if split {
+                std::fs::create_dir_all(&graphs_dir)?;
+                std::fs::write(graphs_dir.join(format!("graph-{}.graph", base.checksum)), &buf)?;
+                std::fs::write(graphs_dir.join("commit-graph-chain"), format!("{}\n", base.checksum))?;
+            } else {
+                std::fs::create_dir_all(&info_dir)?;
+                std::fs::write(info_dir.join("commit-graph"), &buf)?;
+            }
+            repo.config_snapshot_mut()
+                .set_value(&gix::config::tree::Fetch::WRITE_COMMIT_GRAPH, "true")?;
+
+            let remote = repo
+                .find_remote("changes-on-top-of-origin")?
+                .with_fetch_tags(fetch::Tags::None);
+            let outcome = remote
+                .connect(Fetch)?
+                .prepare_fetch(gix::progress::Discard, Default::default())?
+                .receive(gix::progress::Discard, &AtomicBool::default())?;
+            assert!(matches!(outcome.status, Status::Change { .. }), "a pack was received");
+
+            let graph = repo.commit_graph()?;
+            assert_eq!(
+                graph.num_commits(),
+                num_known_commits + 1,
+                "only the newly fetched commit was added"
+            );
+            graph.verify_integrity(|_| Ok::<_, std::convert::Infallible>(()))?;
+            let fetched_tip = repo.find_reference("refs/remotes/changes-on-top-of-origin/main")?.id();
+            assert!(graph.lookup(fetched_tip).is_some());
+
+            if split {
+                let chain = std::fs::read_to_string(graphs_dir.join("commit-graph-chain"))?;
+                let layers: Vec<_> = chain.lines().collect();
+                assert_eq!(
+                    layers.len(),
+                    2,
+                    "a new layer was added on top of the much larger existing one"
+                );
+                assert_eq!(layers[0], base.checksum.to_string(), "the existing layer is kept as is");
+                let new_layer = gix::commitgraph::File::at(graphs_dir.join(format!("graph-{}.graph", layers[1])))?;
+                assert_eq!(new_layer.num_commits(), 1);
+                assert_eq!(new_layer.base_graph_count(), 1);
+                assert!(!info_dir.join("commit-graph").exists());
+            } else {
+                assert!(
+                    !graphs_dir.exists(),
+                    "an existing single commit-graph file is rewritten instead"
+                );
+            }
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "blocking-network-client")]
+    fn remove_commit_graphs(info_dir: &std::path::Path) -> std::io::Result<()> {
+        let graph_path = info_dir.join("commit-graph");
+        if graph_path.is_file() {
+            std::fs::remove_file(&graph_path)?;
+        }
+        let graphs_dir = info_dir.join("commit-graphs");
+        if graphs_dir.is_dir() {
+            std::fs::remove_dir_all(&graphs_dir)?;
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "blocking-network-client")]
+    fn commits_reachable_from_refs(
+        repo: &gix::Repository,
+    ) -> crate::Result<Vec<gix::commitgraph::file::write::Commit>> {
+        let mut tips = Vec::new();
+        for reference in repo.references()?.all()?.peeled() {
+            let id = reference?.id();
+            if repo.find_object(id)?.kind == gix::object::Kind::Commit {
+                tips.push(id.detach());
+            }
+        }
+        let mut seen = std::collections::HashSet::new();
+        let mut commits = Vec::new();
+        while let Some(id) = tips.pop() {
+            if !seen.insert(id) {
+                continue;
+            }
+            let commit = repo.find_object(id)?.into_commit();
+            let parents: Vec<_> = commit.parent_ids().map(|id| id.detach()).collect();
+            tips.extend(parents.iter().copied());
+            commits.push(gix::commitgraph::file::write::Commit {
+                id,
+                tree: commit.tree_id()?.detach(),
+                parents,
+                committer_timestamp: commit.time()?.seconds as u64,
+            });
+        }
+        Ok(commits)
+    }
+
+    #[test]
+    #[cfg(feature = "blocking-network-client")]
+    fn fetch_unpacks_small_packs_into_loose_objects_if_configured() -> crate::Result {
+        for (key, limit, expect_loose) in [
+            (&gix::config::tree::Fetch::UNPACK_LIMIT, 1000, true),
+            (&gix::config::tree::Transfer::UNPACK_LIMIT, 1000, true),
+            (&gix::config::tree::Fetch::UNPACK_LIMIT, 1, false),
+        ] {
+            let (mut repo, _tmp) = repo_rw("two-origins");
+            repo.config_snapshot_mut().set_value(key, limit.to_string().as_str())?;
+
+            let remote = repo
+                .find_remote("changes-on-top-of-origin")?
+                .with_fetch_tags(fetch::Tags::None);
+            let outcome = remote
+                .connect(Fetch)?
+                .prepare_fetch(gix::progress::Discard, Default::default())?
+                .receive(gix::progress::Discard, &AtomicBool::default())?;
+            let write_pack_bundle = match outcome.status {
+                Status::Change { write_pack_bundle, .. } => write_pack_bundle,
+                _ => unreachable!("a pack was received"),
+            };
+            assert!(
+                write_pack_bundle.index.num_objects > 1,
+                "the pack is larger than the lower limit"
+            );
+
+            let fetched_tip = repo
+                .find_reference("refs/remotes/changes-on-top-of-origin/main")?
+                .id()
+                .detach();
+            let hex = fetched_tip.to_hex().to_string();
+            let loose_path = repo.objects.store_ref().path().join(&hex[..2]).join(&hex[2..]);
+            assert_eq!(loose_path.is_file(), expect_loose);
+            if expect_loose {
+                assert!(
+                    write_pack_bundle.data_path.is_none()
+                        && write_pack_bundle.index_path.is_none()
+                        && write_pack_bundle.keep_path.is_none(),
+                    "all pack files were removed after unpacking"
+                );
+            } else {
+                assert!(
+                    write_pack_bundle.data_path.expect("pack was kept").is_file(),
+                    "packs with more objects than the limit are kept"
+                );
+                assert!(write_pack_bundle.index_path.expect("index was kept").is_file());
+            }
+            assert!(
+                repo.find_object(fetched_tip).is_ok(),
+                "the fetched objects are accessible"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "blocking-network-client")]
+    fn fetch_without_updating_refs_only_receives_objects() -> crate::Result {
+        let (mut repo, _tmp) = repo_rw("two-origins");
+        repo.config_snapshot_mut()
+            .set_value(&gix::config::tree::Fetch::UNPACK_LIMIT, "1000")?;
+        let refs_before = all_refs(&repo)?;
+
+        let remote = repo
+            .find_remote("changes-on-top-of-origin")?
+            .with_fetch_tags(fetch::Tags::None);
+        let outcome = remote
+            .connect(Fetch)?
+            .prepare_fetch(gix::progress::Discard, Default::default())?
+            .with_update_refs(false)
+            .receive(gix::progress::Discard, &AtomicBool::default())?;
+        let (write_pack_bundle, update_refs) = match outcome.status {
+            Status::Change {
+                write_pack_bundle,
+                update_refs,
+                ..
+            } => (write_pack_bundle, update_refs),
+            _ => unreachable!("a pack was received"),
+        };
+
+        assert!(
+            !update_refs.edits.is_empty(),
+            "the edits that would be made are reported"
+        );
+        for edit in &update_refs.edits {
+            let new_id = match &edit.change {
+                gix::refs::transaction::Change::Update {
+                    new: gix::refs::Target::Peeled(id),
+                    ..
+                } => *id,
+                _ => unreachable!("only updates of peeled refs are expected"),
+            };
+            assert!(repo.objects.contains(new_id), "the objects were received nonetheless");
+        }
+        assert_eq!(all_refs(&repo)?, refs_before, "no reference was changed");
+        assert!(
+            !repo.git_dir().join("FETCH_HEAD").exists(),
+            "FETCH_HEAD isn't written either"
+        );
+        assert!(
+            write_pack_bundle.keep_path.map_or(false, |path| path.is_file()),
+            "the pack is protected from garbage collection as nothing refers to it"
+        );
+        assert!(
+            write_pack_bundle.data_path.map_or(false, |path| path.is_file()),
+            "the pack isn't unpacked into loose objects despite the unpack limit, as these could be pruned"
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "blocking-network-client")]
+    fn all_refs(repo: &gix::Repository) -> crate::Result<Vec<(gix::refs::FullName, Option<gix::ObjectId>)>> {
+        Ok(repo
+            .references()?
+            .all()?
+            .map(|r| r.map(|r| (r.name().to_owned(), r.try_id().map(gix::Id::detach))))
+            .collect::<Result<_, _>>()?)
+    }
+
+    /// Return a writable repository whose history is incomplete as it misses the parent of `HEAD`, along with the id of that parent.
+    #[cfg(feature = "blocking-network-client")]
+    fn repo_with_missing_ancestor() -> crate::Result<(gix::Repository, TempDir, gix::ObjectId)> {
+        let (repo, tmp) = repo_rw("two-origins");
+        // Make the local repository incomplete by dropping its alternate, but keep the tip so it's still advertised as `have`.
+        let head = repo.head_commit()?;
+        let head_tree = head.tree_id()?.object()?;
+        let git_dir = repo.git_dir().to_owned();
+        {
+            use gix_odb::Write;
+            let objects = gix::odb::at(git_dir.join("objects"))?;
+            objects.write_buf(gix::object::Kind::Commit, &head.data)?;
+            objects.write_buf(head_tree.kind, &head_tree.data)?;
+        }
+        std::fs::remove_file(git_dir.join("objects").join("info").join("alternates"))?;
+        let parent_id = head.parent_ids().next().expect("head has a parent").detach();
+        drop((head, head_tree, repo));
+
+        let repo = gix::open_opts(&git_dir, crate::restricted())?;
+        assert!(repo.find_object(parent_id).is_err(), "the ancestor is missing now");
+        Ok((repo, tmp, parent_id))
+    }
+
+    #[test]
+    #[cfg(feature = "blocking-network-client")]
+    fn fetch_with_connectivity_check_rejects_missing_ancestors_before_updating_refs() -> crate::Result {
+        let (repo, _tmp, parent_id) = repo_with_missing_ancestor()?;
+        let remote = repo
+            .find_remote("changes-on-top-of-origin")?
+            .with_fetch_tags(fetch::Tags::None);
+        let err = remote
+            .connect(Fetch)?
+            .prepare_fetch(gix::progress::Discard, Default::default())?
+            .with_connectivity_check(true)
+            .receive(gix::progress::Discard, &AtomicBool::default())
+            .unwrap_err();
+        assert!(
+            matches!(err, gix::remote::fetch::Error::ConnectivityMissingObject { id } if id == parent_id),
+            "{err:?}"
+        );
+        assert!(
+            repo.try_find_reference("refs/remotes/changes-on-top-of-origin/main")?
+                .is_none(),
+            "no ref was updated"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "blocking-network-client")]
+    fn fetch_with_connectivity_check_rejects_missing_commits_in_promisor_repositories() -> crate::Result {
+        let (mut repo, _tmp, parent_id) = repo_with_missing_ancestor()?;
+        repo.config_snapshot_mut()
+            .set_raw_value("remote", Some("origin".into()), "promisor", "true")?;
+        assert!(repo.is_promisor());
+        let remote = repo
+            .find_remote("changes-on-top-of-origin")?
+            .with_fetch_tags(fetch::Tags::None);
+        let err = remote
+            .connect(Fetch)?
+            .prepare_fetch(gix::progress::Discard, Default::default())?
+            .with_connectivity_check(true)
+            .receive(gix::progress::Discard, &AtomicBool::default())
+            .unwrap_err();
+        assert!(
+            matches!(err, gix::remote::fetch::Error::ConnectivityMissingObject { id } if id == parent_id),
+            "only trees and blobs may be filtered, so missing commits are still detected: {err:?}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "blocking-network-client")]
+    fn fetch_extra_wants_by_object_id() -> crate::Result {
+        for version in [
+            gix::protocol::transport::Protocol::V1,
+            gix::protocol::transport::Protocol::V2,
+        ] {
+            let (mut repo, tmp) = repo_rw("two-origins");
+            let server = gix::open_opts(tmp.path().join("clone-as-base-with-changes"), crate::restricted())?;
+            let unreferenced_commit = {
+                let head = server.head_commit()?;
+                let mut commit: gix::objs::Commit = head.decode()?.into();
+                commit.parents = Some(head.id).into_iter().collect();
+                commit.message = "not reachable from any ref".into();
+                server.write_object(&commit)?.detach()
+            };
+            repo.config_snapshot_mut().set_value(
+                &gix::config::tree::Protocol::VERSION,
+                (version as u8).to_string().as_str(),
+            )?;
+
+            let res = repo
+                .find_remote("changes-on-top-of-origin")?
+                .with_fetch_tags(fetch::Tags::None)
+                .connect(Fetch)?
+                .prepare_fetch(
+                    gix::progress::Discard,
+                    gix::remote::ref_map::Options {
+                        extra_wants: vec![unreferenced_commit],
+                        ..Default::default()
+                    },
+                )?
+                .receive(gix::progress::Discard, &AtomicBool::default());
+            match version {
+                gix::protocol::transport::Protocol::V1 => {
+                    let err = res.unwrap_err();
+                    assert!(
+                        matches!(err, gix::remote::fetch::Error::UnadvertisedWantUnsupported { id } if id == unreferenced_commit),
+                        "git doesn't allow to want unadvertised objects in V1 by default: {err:?}"
+                    );
+                    assert!(repo.find_object(unreferenced_commit).is_err());
+                }
+                _ => {
+                    let outcome = res?;
+                    assert!(
+                        outcome
+                            .ref_map
+                            .mappings
+                            .iter()
+                            .any(|m| m.remote.as_id() == Some(unreferenced_commit.as_ref()) && m.local.is_none()),
+                        "the object id is wanted without a ref to write it to"
+                    );
+                    assert!(
+                        repo.find_object(unreferenced_commit).is_ok(),
+                        "V2 servers send all objects that exist, so the wanted commit was received"
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "blocking-network-client")]
+    fn fetch_with_set_upstream_configures_tracking_branch() -> crate::Result {
+        let (repo, _tmp) = repo_rw("two-origins");
+        let remote = repo
+            .find_remote("changes-on-top-of-origin")?
+            .with_fetch_tags(fetch::Tags::None);
+        let err = remote
+            .connect(Fetch)?
+            .prepare_fetch(gix::progress::Discard, Default::default())?
+            .with_set_upstream("does-not-exist".to_string())
+            .receive(gix::progress::Discard, &AtomicBool::default())
+            .unwrap_err();
+        assert!(
+            matches!(err, gix::remote::fetch::Error::SetUpstreamBranchMissing { ref name } if name == "does-not-exist"),
+            "{err:?}"
+        );
+        assert!(
+            repo.try_find_reference("refs/remotes/changes-on-top-of-origin/main")?
+                .is_none(),
+            "the branch is validated before fetching"
+        );
+
+        let outcome = remote
+            .connect(Fetch)?
+            .prepare_fetch(gix::progress::Discard, Default::default())?
+            .with_set_upstream("not-currently-checked-out".to_string())
+            .receive(gix::progress::Discard, &AtomicBool::default());
+        assert!(
+            matches!(
+                outcome,
+                Err(gix::remote::fetch::Error::SetUpstreamNoUniqueRemoteBranch { num_candidates: 2 })
+            ),
+            "the remote has more than one branch and none has the same name"
+        );
+
+        remote
+            .connect(Fetch)?
+            .prepare_fetch(gix::progress::Discard, Default::default())?
+            .with_set_upstream("main".to_string())
+            .receive(gix::progress::Discard, &AtomicBool::default())?;
+
+        let repo = gix::open_opts(repo.git_dir(), crate::restricted())?;
+        let config = repo.config_snapshot();
+        assert_eq!(
+            config.string("branch.main.remote").expect("set").as_ref(),
+            "changes-on-top-of-origin"
+        );
+        assert_eq!(
+            config.string("branch.main.merge").expect("set").as_ref(),
+            "refs/heads/main"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "blocking-network-client")]
+    fn fetch_with_prune_removes_stale_refs_and_tags_only_if_enabled() -> crate::Result {
+        let (repo, _tmp) = repo_rw("two-origins");
+        let head_id = repo.head_id()?.detach();
+        for stale_ref in ["refs/remotes/changes-on-top-of-origin/gone", "refs/tags/stale"] {
+            repo.reference(
+                stale_ref,
+                head_id,
+                gix::refs::transaction::PreviousValue::MustNotExist,
+                "create ref that doesn't exist on the remote",
+            )?;
+        }
+        let remote = repo.find_remote("changes-on-top-of-origin")?;
+        let pruned_ref_names = |outcome: &gix::remote::fetch::Outcome| {
+            outcome
+                .pruned_refs
+                .iter()
+                .map(|name| name.as_bstr().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        let outcome = remote
+            .connect(Fetch)?
+            .prepare_fetch(gix::progress::Discard, Default::default())?
+            .with_prune(true)
+            .receive(gix::progress::Discard, &AtomicBool::default())?;
+        assert_eq!(
+            pruned_ref_names(&outcome),
+            ["refs/remotes/changes-on-top-of-origin/gone"],
+            "tags aren't pruned by default"
+        );
+        assert!(repo
+            .try_find_reference("refs/remotes/changes-on-top-of-origin/gone")?
+            .is_none());
+        assert!(repo.try_find_reference("refs/tags/stale")?.is_some());
+        assert!(
+            repo.try_find_reference("refs/tags/v1.0")?.is_some(),
+            "tags on the remote were fetched"
+        );
+
+        let outcome = remote
+            .connect(Fetch)?
+            .prepare_fetch(gix::progress::Discard, Default::default())?
+            .with_prune(true)
+            .with_prune_tags(true)
+            .receive(gix::progress::Discard, &AtomicBool::default())?;
+        assert_eq!(pruned_ref_names(&outcome), ["refs/tags/stale"]);
+        assert!(repo.try_find_reference("refs/tags/stale")?.is_none());
+        assert!(
+            repo.try_find_reference("refs/tags/v1.0")?.is_some(),
+            "tags that exist on the remote are kept"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "blocking-network-client")]
+    fn fetch_writes_fetch_head_with_merge_status_of_each_ref() -> crate::Result {
+        let (mut repo, _tmp) = repo_rw("two-origins");
+        {
+            let mut config = repo.config_snapshot_mut();
+            config.set_raw_value("branch", Some("main".into()), "remote", "changes-on-top-of-origin")?;
+            config.set_raw_value("branch", Some("main".into()), "merge", "refs/heads/main")?;
+        }
+        let fetch_head_path = repo.git_dir().join("FETCH_HEAD");
+        let remote = repo.find_remote("changes-on-top-of-origin")?;
+
+        let outcome = remote
+            .connect(Fetch)?
+            .prepare_fetch(gix::progress::Discard, Default::default())?
+            .receive(gix::progress::Discard, &AtomicBool::default())?;
+        let fetch_head = std::fs::read_to_string(&fetch_head_path)?;
+        let lines: Vec<_> = fetch_head.lines().collect();
+        assert!(
+            lines.len() > 2,
+            "all branches as well as the tag are listed, one per line: {fetch_head}"
+        );
+        assert!(lines.len() <= outcome.ref_map.mappings.len());
+
+        let main_id = repo.find_reference("refs/remotes/changes-on-top-of-origin/main")?.id();
+        let fields: Vec<_> = lines[0].split('\t').collect();
+        assert_eq!(
+            fields[..2],
+            [main_id.to_string().as_str(), ""],
+            "the branch to merge comes first and isn't marked"
+        );
+        assert!(
+            fields[2].starts_with("branch 'main' of ") && fields[2].ends_with("clone-as-base-with-changes"),
+            "the note names the ref and the remote url: {}",
+            fields[2]
+        );
+        for line in &lines[1..] {
+            let fields: Vec<_> = line.split('\t').collect();
+            assert_eq!(fields.len(), 3, "{line}");
+            assert_eq!(fields[0].len(), 40, "every line starts with the object id: {line}");
+            assert_eq!(fields[1], "not-for-merge", "{line}");
+        }
+        let tag_id = repo.find_reference("refs/tags/v1.0")?.target().id().to_owned();
+        assert!(
+            lines
+                .iter()
+                .any(|line| line.starts_with(&format!("{tag_id}\tnot-for-merge\ttag 'v1.0' of "))),
+            "tags are listed with the id of the tag object"
+        );
+
+        remote
+            .connect(Fetch)?
+            .prepare_fetch(gix::progress::Discard, Default::default())?
+            .with_append_fetch_head(true)
+            .receive(gix::progress::Discard, &AtomicBool::default())?;
+        assert_eq!(
+            std::fs::read_to_string(&fetch_head_path)?,
+            format!("{fetch_head}{fetch_head}"),
+            "appending keeps previous entries"
+        );
+
+        remote
+            .connect(Fetch)?
+            .prepare_fetch(gix::progress::Discard, Default::default())?
+            .receive(gix::progress::Discard, &AtomicBool::default())?;
+        assert_eq!(
+            std::fs::read_to_string(&fetch_head_path)?,
+            fetch_head,
+            "by default, previous entries are replaced"
+        );
+
+        std::fs::remove_file(&fetch_head_path)?;
+        remote
+            .connect(Fetch)?
+            .prepare_fetch(gix::progress::Discard, Default::default())?
+            .with_write_fetch_head(false)
+            .receive(gix::progress::Discard, &AtomicBool::default())?;
+        assert!(!fetch_head_path.exists(), "writing FETCH_HEAD can be disabled");
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "blocking-network-client")]
+    fn fetch_with_filter_registers_remote_as_promisor() -> crate::Result {
+        let (repo, tmp) = repo_rw("two-origins");
+        let remote = repo.find_remote("changes-on-top-of-origin")?;
+
+        let err = remote
+            .connect(Fetch)?
+            .prepare_fetch(gix::progress::Discard, Default::default())?
+            .with_filter(Some("blob:none".into()))
+            .receive(gix::progress::Discard, &AtomicBool::default())
+            .unwrap_err();
+        assert!(
+            matches!(
+                err,
+                fetch::Error::PartialCloneUnsupported(
+                    gix_protocol::fetch::arguments::filter::Error::Unsupported { .. }
+                )
+            ),
+            "filters must be allowed by the server, which is detected before negotiation: {err:?}"
+        );
+        assert!(!repo.git_dir().join("FETCH_HEAD").exists(), "nothing was fetched");
+
+        let server_dir = tmp.path().join("clone-as-base-with-changes");
+        assert!(gix_testtools::run_git(&server_dir, &["config", "uploadpack.allowFilter", "true"])?.success());
+        let outcome = remote
+            .connect(Fetch)?
+            .prepare_fetch(gix::progress::Discard, Default::default())?
+            .with_filter(Some("blob:none".into()))
+            .with_update_refs(false)
+            .receive(gix::progress::Discard, &AtomicBool::default())?;
+        assert!(matches!(outcome.status, Status::Change { .. }));
+        assert!(
+            !gix::open_opts(repo.git_dir(), crate::restricted())?.is_promisor(),
+            "without updating refs, the remote isn't registered as promisor remote either"
+        );
+
+        let outcome = remote
+            .connect(Fetch)?
+            .prepare_fetch(gix::progress::Discard, Default::default())?
+            .with_filter(Some("blob:none".into()))
+            .receive(gix::progress::Discard, &AtomicBool::default())?;
+        assert!(matches!(outcome.status, Status::Change { .. }));
+
+        let repo = gix::open_opts(repo.git_dir(), crate::restricted())?;
+        let config = repo.config_snapshot();
+        assert_eq!(
+            config.boolean("remote.changes-on-top-of-origin.promisor"),
+            Some(true),
+            "the remote is marked as promisor remote"
+        );
+        assert_eq!(
+            config
+                .string("remote.changes-on-top-of-origin.partialCloneFilter")
+                .as_deref(),
+            Some("blob:none".into()),
+            "the filter is stored to be used in future fetches"
+        );
+
+        repo.find_remote("changes-on-top-of-origin")?
+            .connect(Fetch)?
+            .prepare_fetch(gix::progress::Discard, Default::default())?
+            .receive(gix::progress::Discard, &AtomicBool::default())?;
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "blocking-network-client")]
+    fn fetch_with_filter_treats_filtered_objects_as_promised_in_connectivity_check() -> crate::Result {
+        let (repo, tmp) = repo_rw("two-origins");
+        assert!(!repo.is_promisor(), "no remote is configured as promisor yet");
+        let server_dir = tmp.path().join("clone-as-base-with-changes");
+        assert!(gix_testtools::run_git(&server_dir, &["config", "uploadpack.allowFilter", "true"])?.success());
+
+        let remote = repo.find_remote("changes-on-top-of-origin")?;
+        remote
+            .connect(Fetch)?
+            .prepare_fetch(gix::progress::Discard, Default::default())?
+            .with_filter(Some("tree:0".into()))
+            .with_connectivity_check(true)
+            .receive(gix::progress::Discard, &AtomicBool::default())?;
+
+        let repo = gix::open_opts(repo.git_dir(), crate::restricted())?;
+        assert!(repo.is_promisor(), "the remote was registered as promisor remote");
+        let tip = repo.find_reference("refs/remotes/changes-on-top-of-origin/main")?.id();
+        let tree = tip.object()?.into_commit().tree_id()?;
+        assert!(
+            repo.is_promisor_missing(tree),
+            "the filtered tree is missing, but that's expected in a partial clone"
+        );
+        assert!(!repo.is_promisor_missing(tip), "the commit itself is present");
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "blocking-network-client")]
+    fn fetch_retries_listing_refs_after_transient_errors_as_configured() -> crate::Result {
+        use std::{borrow::Cow, cell::Cell, rc::Rc};
+
+        use gix_protocol::transport::{
+            client::{self, MessageKind, RequestWriter, SetServiceResponse, Transport, TransportWithoutIO, WriteMode},
+            IsSpuriousError, Protocol, Service,
+        };
+
+        /// A transport which fails the first `failures` handshakes with a transient error after connecting,
+        /// just like a connection that breaks down during the handshake.
+        struct Flaky<T> {
+            inner: T,
+            failures: usize,
+            handshakes: Rc<Cell<usize>>,
+        }
+
+        impl<T: TransportWithoutIO> TransportWithoutIO for Flaky<T> {
+            fn request(
+                &mut self,
+                write_mode: WriteMode,
+                on_into_read: MessageKind,
+            ) -> Result<RequestWriter<'_>, client::Error> {
+                self.inner.request(write_mode, on_into_read)
+            }
+
+            fn to_url(&self) -> Cow<'_, gix::bstr::BStr> {
+                self.inner.to_url()
+            }
+
+            fn connection_persists_across_multiple_requests(&self) -> bool {
+                self.inner.connection_persists_across_multiple_requests()
+            }
+
+            fn configure(
+                &mut self,
+                config: &dyn std::any::Any,
+            ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+                self.inner.configure(config)
+            }
+        }
+
+        impl<T: Transport> Transport for Flaky<T> {
+            fn handshake<'a>(
+                &mut self,
+                service: Service,
+                extra_parameters: &'a [(&'a str, Option<&'a str>)],
+            ) -> Result<SetServiceResponse<'_>, client::Error> {
+                self.handshakes.set(self.handshakes.get() + 1);
+                if self.failures > 0 {
+                    self.failures -= 1;
+                    self.inner.handshake(service, extra_parameters)?;
+                    return Err(client::Error::Io(std::io::ErrorKind::ConnectionReset.into()));
+                }
+                self.inner.handshake(service, extra_parameters)
+            }
+        }
+
+        let (mut repo, tmp) = repo_rw("two-origins");
+        repo.config_snapshot_mut()
+            .set_value(&gix::config::tree::gitoxide::Fetch::MAX_RETRIES, "2")?;
+        let server_path = gix::path::into_bstr(tmp.path().join("clone-as-base-with-changes")).into_owned();
+        let remote = repo.find_remote("changes-on-top-of-origin")?;
+        let flaky = |failures: usize, handshakes: &Rc<Cell<usize>>| Flaky {
+            inner: client::file::connect(server_path.clone(), Protocol::V2).expect("infallible"),
+            failures,
+            handshakes: handshakes.clone(),
+        };
+
+        let handshakes = Rc::new(Cell::new(0));
+        let outcome = remote
+            .to_connection_with_transport(flaky(2, &handshakes))
+            .prepare_fetch(gix::progress::Discard, Default::default())?
+            .receive(gix::progress::Discard, &AtomicBool::default())?;
+        assert!(matches!(outcome.status, Status::Change { .. }));
+        assert_eq!(handshakes.get(), 3, "succeeded on the last configured retry");
+        assert_eq!(
+            outcome.ref_map.handshake.server_protocol_version,
+            Protocol::V2,
+            "retries connect with the same protocol version as the first attempt"
+        );
+
+        let handshakes = Rc::new(Cell::new(0));
+        let err = match remote
+            .to_connection_with_transport(flaky(3, &handshakes))
+            .prepare_fetch(gix::progress::Discard, Default::default())
+        {
+            Ok(_) => unreachable!("the retry budget is exceeded"),
+            Err(err) => err,
+        };
+        assert!(err.is_spurious(), "the last transient error is returned");
+        assert_eq!(handshakes.get(), 3, "no more than the configured amount of retries");
+
+        let handshakes = Rc::new(Cell::new(0));
+        remote
+            .to_connection_with_transport(flaky(3, &handshakes))
+            .prepare_fetch(
+                gix::progress::Discard,
+                gix::remote::ref_map::Options {
+                    max_retries: Some(3),
+                    ..Default::default()
+                },
+            )?;
+        assert_eq!(handshakes.get(), 4, "options override the configuration");
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "blocking-network-client")]
+    fn fetch_applies_bundles_advertised_by_the_server_before_negotiating() -> crate::Result {
+        use std::{borrow::Cow, io::Cursor};
+
+        use gix_protocol::transport::{
+            client::{
+                self, Capabilities, MessageKind, RequestWriter, SetServiceResponse, Transport, TransportWithoutIO,
+                WriteMode,
+            },
+            packetline::{PacketLineRef, StreamingPeekableIter},
+            Protocol, Service,
+        };
+
+        /// A transport which advertises the `bundle-uri` capability and answers the request following `ls-refs`
+        /// with the `bundle_list`, as `git` servers support neither everywhere.
+        struct AdvertisesBundles<T> {
+            inner: T,
+            bundle_list: StreamingPeekableIter<Cursor<Vec<u8>>>,
+            requests: usize,
+            bundle_uri_request: Vec<u8>,
+        }
+
+        impl<T: TransportWithoutIO> TransportWithoutIO for AdvertisesBundles<T> {
+            fn request(
+                &mut self,
+                write_mode: WriteMode,
+                on_into_read: MessageKind,
+            ) -> Result<RequestWriter<'_>, client::Error> {
+                self.requests += 1;
+                if self.requests == 2 {
+                    return Ok(RequestWriter::new_from_bufread(
+                        &mut self.bundle_uri_request,
+                        Box::new(self.bundle_list.as_read_without_sidebands()),
+                        write_mode,
+                        on_into_read,
+                    ));
+                }
+                self.inner.request(write_mode, on_into_read)
+            }
+
+            fn to_url(&self) -> Cow<'_, gix::bstr::BStr> {
+                self.inner.to_url()
+            }
+
+            fn connection_persists_across_multiple_requests(&self) -> bool {
+                self.inner.connection_persists_across_multiple_requests()
+            }
+
+            fn configure(
+                &mut self,
+                config: &dyn std::any::Any,
+            ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+                self.inner.configure(config)
+            }
+        }
+
+        impl<T: Transport> Transport for AdvertisesBundles<T> {
+            fn handshake<'a>(
+                &mut self,
+                service: Service,
+                extra_parameters: &'a [(&'a str, Option<&'a str>)],
+            ) -> Result<SetServiceResponse<'_>, client::Error> {
+                let mut res = self.inner.handshake(service, extra_parameters)?;
+                let mut lines = gix::bstr::BString::from("version 2\n");
+                for capability in res.capabilities.iter() {
+                    lines.extend_from_slice(capability.name());
+                    if let Some(value) = capability.value() {
+                        lines.push(b'=');
+                        lines.extend_from_slice(value);
+                    }
+                    lines.push(b'\n');
+                }
+                lines.extend_from_slice(b"bundle-uri\n");
+                res.capabilities = Capabilities::from_lines(lines).expect("valid capabilities");
+                Ok(res)
+            }
+        }
+
+        let (repo, tmp) = repo_rw("two-origins");
+        let server_dir = tmp.path().join("clone-as-base-with-changes");
+        assert!(gix_testtools::run_git(&server_dir, &["bundle", "create", "../everything.bundle", "--all"])?.success());
+        let bundle_path = gix::path::realpath(tmp.path().join("everything.bundle"))?;
+
+        let mut bundle_list = Vec::new();
+        for line in [
+            "bundle.version=1".to_string(),
+            "bundle.mode=all".into(),
+            format!("bundle.everything.uri={}", bundle_path.display()),
+            "bundle.everything.creationToken=1".into(),
+            "bundle.missing.uri=does-not-exist.bundle".into(),
+        ] {
+            bundle_list.extend_from_slice(format!("{:04x}{line}\n", line.len() + 5).as_bytes());
+        }
+        bundle_list.extend_from_slice(b"0000");
+
+        let remote = repo.find_remote("changes-on-top-of-origin")?;
+        let mut transport = AdvertisesBundles {
+            inner: client::file::connect(gix::path::into_bstr(server_dir).into_owned(), Protocol::V2)
+                .expect("infallible"),
+            bundle_list: StreamingPeekableIter::new(Cursor::new(bundle_list), &[PacketLineRef::Flush]),
+            requests: 0,
+            bundle_uri_request: Vec::new(),
+        };
+        let outcome = remote
+            .to_connection_with_transport(&mut transport)
+            .prepare_fetch(gix::progress::Discard, Default::default())?
+            .with_bundle_uri(true)
+            .receive(gix::progress::Discard, &AtomicBool::default())?;
+        assert!(
+            transport.bundle_uri_request.starts_with(b"0017command=bundle-uri\n"),
+            "the bundle list is requested right after listing refs"
+        );
+
+        assert_eq!(outcome.bundles.len(), 1, "the missing bundle is skipped");
+        let bundle = &outcome.bundles[0];
+        assert_eq!(bundle.uri, gix::path::into_bstr(bundle_path.as_path()).as_ref());
+        assert_ne!(bundle.write_pack_bundle.index.num_objects, 0);
+        assert!(
+            bundle.write_pack_bundle.keep_path.is_none(),
+            "the pack is bound by refs"
+        );
+        assert!(!bundle.refs.is_empty());
+        for (name, id) in &bundle.refs {
+            assert!(name.as_bstr().starts_with(b"refs/bundles/"));
+            assert_eq!(repo.find_reference(name.as_bstr())?.id().detach(), *id);
+        }
+        match outcome.status {
+            Status::NoPackReceived { update_refs, .. } => {
+                assert!(
+                    !update_refs.edits.is_empty(),
+                    "all objects are known from the bundle, so refs are updated without receiving a pack"
+                );
+            }
+            Status::Change { .. } => unreachable!("the bundle contains everything"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "blocking-network-client")]
+    fn fetch_applies_bundle_configured_with_fetch_bundle_uri() -> crate::Result {
+        let (mut repo, tmp) = repo_rw("two-origins");
+        let server_dir = tmp.path().join("clone-as-base-with-changes");
+        assert!(gix_testtools::run_git(&server_dir, &["bundle", "create", "../everything.bundle", "--all"])?.success());
+        let bundle_path = tmp.path().join("everything.bundle");
+        repo.config_snapshot_mut().set_value(
+            &gix::config::tree::Fetch::BUNDLE_URI,
+            gix::path::into_bstr(bundle_path.as_path()).as_ref(),
+        )?;
+
+        let outcome = repo
+            .find_remote("changes-on-top-of-origin")?
+            .connect(Fetch)?
+            .prepare_fetch(gix::progress::Discard, Default::default())?
+            .receive(gix::progress::Discard, &AtomicBool::default())?;
+        assert_eq!(outcome.bundles.len(), 1);
+        assert!(
+            matches!(outcome.status, Status::NoPackReceived { .. }),
+            "no pack is needed after applying the bundle"
+        );
+
+        let outcome = repo
+            .find_remote("changes-on-top-of-origin")?
+            .connect(Fetch)?
+            .prepare_fetch(gix::progress::Discard, Default::default())?
+            .receive(gix::progress::Discard, &AtomicBool::default())?;
+        assert!(
+            outcome.bundles.is_empty(),
+            "the bundle isn't applied again as the objects of its refs are present already"
+        );
+
+        let (mut repo, _tmp) = repo_rw("two-origins");
+        repo.config_snapshot_mut().set_value(
+            &gix::config::tree::Fetch::BUNDLE_URI,
+            gix::path::into_bstr(tmp.path().join("does-not-exist.bundle")).as_ref(),
+        )?;
+        let outcome = repo
+            .find_remote("changes-on-top-of-origin")?
+            .connect(Fetch)?
+            .prepare_fetch(gix::progress::Discard, Default::default())?
+            .receive(gix::progress::Discard, &AtomicBool::default())?;
+        assert!(outcome.bundles.is_empty(), "bundles that can't be obtained are skipped");
+        assert!(matches!(outcome.status, Status::Change { .. }));
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "blocking-network-client")]
+    fn fetch_skips_bundles_of_the_list_in_fetch_bundle_uri_by_creation_token() -> crate::Result {
+        let (mut repo, tmp) = repo_rw("two-origins");
+        let server_dir = tmp.path().join("clone-as-base-with-changes");
+        assert!(gix_testtools::run_git(&server_dir, &["bundle", "create", "../everything.bundle", "--all"])?.success());
+        let list_path = tmp.path().join("bundle-list");
+        std::fs::write(
+            &list_path,
+            "[bundle]\n\tversion = 1\n\tmode = all\n[bundle \"everything\"]\n\turi = everything.bundle\n\tcreationToken = 5\n",
+        )?;
+        let list_uri = gix::path::into_bstr(list_path.as_path()).into_owned();
+        repo.config_snapshot_mut()
+            .set_value(&gix::config::tree::Fetch::BUNDLE_URI, list_uri.as_ref())?;
+
+        let outcome = repo
+            .find_remote("changes-on-top-of-origin")?
+            .connect(Fetch)?
+            .prepare_fetch(gix::progress::Discard, Default::default())?
+            .receive(gix::progress::Discard, &AtomicBool::default())?;
+        assert_eq!(outcome.bundles.len(), 1, "the bundle is resolved relative to the list");
+        assert_eq!(outcome.bundles[0].creation_token, Some(5));
+        let config =
+            gix::config::File::from_path_no_includes(repo.git_dir().join("config"), gix::config::Source::Local)?;
+        assert_eq!(
+            config.integer("fetch", None, "bundleCreationToken").transpose()?,
+            Some(5),
+            "the creation token of the applied bundle is stored in the local configuration"
+        );
+
+        let (mut repo, _tmp) = repo_rw("two-origins");
+        let mut config = repo.config_snapshot_mut();
+        config.set_value(&gix::config::tree::Fetch::BUNDLE_URI, list_uri.as_ref())?;
+        config.set_value(&gix::config::tree::Fetch::BUNDLE_CREATION_TOKEN, "5")?;
+        config.commit()?;
+        let outcome = repo
+            .find_remote("changes-on-top-of-origin")?
+            .connect(Fetch)?
+            .prepare_fetch(gix::progress::Discard, Default::default())?
+            .receive(gix::progress::Discard, &AtomicBool::default())?;
+        assert!(
+            outcome.bundles.is_empty(),
+            "bundles whose creation token isn't greater than the stored one were applied before"
+        );
+        assert!(matches!(outcome.status, Status::Change { .. }));
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "blocking-network-client")]
+    fn fetch_without_updating_refs_applies_bundles_without_writing_their_refs() -> crate::Result {
+        let (mut repo, tmp) = repo_rw("two-origins");
+        let server_dir = tmp.path().join("clone-as-base-with-changes");
+        assert!(gix_testtools::run_git(&server_dir, &["bundle", "create", "../everything.bundle", "--all"])?.success());
+        let list_path = tmp.path().join("bundle-list");
+        std::fs::write(
+            &list_path,
+            "[bundle]\n\tversion = 1\n\tmode = all\n[bundle \"everything\"]\n\turi = everything.bundle\n\tcreationToken = 5\n",
+        )?;
+        repo.config_snapshot_mut().set_value(
+            &gix::config::tree::Fetch::BUNDLE_URI,
+            gix::path::into_bstr(list_path.as_path()).as_ref(),
+        )?;
+
+        let outcome = repo
+            .find_remote("changes-on-top-of-origin")?
+            .connect(Fetch)?
+            .prepare_fetch(gix::progress::Discard, Default::default())?
+            .with_update_refs(false)
+            .receive(gix::progress::Discard, &AtomicBool::default())?;
+        assert_eq!(outcome.bundles.len(), 1, "the bundle is applied nonetheless");
+        let bundle = &outcome.bundles[0];
+        assert!(
+            !bundle.refs.is_empty(),
+            "the refs that would have been written are reported"
+        );
+        for (name, id) in &bundle.refs {
+            assert!(repo.objects.contains(id));
+            assert!(repo.try_find_reference(name)?.is_none(), "but they aren't written");
+        }
+        assert!(
+            bundle
+                .write_pack_bundle
+                .keep_path
+                .as_ref()
+                .map_or(false, |path| path.is_file()),
+            "the pack is protected from garbage collection as nothing refers to it"
+        );
+        let config =
+            gix::config::File::from_path_no_includes(repo.git_dir().join("config"), gix::config::Source::Local)?;
+        assert!(
+            config.integer("fetch", None, "bundleCreationToken").is_none(),
+            "the creation token isn't stored either"
+        );
+        Ok(())
+    }
+
     #[test]
     #[cfg(feature = "blocking-network-client")]
     fn fetch_with_alternates_adds_tips_from_alternates() -> crate::Result<()> {
@@ -156,6 +1243,69 @@ mod blocking_and_async_io {
         Ok(())
     }
 
+    #[test]
+    #[cfg(feature = "blocking-network-client")]
+    fn fetch_with_alternates_respects_alternate_refs_configuration() -> crate::Result {
+        use gix::config::tree::Core;
+
+        let (_repo, tmp) = repo_rw("two-origins");
+        let server_dir = tmp.path().join("clone-as-base-with-changes");
+        let alternate_objects = gix::path::realpath(tmp.path().join("base").join("objects"))?;
+        let base_main = gix::open_opts(tmp.path().join("base"), crate::restricted())?
+            .find_reference("refs/heads/main")?
+            .id()
+            .detach();
+
+        let mut num_fetches = 0;
+        let mut num_objects_received =
+            |config: Option<(&'static dyn gix::config::tree::Key, String)>| -> crate::Result<u32> {
+                num_fetches += 1;
+                let git_dir = tmp.path().join(format!("fresh-{num_fetches}"));
+                let mut repo = gix::init_bare(&git_dir)?;
+                std::fs::write(
+                    git_dir.join("objects").join("info").join("alternates"),
+                    format!("{}\n", alternate_objects.display()),
+                )?;
+                if let Some((key, value)) = config {
+                    repo.config_snapshot_mut().set_value(key, value.as_str())?;
+                }
+                let outcome = repo
+                    .remote_at(server_dir.as_path())?
+                    .with_refspecs(Some("+refs/heads/*:refs/remotes/origin/*"), Fetch)?
+                    .connect(Fetch)?
+                    .prepare_fetch(gix::progress::Discard, Default::default())?
+                    .receive(gix::progress::Discard, &AtomicBool::default())?;
+                Ok(match outcome.status {
+                    Status::Change { write_pack_bundle, .. } => write_pack_bundle.index.num_objects,
+                    Status::NoPackReceived { .. } => unreachable!("the server has a new commit"),
+                })
+            };
+
+        let with_alternate_tips = num_objects_received(None)?;
+        let without_alternate_tips =
+            num_objects_received(Some((&Core::ALTERNATE_REFS_PREFIXES, "refs/does-not-exist/".into())))?;
+        assert!(
+            with_alternate_tips < without_alternate_tips,
+            "the refs of the alternate are sent as haves, so only new objects are received: {with_alternate_tips} < {without_alternate_tips}"
+        );
+        assert_eq!(
+            num_objects_received(Some((&Core::ALTERNATE_REFS_PREFIXES, "refs/tags/ refs/heads/".into())))?,
+            with_alternate_tips,
+            "matching prefixes provide the same tips"
+        );
+        assert_eq!(
+            num_objects_received(Some((&Core::ALTERNATE_REFS_COMMAND, format!("echo {base_main}; true"))))?,
+            with_alternate_tips,
+            "the command receives the path to the alternate repository and provides the tips"
+        );
+        assert_eq!(
+            num_objects_received(Some((&Core::ALTERNATE_REFS_COMMAND, "true".into())))?,
+            without_alternate_tips,
+            "the command takes precedence over all refs of the alternate"
+        );
+        Ok(())
+    }
+
     #[maybe_async::test(
         feature = "blocking-network-client",
         async(feature = "async-network-client-async-std", async_std::test)
@@ -237,6 +1387,83 @@ mod blocking_and_async_io {
         Ok(())
     }
 
+    #[maybe_async::test(
+        feature = "blocking-network-client",
+        async(feature = "async-network-client-async-std", async_std::test)
+    )]
+    async fn fetch_with_bounded_negotiation_rounds() -> crate::Result {
+        for version in [
+            gix::protocol::transport::Protocol::V1,
+            gix::protocol::transport::Protocol::V2,
+        ] {
+            let (mut client_repo, _tmp) = {
+                let client_repo = remote::repo("multi_round/client");
+                let daemon = spawn_git_daemon_if_async(client_repo.work_dir().expect("non-bare"))?;
+                let tmp = TempDir::new()?;
+                let repo = gix::prepare_clone_bare(
+                    daemon.as_ref().map_or_else(
+                        || client_repo.git_dir().to_owned(),
+                        |d| std::path::PathBuf::from(format!("{}/", d.url)),
+                    ),
+                    tmp.path(),
+                )?
+                .fetch_only(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())
+                .await?
+                .0;
+                (repo, tmp)
+            };
+
+            {
+                let mut config = client_repo.config_snapshot_mut();
+                config.set_value(
+                    &gix::config::tree::Protocol::VERSION,
+                    (version as u8).to_string().as_str(),
+                )?;
+                config.set_value(
+                    &gix::config::tree::Fetch::NEGOTIATION_ALGORITHM,
+                    gix::negotiate::Algorithm::Consecutive.to_string().as_str(),
+                )?;
+            }
+            let server_repo = remote::repo("multi_round/server");
+            let daemon = spawn_git_daemon_if_async(server_repo.work_dir().expect("non-bare"))?;
+            let remote = into_daemon_remote_if_async(
+                client_repo.remote_at(server_repo.work_dir().expect("non-bare"))?,
+                daemon.as_ref(),
+                None,
+            );
+            let max_rounds = 2;
+            let changes = remote
+                .with_refspecs(Some("refs/heads/*:refs/remotes/origin/*"), Fetch)?
+                .connect(Fetch)
+                .await?
+                .prepare_fetch(gix::progress::Discard, Default::default())
+                .await?
+                .with_max_negotiation_rounds(max_rounds)
+                .receive(gix::progress::Discard, &AtomicBool::default())
+                .await?;
+
+            match changes.status {
+                Status::Change {
+                    write_pack_bundle,
+                    negotiate,
+                    ..
+                } => {
+                    assert_eq!(
+                        negotiate.rounds.len(),
+                        max_rounds,
+                        "without the limit, this negotiation takes 4 rounds, but we stop early"
+                    );
+                    assert!(
+                        write_pack_bundle.index.num_objects >= 7,
+                        "we receive at least what's needed, possibly more due to the incomplete negotiation"
+                    );
+                }
+                _ => unreachable!("We expect a pack for sure"),
+            }
+        }
+        Ok(())
+    }
+
     #[maybe_async::test(
         feature = "blocking-network-client",
         async(feature = "async-network-client-async-std", async_std::test)
@@ -391,7 +1618,8 @@ mod blocking_and_async_io {
                         .await?;
 
                     match res.status {
-                    fetch::Status::NoPackReceived { update_refs, negotiate: _, dry_run } => {
+                    fetch::Status::NoPackReceived { update_refs, negotiate: _, dry_run, estimate } => {
+                        assert_eq!(estimate, None, "estimates are only made in dry-run mode");
                         assert_eq!(update_refs.edits.len(), expected_ref_count, "{shallow_args:?}|{fetch_tags:?}");
                         assert!(!dry_run, "we actually perform the operation");
                     },
@@ -527,6 +1755,7 @@ mod blocking_and_async_io {
                     .prepare_fetch(progress::Discard, Default::default())
                     .await?
                     .with_dry_run(dry_run)
+                    .with_thread_limit(1)
                     .receive(progress::Discard, &AtomicBool::default())
                     .await?;
                 let refs = match outcome.status {
@@ -575,11 +1804,17 @@ mod blocking_and_async_io {
                         dry_run,
                         update_refs,
                         negotiate: _,
+                        estimate,
                     } => {
                         assert!(
                             dry_run,
                             "the only reason we receive no pack is if we are in dry-run mode"
                         );
+                        assert_eq!(
+                            estimate,
+                            Some(fetch::outcome::Estimate::Objects(4)),
+                            "the server announces the same amount of objects it sends when not in dry-run mode"
+                        );
                         update_refs
                     }
                 };
@@ -2,11 +2,235 @@ mod config_snapshot;
 mod identity;
 mod remote;
 
+mod advice {
+    use gix::config::tree::Advice;
+
+    #[test]
+    fn is_enabled_unless_configured_otherwise() -> crate::Result {
+        let mut repo = crate::basic_repo()?;
+        assert!(repo.advice(&Advice::FETCH_REMOVED_DESTINATIONS)?);
+        assert!(repo.advice(&Advice::FETCH_FILTERED_TIPS)?);
+
+        repo.config_snapshot_mut()
+            .set_value(&Advice::FETCH_FILTERED_TIPS, "false")?;
+        assert!(
+            repo.advice(&Advice::FETCH_REMOVED_DESTINATIONS)?,
+            "other advice is unaffected"
+        );
+        assert!(!repo.advice(&Advice::FETCH_FILTERED_TIPS)?);
+
+        repo.config_snapshot_mut()
+            .set_raw_value("advice", None, "fetchRemovedDestinations", "not-a-boolean")?;
+        assert!(
+            repo.advice(&Advice::FETCH_REMOVED_DESTINATIONS)?,
+            "invalid values fall back to the default as configuration is lenient by default"
+        );
+        Ok(())
+    }
+}
+
+mod stat_options {
+    use gix::{config::tree::Core, index::entry::Stat};
+
+    #[test]
+    fn check_stat_controls_which_fields_are_compared() -> crate::Result {
+        let mut repo = crate::basic_repo()?;
+        let index_stat = Stat {
+            ino: 1,
+            uid: 2,
+            size: 42,
+            ..Default::default()
+        };
+        let worktree_stat = Stat {
+            ino: 10,
+            uid: 20,
+            ..index_stat
+        };
+
+        let opts = repo.stat_options()?;
+        assert!(opts.check_stat, "all fields are checked by default");
+        assert!(!index_stat.matches(&worktree_stat, opts));
+
+        repo.config_snapshot_mut().set_value(&Core::CHECK_STAT, "minimal")?;
+        let opts = repo.stat_options()?;
+        assert!(!opts.check_stat);
+        assert!(
+            index_stat.matches(&worktree_stat, opts),
+            "inode and owner changes are ignored, only size and times matter"
+        );
+        assert!(
+            !index_stat.matches(
+                &Stat {
+                    size: 0,
+                    ..worktree_stat
+                },
+                opts
+            ),
+            "size changes are still detected"
+        );
+
+        repo.config_snapshot_mut().set_value(&Core::CHECK_STAT, "default")?;
+        let opts = repo.stat_options()?;
+        assert!(opts.check_stat);
+        assert!(!index_stat.matches(&worktree_stat, opts));
+        Ok(())
+    }
+}
+
+mod index {
+    use gix::config::tree::{core::UntrackedCacheMode, Core};
+
+    #[test]
+    fn untracked_cache_is_kept_unless_configured() -> crate::Result {
+        let mut repo = crate::basic_repo()?;
+        assert_eq!(repo.untracked_cache()?, UntrackedCacheMode::Keep);
+
+        for (value, expected) in [
+            ("keep", UntrackedCacheMode::Keep),
+            ("true", UntrackedCacheMode::Enabled),
+            ("false", UntrackedCacheMode::Disabled),
+        ] {
+            repo.config_snapshot_mut().set_value(&Core::UNTRACKED_CACHE, value)?;
+            assert_eq!(repo.untracked_cache()?, expected, "{value}");
+        }
+
+        repo.config_snapshot_mut()
+            .set_raw_value("core", None, "untrackedCache", "invalid")?;
+        assert_eq!(
+            repo.untracked_cache()?,
+            UntrackedCacheMode::Keep,
+            "invalid values fall back to the default as configuration is lenient by default"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn split_index_is_off_unless_configured() -> crate::Result {
+        let mut repo = crate::basic_repo()?;
+        assert!(!repo.split_index()?);
+
+        repo.config_snapshot_mut().set_value(&Core::SPLIT_INDEX, "true")?;
+        assert!(repo.split_index()?);
+
+        repo.config_snapshot_mut()
+            .set_raw_value("core", None, "splitIndex", "not-a-boolean")?;
+        assert!(
+            !repo.split_index()?,
+            "invalid values fall back to the default as configuration is lenient by default"
+        );
+        Ok(())
+    }
+}
+
+mod sparse_checkout {
+    use gix::config::tree::Core;
+
+    #[test]
+    fn both_flags_are_off_unless_configured() -> crate::Result {
+        let mut repo = crate::basic_repo()?;
+        assert!(!repo.sparse_checkout()?);
+        assert!(!repo.sparse_checkout_cone()?);
+
+        repo.config_snapshot_mut().set_value(&Core::SPARSE_CHECKOUT, "true")?;
+        assert!(repo.sparse_checkout()?);
+        assert!(!repo.sparse_checkout_cone()?, "cone mode is configured separately");
+
+        repo.config_snapshot_mut()
+            .set_value(&Core::SPARSE_CHECKOUT_CONE, "true")?;
+        assert!(repo.sparse_checkout()?);
+        assert!(repo.sparse_checkout_cone()?);
+
+        repo.config_snapshot_mut()
+            .set_raw_value("core", None, "sparseCheckout", "not-a-boolean")?;
+        assert!(
+            !repo.sparse_checkout()?,
+            "invalid values fall back to the default as configuration is lenient by default"
+        );
+        Ok(())
+    }
+}
+
+mod hooks_path {
+    use gix::config::tree::Core;
+
+    #[test]
+    fn defaults_to_hooks_directory_in_git_dir() -> crate::Result {
+        let repo = crate::basic_repo()?;
+        assert_eq!(repo.hooks_path()?, repo.git_dir().join("hooks"));
+        Ok(())
+    }
+
+    #[test]
+    fn absolute_paths_are_used_as_is() -> crate::Result {
+        let mut repo = crate::basic_repo()?;
+        let hooks = gix_testtools::tempfile::TempDir::new()?;
+        repo.config_snapshot_mut()
+            .set_value(&Core::HOOKS_PATH, gix::path::into_bstr(hooks.path()).as_ref())?;
+        assert_eq!(repo.hooks_path()?, hooks.path());
+        Ok(())
+    }
+
+    #[test]
+    fn relative_paths_are_relative_to_the_worktree_or_the_git_dir_if_bare() -> crate::Result {
+        let mut repo = crate::basic_repo()?;
+        repo.config_snapshot_mut()
+            .set_value(&Core::HOOKS_PATH, "custom/hooks")?;
+        assert_eq!(
+            repo.hooks_path()?,
+            repo.work_dir().expect("non-bare").join("custom").join("hooks")
+        );
+
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let mut repo = gix::init_bare(tmp.path())?;
+        repo.config_snapshot_mut()
+            .set_value(&Core::HOOKS_PATH, "custom/hooks")?;
+        assert_eq!(
+            repo.hooks_path()?,
+            repo.git_dir().join("custom").join("hooks"),
+            "without worktree, the git dir is used instead"
+        );
+        Ok(())
+    }
+}
+
 #[cfg(feature = "blocking-network-client")]
 mod ssh_options {
     use std::ffi::OsStr;
 
-    use crate::repository::config::repo;
+    use gix_sec::Permission;
+    use gix_testtools::Env;
+    use serial_test::serial;
+
+    use crate::repository::config::{repo, repo_opts};
+
+    #[test]
+    #[serial]
+    fn ssh_command_from_config_and_environment() {
+        let repository = repo("ssh-all-options");
+        assert_eq!(repository.ssh_command(), Some("ssh -VVV".into()));
+
+        let _env = Env::new().set("GIT_SSH_COMMAND", "ssh-from-env");
+        let repository = repo_opts("ssh-all-options", |mut opts| {
+            opts.permissions.env.git_prefix = Permission::Allow;
+            opts
+        });
+        assert_eq!(
+            repository.ssh_command(),
+            Some("ssh-from-env".into()),
+            "the environment overrides the configuration"
+        );
+
+        let repository = repo("ssh-command-fallback");
+        assert_eq!(repository.ssh_command(), None, "unset values are `None`");
+
+        let repository = repo_opts("ssh-all-options", |opts| opts.config_overrides(["core.sshCommand= \t"]));
+        assert_eq!(repository.ssh_command(), None, "empty values count as unset");
+        assert_eq!(
+            repository.ssh_connect_options().expect("valid").command.as_deref(),
+            Some(OsStr::new("does not matter as it is a fallback")),
+            "which activates the fallback"
+        );
+    }
 
     #[test]
     fn with_command_and_variant() -> crate::Result {
@@ -69,6 +69,131 @@ mod missing_config_file {
     }
 }
 
+mod log_all_ref_updates {
+    use gix_ref::store::WriteReflog;
+
+    use crate::util::named_subrepo_opts;
+
+    #[test]
+    fn unset_is_disabled_in_bare_repositories() -> crate::Result {
+        let repo = named_subrepo_opts("make_config_repos.sh", "bare-no-config", gix::open::Options::isolated())?;
+        assert!(repo.is_bare());
+        assert_eq!(repo.refs.write_reflog, WriteReflog::Disable);
+        Ok(())
+    }
+
+    #[test]
+    fn unset_is_normal_in_repositories_with_worktree() -> crate::Result {
+        let repo = named_subrepo_opts(
+            "make_config_repos.sh",
+            "worktree-no-config",
+            gix::open::Options::isolated(),
+        )?;
+        assert!(!repo.is_bare());
+        assert_eq!(repo.refs.write_reflog, WriteReflog::Normal);
+        Ok(())
+    }
+
+    #[test]
+    fn always_is_respected_in_bare_repositories() -> crate::Result {
+        let repo = named_subrepo_opts(
+            "make_config_repos.sh",
+            "bare-no-config",
+            gix::open::Options::isolated().config_overrides(["core.logAllRefUpdates=always"]),
+        )?;
+        assert!(repo.is_bare());
+        assert_eq!(repo.refs.write_reflog, WriteReflog::Always);
+        Ok(())
+    }
+}
+
+#[test]
+fn unsupported_repository_format_version_is_an_error() -> crate::Result {
+    let repo_path = gix_testtools::scripted_fixture_read_only("make_config_repos.sh")?
+        .join("unsupported-repository-format-version");
+    for strict in [false, true] {
+        let options = gix::open::Options::isolated().strict_config(strict);
+        let err = gix::open_opts(&repo_path, options).unwrap_err();
+        assert!(
+            matches!(
+                err,
+                gix::open::Error::Config(gix::config::Error::UnsupportedRepositoryFormatVersion { version: 2 })
+            ),
+            "{err:?}"
+        );
+    }
+    Ok(())
+}
+
+mod object_format {
+    fn repo_with_config(
+        core_and_extensions: &str,
+    ) -> crate::Result<(std::path::PathBuf, gix_testtools::tempfile::TempDir)> {
+        let tmp = gix_testtools::tempfile::tempdir()?;
+        gix::init_bare(tmp.path())?;
+        std::fs::write(tmp.path().join("config"), core_and_extensions)?;
+        Ok((tmp.path().to_owned(), tmp))
+    }
+
+    #[test]
+    fn sha1_in_version_1_is_supported() -> crate::Result {
+        let (path, _tmp) = repo_with_config(
+            "[core]\n\trepositoryFormatVersion = 1\n\tbare = true\n[extensions]\n\tobjectFormat = sha1\n",
+        )?;
+        let repo = gix::open_opts(path, gix::open::Options::isolated())?;
+        assert_eq!(repo.object_hash(), gix_hash::Kind::Sha1);
+        Ok(())
+    }
+
+    #[test]
+    fn sha256_in_version_1_is_recognized_but_unsupported() -> crate::Result {
+        let (path, _tmp) = repo_with_config(
+            "[core]\n\trepositoryFormatVersion = 1\n\tbare = true\n[extensions]\n\tobjectFormat = sha256\n",
+        )?;
+        let err = gix::open_opts(path, gix::open::Options::isolated()).unwrap_err();
+        assert!(
+            matches!(
+                &err,
+                gix::open::Error::Config(gix::config::Error::UnsupportedObjectFormat { name }) if name == "sha256"
+            ),
+            "{err:?}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn declaring_an_object_format_in_version_0_is_an_error() -> crate::Result {
+        let (path, _tmp) = repo_with_config(
+            "[core]\n\trepositoryFormatVersion = 0\n\tbare = true\n[extensions]\n\tobjectFormat = sha256\n",
+        )?;
+        let err = gix::open_opts(path, gix::open::Options::isolated()).unwrap_err();
+        assert!(
+            matches!(
+                err,
+                gix::open::Error::Config(gix::config::Error::ExtensionRequiresRepositoryFormatVersion1 {
+                    version: 0,
+                    ..
+                })
+            ),
+            "{err:?}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_object_formats_are_an_error() -> crate::Result {
+        let (path, _tmp) = repo_with_config(
+            "[core]\n\trepositoryFormatVersion = 1\n\tbare = true\n[extensions]\n\tobjectFormat = md5\n",
+        )?;
+        let err = gix::open_opts(path, gix::open::Options::isolated()).unwrap_err();
+        assert!(
+            matches!(err, gix::open::Error::Config(gix::config::Error::ConfigTypedString(_))),
+            "{err:?}"
+        );
+        Ok(())
+    }
+}
+
 mod not_a_repository {
 
     #[test]
@@ -219,6 +344,7 @@ mod with_overrides {
             .set("GITOXIDE_PACK_CACHE_MEMORY", "0")
             .set("GITOXIDE_OBJECT_CACHE_MEMORY", "5m")
             .set("GIT_SSL_CAINFO", "./env.pem")
+            .set("GIT_SSL_CAPATH", "./env-certs")
             .set("GIT_SSL_VERSION", "tlsv1.3")
             .set("GIT_SSH_VARIANT", "ssh-variant-env")
             .set("GIT_SSH_COMMAND", "ssh-command-env")
@@ -346,6 +472,10 @@ mod with_overrides {
                 cow_bstr("./env.pem")
             ]
         );
+        assert_eq!(
+            config.string_by_key("http.sslCAPath").expect("present").as_ref(),
+            "./env-certs"
+        );
         assert_eq!(
             config.strings_by_key("http.sslVersion").expect("at least one value"),
             [
@@ -523,3 +653,122 @@ mod worktree {
         Ok(())
     }
 }
+
+#[cfg(unix)]
+mod symlinked_home {
+    use gix_sec::Permission;
+    use gix_testtools::Env;
+    use serial_test::serial;
+
+    #[test]
+    #[serial]
+    fn home_is_canonicalized_for_includes_and_paths() -> crate::Result {
+        let tmp = gix_testtools::tempfile::tempdir()?;
+        let tmp = gix::path::realpath(tmp.path())?;
+        let real_home = tmp.join("real-home");
+        let repo_dir = real_home.join("repo");
+        gix::init(&repo_dir)?;
+        std::fs::write(real_home.join("included.config"), "[user]\n\tname = included\n")?;
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(repo_dir.join(".git/config"))
+            .and_then(|mut config| {
+                std::io::Write::write_all(
+                    &mut config,
+                    b"[includeIf \"gitdir:~/repo/\"]\n\tpath = ~/included.config\n[core]\n\texcludesFile = ~/ignore\n",
+                )
+            })?;
+        let home = tmp.join("home");
+        std::os::unix::fs::symlink(&real_home, &home)?;
+        let _env = Env::new().set("HOME", home.to_str().expect("valid UTF-8"));
+
+        let mut opts = gix::open::Options::isolated();
+        opts.permissions.env.home = Permission::Allow;
+        opts.permissions.config.includes = true;
+        let repo = gix::open_opts(&repo_dir, opts)?;
+        let config = repo.config_snapshot();
+        assert_eq!(
+            config.string("user.name").expect("present").as_ref(),
+            "included",
+            "`~` expands to the real home directory, so it matches the real path of the git directory"
+        );
+        assert_eq!(
+            config.trusted_path("core.excludesFile").expect("present")?,
+            real_home.join("ignore"),
+            "paths are interpolated with the real home directory as well"
+        );
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+mod worktree_config {
+    use gix_sec::Permission;
+    use gix_testtools::Env;
+    use serial_test::serial;
+
+    #[test]
+    #[serial]
+    fn overrides_user_config_except_for_core_bare_and_worktree() -> crate::Result {
+        let tmp = gix_testtools::tempfile::tempdir()?;
+        let tmp = gix::path::realpath(tmp.path())?;
+        let home = tmp.join("home");
+        std::fs::create_dir(&home)?;
+        std::fs::write(
+            home.join(".gitconfig"),
+            "[user]\n\tname = from-user\n\temail = user@example.com\n",
+        )?;
+        let repo_dir = tmp.join("repo");
+        gix::init(&repo_dir)?;
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(repo_dir.join(".git/config"))
+            .and_then(|mut config| {
+                std::io::Write::write_all(&mut config, b"[extensions]\n\tworktreeConfig = true\n")
+            })?;
+        std::fs::write(
+            repo_dir.join(".git/config.worktree"),
+            "[user]\n\tname = from-worktree\n[core]\n\tbare = true\n\tworktree = ../elsewhere\n",
+        )?;
+        let _env = Env::new().set("HOME", home.to_str().expect("valid UTF-8"));
+
+        let mut opts = gix::open::Options::isolated();
+        opts.permissions.env.home = Permission::Allow;
+        opts.permissions.config.user = true;
+        let repo = gix::open_opts(&repo_dir, opts)?;
+        let config = repo.config_snapshot();
+        assert_eq!(
+            config.string("user.name").expect("present").as_ref(),
+            "from-worktree",
+            "the worktree configuration has a higher precedence than the user configuration"
+        );
+        assert_eq!(
+            config.string("user.email").expect("present").as_ref(),
+            "user@example.com",
+            "values that aren't overridden remain visible"
+        );
+        assert!(!repo.is_bare(), "core.bare isn't overridden");
+        assert_eq!(
+            repo.work_dir(),
+            Some(repo_dir.as_path()),
+            "core.worktree is ignored as well"
+        );
+        let worktree_core = config
+            .plumbing()
+            .sections_by_name("core")
+            .into_iter()
+            .flatten()
+            .find(|section| section.meta().source == gix::config::Source::Worktree)
+            .expect("the core section of config.worktree is present");
+        assert_eq!(
+            worktree_core.body().value("bare").as_deref(),
+            Some("true".into()),
+            "the values are retained so they survive writing the configuration back"
+        );
+        assert_eq!(
+            worktree_core.body().value("worktree").as_deref(),
+            Some("../elsewhere".into())
+        );
+        Ok(())
+    }
+}
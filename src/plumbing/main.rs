@@ -231,22 +231,34 @@ pub fn main() -> Result<()> {
         #[cfg(feature = "gitoxide-core-blocking-client")]
         Subcommands::Fetch(crate::plumbing::options::fetch::Platform {
             dry_run,
+            append,
             handshake_info,
             negotiation_info,
             open_negotiation_graph,
+            max_retries,
+            jobs,
             remote,
             shallow,
-            ref_spec,
+            ref_prefix,
+            stdin,
+            mut ref_spec,
         }) => {
+            if stdin {
+                ref_spec.extend(core::repository::fetch::ref_specs_from_lines(stdin_or_bail()?)?);
+            }
             let opts = core::repository::fetch::Options {
                 format,
                 dry_run,
+                append,
                 remote,
                 handshake_info,
                 negotiation_info,
                 open_negotiation_graph,
+                max_retries,
+                jobs,
                 shallow: shallow.into(),
                 ref_specs: ref_spec,
+                ref_prefix_override: ref_prefix,
             };
             prepare_and_run(
                 "fetch",
@@ -514,6 +526,7 @@ pub fn main() -> Result<()> {
                     directory,
                     refs,
                     refs_directory,
+                    filter,
                 } => {
                     let (_handle, progress) =
                         async_util::prepare(verbose, "pack-receive", core::pack::receive::PROGRESS_RANGE);
@@ -523,6 +536,7 @@ pub fn main() -> Result<()> {
                         directory,
                         refs_directory,
                         refs.into_iter().map(|s| s.into()).collect(),
+                        filter.map(Into::into),
                         progress,
                         core::pack::receive::Context {
                             thread_limit,
@@ -541,6 +555,7 @@ pub fn main() -> Result<()> {
                     directory,
                     refs,
                     refs_directory,
+                    filter,
                 } => prepare_and_run(
                     "pack-receive",
                     trace,
@@ -555,6 +570,7 @@ pub fn main() -> Result<()> {
                             directory,
                             refs_directory,
                             refs.into_iter().map(Into::into).collect(),
+                            filter.map(Into::into),
                             progress,
                             core::pack::receive::Context {
                                 thread_limit,
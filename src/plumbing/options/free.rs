@@ -197,6 +197,12 @@ pub mod pack {
             #[clap(long = "reference", short = 'r')]
             refs: Vec<String>,
 
+            /// Ask the server to omit objects matching this filter specification, like `blob:none`, `blob:limit=<n>` or `tree:0`.
+            ///
+            /// Note that this fails if the server doesn't support filters.
+            #[clap(long)]
+            filter: Option<String>,
+
             /// The directory into which to write the received pack and index.
             ///
             /// If unset, they will be discarded.
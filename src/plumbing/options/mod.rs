@@ -200,6 +200,10 @@ pub mod fetch {
         #[clap(long, short = 'n')]
         pub dry_run: bool,
 
+        /// Add the fetched refs to the existing entries of `FETCH_HEAD` instead of replacing them.
+        #[clap(long, short = 'a')]
+        pub append: bool,
+
         /// Output additional typically information provided by the server as part of the connection handshake.
         #[clap(long, short = 'H')]
         pub handshake_info: bool,
@@ -212,6 +216,15 @@ pub mod fetch {
         #[clap(long, value_name = "PATH", short = 'g')]
         pub open_negotiation_graph: Option<std::path::PathBuf>,
 
+        /// Retry listing refs up to COUNT times after transient network errors, overriding `gitoxide.fetch.maxRetries`.
+        #[clap(long, value_name = "COUNT")]
+        pub max_retries: Option<u32>,
+
+        /// The amount of threads to use for loading local pack indices and resolving the received pack, with 0 meaning
+        /// as many as there are logical cores. Overrides `pack.threads`.
+        #[clap(long, short = 'j', value_name = "N")]
+        pub jobs: Option<usize>,
+
         #[clap(flatten)]
         pub shallow: ShallowOptions,
 
@@ -221,6 +234,17 @@ pub mod fetch {
         #[clap(long, short = 'r')]
         pub remote: Option<String>,
 
+        /// Fetch into a custom ref namespace by rewriting the destination of all ref-specs to land underneath PREFIX.
+        ///
+        /// For example, with `refs/mirror/` the ref-spec `refs/heads/*:refs/remotes/origin/*` updates `refs/mirror/remotes/origin/*`.
+        /// Tags are only fetched if a ref-spec asks for them.
+        #[clap(long, value_name = "PREFIX", value_parser = crate::shared::AsBString)]
+        pub ref_prefix: Option<gix::bstr::BString>,
+
+        /// Read additional ref-specs from standard input, one per line, ignoring empty lines and lines starting with `#`.
+        #[clap(long)]
+        pub stdin: bool,
+
         /// Override the built-in and configured ref-specs with one or more of the given ones.
         #[clap(value_parser = crate::shared::AsBString)]
         pub ref_spec: Vec<gix::bstr::BString>,
@@ -116,14 +116,6 @@ static GIT_CONFIG: &[Record] = &[
         config: "core.packedGitLimit",
         usage: NotApplicable { reason: "we target 32bit systems only and don't use a windowing mechanism" }
     },
-    Record {
-        config: "core.alternateRefsCommand",
-        usage: NotPlanned { reason: "there is no need as we can perform the required operation in-binary. This could happen though if there is a use-case and demand." }
-    },
-    Record {
-        config: "core.alternateRefsPrefixes",
-        usage: NotPlanned { reason: "seems like a niche feature, but can be implemented if there is demand" }
-    },
     Record {
         config: "core.bigFileThreshold",
         usage: Planned { note: Some("unfortunately we can't stream packed files yet, even if not delta-compressed, but respecting the threshold for other operations is definitely a must") }
@@ -144,14 +136,6 @@ static GIT_CONFIG: &[Record] = &[
         config: "core.protectNTFS",
         usage: NotPlanned { reason: "lack of demand"},
     },
-    Record {
-        config: "core.sparseCheckout",
-        usage: Planned { note: Some("we want to support huge repos and be the fastest in doing so") },
-    },
-    Record {
-        config: "core.sparseCheckoutCone",
-        usage: Planned { note: Some("this is a nice improvement over spareCheckout alone and should one day be available too") },
-    },
     Record {
         config: "core.gitProxy",
         usage: NotPlanned { reason: "the transport mechanism works differently enough to not support it for now, but of course it's possible to add support if there is demand" },
@@ -160,10 +144,6 @@ static GIT_CONFIG: &[Record] = &[
         config: "checkout.defaultRemote",
         usage: Planned { note: Some("needed for correct checkout behaviour, similar to what git does") },
     },
-    Record {
-        config: "core.untrackedCache",
-        usage: Planned { note: Some("needed for fast worktree operation") },
-    },
     Record {
         config: "checkout.guess",
         usage: Planned { note: None },
@@ -282,22 +262,6 @@ static GIT_CONFIG: &[Record] = &[
         config: "fetch.fsck.skipList",
         usage: Puzzled,
     },
-    Record {
-        config: "fetch.unpackLimit",
-        usage: Planned { note: None },
-    },
-    Record {
-        config: "fetch.prune",
-        usage: Planned { note: None },
-    },
-    Record {
-        config: "fetch.pruneTags",
-        usage: Planned { note: None },
-    },
-    Record {
-        config: "fetch.writeCommitGraph",
-        usage: Planned { note: None },
-    },
     Record {
         config: "fetch.parallel",
         usage: Planned { note: None },
@@ -320,18 +284,6 @@ static GIT_CONFIG: &[Record] = &[
         config: "advice.updateSparsePath",
         usage: NotApplicable { reason: "gitoxide does not yet have an 'advice' system" },
     },
-    Record {
-        config: "core.sparseCheckout",
-        usage: Planned { note: Some("together with 'index.sparse' and 'core.sparseCheckoutCone', configures if the index should be written sparse or not") },
-    },
-    Record {
-        config: "core.sparseCheckoutCone",
-        usage: Planned { note: Some("non-cone mode is deprecated but should still fail gracefully if encountered") },
-    },
-    Record {
-        config: "core.splitIndex",
-        usage: NotPlanned { reason: "we don't want to be able to create split indices, but we will read them. It's (somewhat) superseded by sparse indices" },
-    },
     Record {
         config: "splitIndex.maxPercentageChange",
         usage: NotPlanned { reason: "seems like it's superseded by sparse indices" },
@@ -376,14 +328,6 @@ static GIT_CONFIG: &[Record] = &[
         config: "http.delegation",
         usage: NotPlanned { reason: "on demand" }
     },
-    Record {
-        config: "http.cookieFile",
-        usage: NotPlanned { reason: "on demand" }
-    },
-    Record {
-        config: "http.saveCookies",
-        usage: NotPlanned { reason: "on demand" }
-    },
     Record {
         config: "http.curloptResolve",
         usage: NotPlanned { reason: "on demand" }
@@ -396,10 +340,6 @@ static GIT_CONFIG: &[Record] = &[
         config: "http.sslCipherList",
         usage: NotPlanned { reason: "on demand" }
     },
-    Record {
-        config: "http.sslVerify",
-        usage: NotPlanned { reason: "on demand" }
-    },
     Record {
         config: "http.sslCert",
         usage: NotPlanned { reason: "on demand" }
@@ -416,10 +356,6 @@ static GIT_CONFIG: &[Record] = &[
         config: "http.sslCertPasswordProtected",
         usage: NotPlanned { reason: "on demand" }
     },
-    Record {
-        config: "http.sslCAPath",
-        usage: NotPlanned { reason: "on demand" }
-    },
     Record {
         config: "http.sslBackend",
         usage: NotPlanned { reason: "on demand" }
@@ -452,26 +388,10 @@ static GIT_CONFIG: &[Record] = &[
         config: "http.<url>.*",
         usage: Planned { note: Some("it's a vital part of git configuration. It's unclear how to get a baseline from git for this one.") }
     },
-    Record {
-        config: "init.templateDir",
-        usage: NotPlanned { reason: "git expects this dir to be a valid git dir - I'd find additive template dirs more interesting, or changes done afterwards procedurally. Maybe this needs a 'init_or_open' semantic to be really useful" }
-    },
     Record {
         config: "sparse.expectFilesOutsideOfPatterns",
         usage: NotPlanned { reason: "todo" },
     },
-    Record {
-        config: "remote.<name>.promisor",
-        usage: Planned {
-            note: Some("required for big monorepos, and typically used in conjunction with sparse indices")
-        }
-    },
-    Record {
-        config: "remote.<name>.partialCloneFilter",
-        usage: Planned {
-            note: Some("required for big monorepos, and typically used in conjunction with sparse indices")
-        }
-    },
     Record {
         config: "merge.renameLimit",
         usage: Planned { note: Some("The same as diff.renameLimit") }
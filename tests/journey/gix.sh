@@ -156,6 +156,64 @@ title "gix (with repository)"
       )
     )
   )
+
+  title "gix fetch"
+  (when "running 'fetch'"
+    snapshot="$snapshot/fetch"
+    (small-repo-in-sandbox
+      if [[ "$kind" != "small" && "$kind" != "async" ]]; then
+      git clone -q . clone
+      cd clone
+      if test "$kind" = "max" || test "$kind" = "max-pure"; then
+      (with "--dry-run and --format json"
+        it "generates the correct output in JSON format" && {
+          WITH_SNAPSHOT="$snapshot/dry-run-json" \
+          expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose --format json -c protocol.version=2 fetch --dry-run refs/heads/main:refs/remotes/origin/main
+        }
+      )
+      fi
+      (with "--ref-prefix"
+        it "succeeds" && {
+          expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose fetch --ref-prefix refs/mirror/
+        }
+        it "places the fetched refs underneath the prefix" && {
+          expect_run $SUCCESSFULLY git show-ref --verify refs/mirror/remotes/origin/main refs/mirror/remotes/origin/dev
+        }
+        it "leaves the normal tracking refs untouched" && {
+          expect_run $SUCCESSFULLY git show-ref --verify refs/remotes/origin/main refs/remotes/origin/dev
+          expect_run $WITH_FAILURE git show-ref --verify refs/mirror/tags/annotated
+        }
+        it "fails if the prefix doesn't end with a slash" && {
+          expect_run $WITH_FAILURE "$exe_plumbing" --no-verbose fetch --ref-prefix refs/mirror
+        }
+        it "fails if an existing ref is in the way of the prefix" && {
+          expect_run $WITH_FAILURE "$exe_plumbing" --no-verbose fetch --ref-prefix refs/remotes/origin/main/
+        }
+      )
+      (with "--stdin"
+        it "fetches the ref-specs read from standard input, ignoring empty lines and comments" && {
+          expect_run_sh $SUCCESSFULLY "printf '# generated\\n\\nrefs/heads/main:refs/from-stdin/main\\n  refs/heads/dev:refs/from-stdin/dev\\n' | $exe_plumbing --no-verbose fetch --stdin"
+          expect_run $SUCCESSFULLY git show-ref --verify refs/from-stdin/main refs/from-stdin/dev
+        }
+        it "reports the line number of invalid ref-specs" && {
+          expect_run_sh $SUCCESSFULLY "printf 'refs/heads/main:refs/from-stdin/main\\nrefs/heads/*:refs/heads/main\\n' | $exe_plumbing --no-verbose fetch --stdin 2>&1 | grep 'on line 2' >/dev/null"
+        }
+      )
+      (with "a ref-spec with a partial destination"
+        it "warns about the removed destination and the filtered tips by default" && {
+          expect_run_sh $SUCCESSFULLY "$exe_plumbing --no-verbose fetch refs/heads/main:main 2>&1 | grep 'destination refs were removed' >/dev/null"
+          expect_run_sh $SUCCESSFULLY "$exe_plumbing --no-verbose fetch refs/heads/main:main 2>&1 | grep 'were filtered' >/dev/null"
+        }
+        it "doesn't warn about the removed destination if advice.fetchRemovedDestinations is disabled" && {
+          expect_run_sh $WITH_FAILURE "$exe_plumbing --no-verbose -c advice.fetchRemovedDestinations=false fetch refs/heads/main:main 2>&1 | grep 'destination refs were removed' >/dev/null"
+        }
+        it "still prints other advice" && {
+          expect_run_sh $SUCCESSFULLY "$exe_plumbing --no-verbose -c advice.fetchRemovedDestinations=false fetch refs/heads/main:main 2>&1 | grep 'were filtered' >/dev/null"
+        }
+      )
+      fi
+    )
+  )
 )
 
 title "gix attributes"